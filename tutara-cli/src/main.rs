@@ -2,19 +2,22 @@ extern crate tutara_interpreter;
 
 use std::env;
 use std::io;
-use std::io::{Read, Write};
+use std::io::{IsTerminal, Read, Write};
 use std::result::Result;
 use tutara_compiler_llvm::Evaluator;
-use tutara_interpreter::{parser::Parser, Statement, Token, TokenType, Tokenizer};
+use tutara_interpreter::{
+	parser::Parser, Analyzer, Diagnostic, Error, Interpreter, LintConfig, Linter, Style, Token,
+	TokenType, Tokenizer,
+};
 
 use clap::{crate_version, App, AppSettings, Arg, ArgSettings};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{Color, ColorChoice, ColorSpec, NoColor, StandardStream, WriteColor};
 
 fn color_for_token(token: &Token) -> Option<Color> {
 	// Colors based on Nord color palette
 	match token.r#type {
-		TokenType::Integer => Some(Color::Rgb(94, 129, 172)),
-		TokenType::String => Some(Color::Rgb(163, 190, 140)),
+		TokenType::Integer | TokenType::Float => Some(Color::Rgb(94, 129, 172)),
+		TokenType::String | TokenType::Template => Some(Color::Rgb(163, 190, 140)),
 		TokenType::Boolean => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Val => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Var => Some(Color::Rgb(208, 135, 1)),
@@ -37,6 +40,8 @@ fn color_for_token(token: &Token) -> Option<Color> {
 		TokenType::If => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Else => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Match => Some(Color::Rgb(208, 135, 1)),
+		TokenType::Type => Some(Color::Rgb(208, 135, 1)),
+		TokenType::Enum => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Function => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Return => Some(Color::Rgb(208, 135, 1)),
 		TokenType::Separator => Some(Color::Rgb(236, 239, 244)),
@@ -50,6 +55,8 @@ fn color_for_token(token: &Token) -> Option<Color> {
 		TokenType::CloseParenthesis => Some(Color::Rgb(143, 188, 187)),
 		TokenType::OpenCurlyBracket => Some(Color::Rgb(143, 188, 187)),
 		TokenType::CloseCurlyBracket => Some(Color::Rgb(143, 188, 187)),
+		TokenType::OpenSquareBracket => Some(Color::Rgb(143, 188, 187)),
+		TokenType::CloseSquareBracket => Some(Color::Rgb(143, 188, 187)),
 		TokenType::Assign => Some(Color::Rgb(236, 239, 244)),
 		TokenType::AssignPlus => Some(Color::Rgb(236, 239, 244)),
 		TokenType::AssignMinus => Some(Color::Rgb(236, 239, 244)),
@@ -59,12 +66,58 @@ fn color_for_token(token: &Token) -> Option<Color> {
 		TokenType::AssignModulo => Some(Color::Rgb(236, 239, 244)),
 		TokenType::Specifier => Some(Color::Rgb(236, 239, 244)),
 		TokenType::Comment => Some(Color::Rgb(216, 222, 233)),
+		TokenType::DocComment => Some(Color::Rgb(129, 161, 193)),
 		TokenType::Dot => Some(Color::Rgb(236, 239, 244)),
 		TokenType::Arrow => Some(Color::Rgb(236, 239, 244)),
+		TokenType::Range => Some(Color::Rgb(180, 142, 173)),
+		TokenType::RangeInclusive => Some(Color::Rgb(180, 142, 173)),
 	}
 }
 
-fn run(input: &str, output: &str, format: &str) -> Result<(), std::io::Error> {
+fn color_for_style(style: Style) -> ColorSpec {
+	let mut spec = ColorSpec::new();
+
+	// Colors based on Nord color palette
+	match style {
+		Style::Error => spec.set_fg(Some(Color::Rgb(191, 97, 106))).set_bold(true),
+		Style::Warning => spec.set_fg(Some(Color::Rgb(235, 203, 139))).set_bold(true),
+		Style::Message => spec.set_bold(true),
+		Style::Gutter => spec.set_fg(Some(Color::Rgb(129, 161, 193))).set_bold(true),
+		Style::Secondary => spec.set_fg(Some(Color::Rgb(129, 161, 193))),
+		Style::Note => spec.set_fg(Some(Color::Rgb(163, 190, 140))).set_bold(true),
+		Style::Help => spec.set_fg(Some(Color::Rgb(143, 188, 187))).set_bold(true),
+		Style::Plain => &mut spec,
+	};
+
+	spec
+}
+
+// Diagnostics are only colored when they are written to a terminal
+fn diagnostic_stdout() -> StandardStream {
+	if io::stdout().is_terminal() {
+		StandardStream::stdout(ColorChoice::Auto)
+	} else {
+		StandardStream::stdout(ColorChoice::Never)
+	}
+}
+
+// Writes the error with the lines of the source it points to, outputs without colors get plain text
+fn write_diagnostic(
+	output: &mut dyn WriteColor,
+	error: &Error,
+	src: &str,
+) -> Result<(), std::io::Error> {
+	for (style, text) in Diagnostic::new(error, src).render() {
+		output.set_color(&color_for_style(style))?;
+		write!(output, "{}", text)?;
+	}
+
+	output.reset()?;
+
+	writeln!(output)
+}
+
+fn run(input: &str, output: &str, format: &str, backend: &str) -> Result<(), std::io::Error> {
 	let mut input_read: Box<dyn Read> = if input == "-" {
 		Box::new(std::io::stdin())
 	} else {
@@ -79,11 +132,11 @@ fn run(input: &str, output: &str, format: &str) -> Result<(), std::io::Error> {
 		}
 	};
 
-	let mut output_write: Box<dyn Write> = if output == "-" {
-		Box::new(std::io::stdout())
+	let mut output_write: Box<dyn WriteColor> = if output == "-" {
+		Box::new(diagnostic_stdout())
 	} else {
 		match std::fs::File::create(&output) {
-			Ok(file) => Box::new(file),
+			Ok(file) => Box::new(NoColor::new(file)),
 			Err(err) => {
 				println!("File could not be written to: {}", err);
 				return Ok(());
@@ -99,7 +152,8 @@ fn run(input: &str, output: &str, format: &str) -> Result<(), std::io::Error> {
 		},
 		"tokens" => tokenize(&mut input_read, &mut output_write),
 		"statements" => parse(&mut input_read, &mut output_write),
-		"result" => evaluate(&mut input_read, &mut output_write),
+		"check" => check(&mut input_read, &mut output_write),
+		"result" => evaluate(&mut input_read, &mut output_write, backend),
 		_ => unreachable!(),
 	}
 }
@@ -133,18 +187,31 @@ fn highlight(input: &mut dyn std::io::Read) -> Result<(), std::io::Error> {
 			if column_index < token.column {
 				let diff = token.column - column_index;
 				write!(output, "{}", " ".repeat(diff as usize))?;
-				column_index += diff;
 			}
 
-			write!(
-				output,
-				"{}",
-				line.chars()
-					.skip(token.column as usize)
-					.take(token.length as usize)
-					.collect::<String>()
-			)?;
-			column_index += token.length;
+			let mut column = token.column as usize;
+			let mut remaining = token.length as usize;
+
+			// Multi-line strings continue on the following lines
+			loop {
+				let text = line.chars().skip(column).take(remaining).collect::<String>();
+				let length = text.chars().count();
+				write!(output, "{}", text)?;
+
+				column_index = (column + length) as u32;
+				remaining -= length;
+
+				if remaining == 0 {
+					break;
+				}
+
+				// The new line character
+				remaining -= 1;
+				line_index += 1;
+				column = 0;
+				line = lines.next().unwrap();
+				writeln!(output)?;
+			}
 		}
 	}
 
@@ -154,7 +221,10 @@ fn highlight(input: &mut dyn std::io::Read) -> Result<(), std::io::Error> {
 	writeln!(output)
 }
 
-fn tokenize(input: &mut dyn std::io::Read, output: &mut dyn Write) -> Result<(), std::io::Error> {
+fn tokenize(
+	input: &mut dyn std::io::Read,
+	output: &mut dyn WriteColor,
+) -> Result<(), std::io::Error> {
 	let mut src = String::new();
 	input.read_to_string(&mut src)?;
 
@@ -163,42 +233,201 @@ fn tokenize(input: &mut dyn std::io::Read, output: &mut dyn Write) -> Result<(),
 
 	match tokens {
 		Ok(tokens) => writeln!(output, "{}", serde_json::to_string_pretty(&tokens).unwrap()),
-		Err(err) => writeln!(output, "Error: {}", err),
+		Err(err) => write_diagnostic(output, &err, &src),
 	}
 }
 
-fn parse(input: &mut dyn std::io::Read, output: &mut dyn Write) -> Result<(), std::io::Error> {
+fn parse(
+	input: &mut dyn std::io::Read,
+	output: &mut dyn WriteColor,
+) -> Result<(), std::io::Error> {
 	let mut src = String::new();
 	input.read_to_string(&mut src)?;
 
 	let tokenizer = Tokenizer::new(&src);
-	let parser = Parser::new(tokenizer.peekable());
-	let statements: Result<Vec<Statement>, tutara_interpreter::Error> = parser.collect();
+	let (statements, errors) = Parser::new(tokenizer.peekable()).parse_all();
 
-	match statements {
-		Ok(statements) => writeln!(output, "{}", serde_json::to_string_pretty(&statements).unwrap()),
-		Err(err) => writeln!(output, "Error: {}", err),
+	if errors.is_empty() {
+		writeln!(output, "{}", serde_json::to_string_pretty(&statements).unwrap())
+	} else {
+		for err in errors {
+			write_diagnostic(output, &err, &src)?;
+		}
+
+		Ok(())
 	}
 }
 
-fn evaluate(input: &mut dyn std::io::Read, output: &mut dyn Write) -> Result<(), std::io::Error> {
+fn check(
+	input: &mut dyn std::io::Read,
+	output: &mut dyn WriteColor,
+) -> Result<(), std::io::Error> {
 	let mut src = String::new();
 	input.read_to_string(&mut src)?;
 
+	let mut analyzer = Analyzer::new();
+
+	for err in find_errors(&src, &mut analyzer) {
+		write_diagnostic(output, &err, &src)?;
+	}
+
+	for warning in analyzer.take_warnings() {
+		write_diagnostic(output, &warning, &src)?;
+	}
+
+	Ok(())
+}
+
+// Every syntax error of the script, or every type error when it has no syntax errors
+fn find_errors(src: &str, analyzer: &mut Analyzer) -> Vec<Error> {
+	let tokenizer = Tokenizer::new(src);
+	let (statements, errors) = Parser::new(tokenizer.peekable()).parse_all();
+
+	// The partial tree of a script with syntax errors is not analyzed
+	if !errors.is_empty() {
+		return errors;
+	}
+
+	analyzer.check(&statements)
+}
+
+fn lint(input: &str, config: Option<&str>) -> Result<(), std::io::Error> {
+	let src = if input == "-" {
+		let mut src = String::new();
+		std::io::stdin().read_to_string(&mut src)?;
+		src
+	} else {
+		match std::fs::read_to_string(input) {
+			Ok(src) => src,
+			Err(err) => {
+				println!("File could not be read. Are you sure it exists?");
+				println!("{}", err);
+
+				return Ok(());
+			}
+		}
+	};
+
+	let config: LintConfig = match config {
+		Some(config) => match std::fs::read_to_string(config) {
+			Ok(config) => match serde_json::from_str(&config) {
+				Ok(config) => config,
+				Err(err) => {
+					println!("Lint config is invalid: {}", err);
+					return Ok(());
+				}
+			},
+			Err(err) => {
+				println!("Lint config could not be read: {}", err);
+				return Ok(());
+			}
+		},
+		None => LintConfig::default(),
+	};
+
+	let mut output = diagnostic_stdout();
 	let tokenizer = Tokenizer::new(&src);
-	let parser = Parser::new(tokenizer.peekable());
-	let evaluation = Evaluator::evaluate(parser);
+	let (statements, errors) = Parser::new(tokenizer.peekable()).parse_all();
+
+	if !errors.is_empty() {
+		for err in errors {
+			write_diagnostic(&mut output, &err, &src)?;
+		}
+
+		return Ok(());
+	}
+
+	for diagnostic in Linter::new(config).lint(&statements) {
+		write_diagnostic(&mut output, &diagnostic, &src)?;
+	}
+
+	// Type errors are reported by run, only the warnings of the analyzer are part of the lint output
+	let mut analyzer = Analyzer::new();
+	analyzer.check(&statements);
+
+	for warning in analyzer.take_warnings() {
+		write_diagnostic(&mut output, &warning, &src)?;
+	}
+
+	Ok(())
+}
+
+fn evaluate(
+	input: &mut dyn std::io::Read,
+	output: &mut dyn WriteColor,
+	backend: &str,
+) -> Result<(), std::io::Error> {
+	let mut src = String::new();
+	input.read_to_string(&mut src)?;
+
+	// The backends stop at the first error, so all of them are reported before evaluating
+	let mut analyzer = Analyzer::new();
+	let errors = find_errors(&src, &mut analyzer);
+
+	for err in errors.iter() {
+		write_diagnostic(output, err, &src)?;
+	}
+
+	for warning in analyzer.take_warnings() {
+		write_diagnostic(output, &warning, &src)?;
+	}
+
+	if !errors.is_empty() {
+		return Ok(());
+	}
+
+	let evaluation = match backend {
+		"llvm" => {
+			let tokenizer = Tokenizer::new(&src);
+			let parser = Parser::new(tokenizer.peekable());
+			Evaluator::evaluate(parser).map(|evaluation| evaluation.to_string())
+		}
+		"interpreter" => {
+			let (evaluation, lines) = interpret(src.clone());
+
+			for line in lines {
+				writeln!(output, "{}", line)?;
+			}
+
+			evaluation
+		}
+		_ => unreachable!(),
+	};
 
 	match evaluation {
 		Ok(evaluation) => writeln!(output, "{}", evaluation),
-		Err(err) => writeln!(output, "Error: {}", err),
+		Err(err) => write_diagnostic(output, &err, &src),
 	}
 }
 
-fn interactive_mode() -> Result<(), std::io::Error> {
+// The interpreter evaluates calls recursively, so it runs on a thread with a large stack to allow deep calls
+const INTERPRETER_STACK_SIZE: usize = 512 * 1024 * 1024;
+const INTERPRETER_CALL_DEPTH: usize = 10_000;
+
+fn interpret(src: String) -> (Result<String, Error>, Vec<String>) {
+	std::thread::Builder::new()
+		.stack_size(INTERPRETER_STACK_SIZE)
+		.spawn(move || {
+			let tokenizer = Tokenizer::new(&src);
+			let parser = Parser::new(tokenizer.peekable());
+			let mut interpreter = Interpreter::new().with_max_call_depth(INTERPRETER_CALL_DEPTH);
+			let evaluation = interpreter.evaluate(parser);
+
+			(
+				evaluation.map(|evaluation| evaluation.to_string()),
+				interpreter.output().to_vec(),
+			)
+		})
+		.expect("Failed to start the interpreter")
+		.join()
+		.expect("The interpreter panicked")
+}
+
+fn interactive_mode(backend: &str) -> Result<(), std::io::Error> {
 	println!("Initialized Tutara interactive mode. Use \".exit\" to leave.");
 	println!();
 
+	let mut output = diagnostic_stdout();
 	let mut buffer = Vec::new();
 
 	loop {
@@ -213,7 +442,7 @@ fn interactive_mode() -> Result<(), std::io::Error> {
 			buffer.push(input.clone());
 
 			if input.starts_with("return") {
-				evaluate(&mut buffer.join("").as_bytes(), &mut io::stdout())?;
+				evaluate(&mut buffer.join("").as_bytes(), &mut output, backend)?;
 
 				buffer.clear();
 			}
@@ -223,6 +452,15 @@ fn interactive_mode() -> Result<(), std::io::Error> {
 	Ok(())
 }
 
+fn backend_arg() -> Arg<'static> {
+	Arg::new("backend")
+		.short('b')
+		.about("Set the backend used to evaluate scripts")
+		.takes_value(true)
+		.possible_values(&["llvm", "interpreter"])
+		.default_value("llvm")
+}
+
 fn main() -> Result<(), std::io::Error> {
 	let matches = App::new("Tutara")
 		.version(crate_version!())
@@ -254,8 +492,26 @@ fn main() -> Result<(), std::io::Error> {
 						.about("Set format")
 						.setting(ArgSettings::Hidden)
 						.takes_value(true)
-						.possible_values(&["highlight", "tokens", "statements", "result"])
+						.possible_values(&["highlight", "tokens", "statements", "check", "result"])
 						.default_value("result"),
+				)
+				.arg(backend_arg()),
+		)
+		.subcommand(
+			App::new("lint")
+				.about("Lint a script")
+				.arg(
+					Arg::new("input")
+						.short('i')
+						.about("Set input file or '-' to use STDIN")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::new("config")
+						.short('c')
+						.about("Set the JSON file that configures the lint rules")
+						.takes_value(true),
 				),
 		)
 		.subcommand(
@@ -269,7 +525,11 @@ fn main() -> Result<(), std::io::Error> {
 						.required(true),
 				),
 		)
-		.subcommand(App::new("interactive").about("Start interactive mode"))
+		.subcommand(
+			App::new("interactive")
+				.about("Start interactive mode")
+				.arg(backend_arg()),
+		)
 		.get_matches();
 
 	match matches.subcommand() {
@@ -277,10 +537,17 @@ fn main() -> Result<(), std::io::Error> {
 			let input = run_matches.value_of("input").unwrap();
 			let output = run_matches.value_of("output").unwrap();
 			let format = run_matches.value_of("format").unwrap();
+			let backend = run_matches.value_of("backend").unwrap();
 
-			run(input, output, format)
+			run(input, output, format, backend)
+		}
+		Some(("lint", lint_matches)) => lint(
+			lint_matches.value_of("input").unwrap(),
+			lint_matches.value_of("config"),
+		),
+		Some(("interactive", interactive_matches)) => {
+			interactive_mode(interactive_matches.value_of("backend").unwrap())
 		}
-		Some(("interactive", _)) => interactive_mode(),
 		_ => unreachable!(),
 	}
 }
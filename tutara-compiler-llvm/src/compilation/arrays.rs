@@ -0,0 +1,538 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use inkwell::{
+	module::Linkage,
+	types::{AnyType, AnyTypeEnum, BasicType, BasicTypeEnum, PointerType, StructType},
+	values::{BasicValueEnum, FunctionValue, IntValue, PointerValue},
+	AddressSpace, IntPredicate,
+};
+use std::convert::TryFrom;
+use tutara_interpreter::{Error, ErrorCode, Expression, Token, TokenType, Type};
+
+// Arrays are heap allocated headers holding the length, the capacity and the elements.
+const ARRAY_LENGTH: u32 = 0;
+const ARRAY_CAPACITY: u32 = 1;
+const ARRAY_DATA: u32 = 2;
+const ARRAY_MINIMUM_CAPACITY: u64 = 4;
+
+impl<'a> Compiler<'a> {
+	pub fn evaluate_array(&self, elements: Vec<Expression>) -> Result<Operation<'a>, Error> {
+		let mut values: Vec<BasicValueEnum<'a>> = Vec::new();
+
+		for element in elements.into_iter() {
+			match self.evaluate_expression(element)?.basic_value() {
+				Some(value) => values.push(value),
+				None => {
+					return Err(Error::new_compiler_error(
+						"Unsupported array element".to_string(),
+					))
+				}
+			}
+		}
+
+		let element_type = match values.first() {
+			Some(value) => value.get_type(),
+			None => {
+				return Err(Error::new_compiler_error(
+					"Unable to infer type of empty array".to_string(),
+				))
+			}
+		};
+
+		if values.iter().any(|value| value.get_type() != element_type) {
+			return Err(Error::new_compiler_error(
+				"Array elements should be of the same type".to_string(),
+			));
+		}
+
+		Ok(Operation::ArrayValue(
+			self.build_array(element_type, &values)?,
+		))
+	}
+
+	// Empty array literals take their element type from the type specification
+	pub fn evaluate_empty_array(&self, type_specification: &Token) -> Result<Operation<'a>, Error> {
+		match Type::from_token(type_specification)? {
+			Type::Array(element) => Ok(Operation::ArrayValue(
+				self.build_array(self.get_type(&element)?, &[])?,
+			)),
+			r#type => Err(Error::new_compiler_error(format!(
+				"Unable to assign array to variable of type {}",
+				r#type
+			))),
+		}
+	}
+
+	pub fn evaluate_index(
+		&self,
+		indexed: Expression,
+		open_square_bracket: Token,
+		index: Expression,
+	) -> Result<Operation<'a>, Error> {
+		let element = self.build_index_pointer(indexed, open_square_bracket, index)?;
+
+		match Operation::from_basic_value(self.builder.build_load(element, "element")) {
+			Some(operation) => Ok(operation),
+			None => Err(Error::new_compiler_error("Unsupported element".to_string())),
+		}
+	}
+
+	// Pointer to the element of an array or the value of a map
+	pub fn build_index_pointer(
+		&self,
+		indexed: Expression,
+		open_square_bracket: Token,
+		index: Expression,
+	) -> Result<PointerValue<'a>, Error> {
+		Ok(
+			match (
+				self.evaluate_expression(indexed)?,
+				self.evaluate_expression(index)?,
+			) {
+				(Operation::ArrayValue(array), Operation::IntValue(index)) => {
+					self.build_array_element_pointer(array, index, open_square_bracket)
+				}
+				(Operation::MapValue(map), key) => match key.basic_value() {
+					Some(key) => self.build_map_value_pointer(map, key, open_square_bracket)?,
+					None => {
+						return Err(Error::new_compiler_error("Unsupported map key".to_string()))
+					}
+				},
+				(Operation::ArrayValue(_), _) => {
+					return Err(Error::new_compiler_error(
+						"Array index should be of type Int".to_string(),
+					))
+				}
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Unable to index expression".to_string(),
+					))
+				}
+			},
+		)
+	}
+
+	pub fn evaluate_set(
+		&self,
+		target: Expression,
+		operator: Token,
+		expression: Expression,
+	) -> Result<Operation<'a>, Error> {
+		if operator.r#type != TokenType::Assign {
+			return Err(Error::new_compiler_error(
+				"Unsupported assignment operator".to_string(),
+			));
+		}
+
+		let (indexed, open_square_bracket, index) = match target {
+			Expression::Index(indexed, open_square_bracket, index, _) => {
+				(*indexed, open_square_bracket, *index)
+			}
+			Expression::Get(object, property) => {
+				return self.set_record_field(*object, property, expression)
+			}
+			_ => {
+				return Err(Error::new_compiler_error(
+					"Unsupported assignment target".to_string(),
+				))
+			}
+		};
+
+		let indexed = self.evaluate_expression(indexed)?;
+		let index = self.evaluate_expression(index)?;
+		let value = match self.evaluate_expression(expression)?.basic_value() {
+			Some(value) => value,
+			None => {
+				return Err(Error::new_compiler_error(
+					"Unsupported assignment operation".to_string(),
+				))
+			}
+		};
+
+		match (indexed, index) {
+			(Operation::ArrayValue(array), Operation::IntValue(index)) => {
+				let element = self.build_array_element_pointer(array, index, open_square_bracket);
+
+				if value.get_type().as_any_type_enum() != element.get_type().get_element_type() {
+					return Err(Error::new_compiler_error(
+						"Unable to assign value of a different type to array element".to_string(),
+					));
+				}
+
+				self.builder.build_store(element, value);
+				Ok(Operation::NoOp)
+			}
+			(Operation::MapValue(map), key) => match key.basic_value() {
+				Some(key) => {
+					self.build_map_insert(map, key, value)?;
+					Ok(Operation::NoOp)
+				}
+				None => Err(Error::new_compiler_error("Unsupported map key".to_string())),
+			},
+			(Operation::ArrayValue(_), _) => Err(Error::new_compiler_error(
+				"Array index should be of type Int".to_string(),
+			)),
+			_ => Err(Error::new_compiler_error(
+				"Unable to index expression".to_string(),
+			)),
+		}
+	}
+}
+
+impl<'a> Compiler<'a> {
+	pub fn array_type(&self, element_type: BasicTypeEnum<'a>) -> PointerType<'a> {
+		self.array_header_type(element_type)
+			.ptr_type(AddressSpace::Generic)
+	}
+
+	pub fn get_array_element_type(&self, array_type: PointerType<'a>) -> Option<BasicTypeEnum<'a>> {
+		match array_type.get_element_type() {
+			AnyTypeEnum::StructType(header) => match header.get_field_type_at_index(ARRAY_DATA) {
+				Some(BasicTypeEnum::PointerType(data)) => {
+					BasicTypeEnum::try_from(data.get_element_type()).ok()
+				}
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	pub fn build_array(
+		&self,
+		element_type: BasicTypeEnum<'a>,
+		values: &[BasicValueEnum<'a>],
+	) -> Result<PointerValue<'a>, Error> {
+		let i64_type = self.context.i64_type();
+		let length = i64_type.const_int(values.len() as u64, false);
+		let capacity = i64_type.const_int(ARRAY_MINIMUM_CAPACITY.max(values.len() as u64), false);
+
+		let array = self
+			.builder
+			.build_malloc(self.array_header_type(element_type), "array")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+		let data = self
+			.builder
+			.build_array_malloc(element_type, capacity, "array_data")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+		for (index, value) in values.iter().enumerate() {
+			let element = unsafe {
+				self.builder.build_in_bounds_gep(
+					data,
+					&[i64_type.const_int(index as u64, false)],
+					"array_element",
+				)
+			};
+			self.builder.build_store(element, *value);
+		}
+
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_LENGTH), length);
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_CAPACITY), capacity);
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_DATA), data);
+
+		Ok(array)
+	}
+
+	pub fn get_array_length(&self, array: PointerValue<'a>) -> IntValue<'a> {
+		self.builder
+			.build_load(self.get_array_field(array, ARRAY_LENGTH), "array_length")
+			.into_int_value()
+	}
+
+	// Pointer to the element at the given index, the index is not checked
+	pub fn get_array_element(
+		&self,
+		array: PointerValue<'a>,
+		index: IntValue<'a>,
+	) -> PointerValue<'a> {
+		let data = self
+			.builder
+			.build_load(self.get_array_field(array, ARRAY_DATA), "array_data")
+			.into_pointer_value();
+
+		unsafe {
+			self.builder
+				.build_in_bounds_gep(data, &[index], "array_element")
+		}
+	}
+
+	// Pointer to the element at the given index, a runtime error is raised when the index
+	// is not within the bounds of the array.
+	pub fn build_array_element_pointer(
+		&self,
+		array: PointerValue<'a>,
+		index: IntValue<'a>,
+		open_square_bracket: Token,
+	) -> PointerValue<'a> {
+		let above_start = self.builder.build_int_compare(
+			IntPredicate::SGE,
+			index,
+			self.context.i64_type().const_zero(),
+			"array_index_above_start",
+		);
+		let below_length = self.builder.build_int_compare(
+			IntPredicate::SLT,
+			index,
+			self.get_array_length(array),
+			"array_index_below_length",
+		);
+		let in_bounds = self
+			.builder
+			.build_and(above_start, below_length, "array_index_in_bounds");
+
+		self.build_runtime_check(
+			in_bounds,
+			Error::new_runtime_error("Index out of bounds".to_string(), Some(open_square_bracket))
+				.with_code(ErrorCode::OutOfBounds),
+		);
+
+		self.get_array_element(array, index)
+	}
+
+	pub fn build_array_push(
+		&self,
+		array: PointerValue<'a>,
+		value: BasicValueEnum<'a>,
+	) -> Result<(), Error> {
+		let i64_type = self.context.i64_type();
+		let element_type = value.get_type();
+
+		if Some(element_type) != self.get_array_element_type(array.get_type()) {
+			return Err(Error::new_compiler_error(
+				"Unable to push value of a different type to array".to_string(),
+			));
+		}
+
+		let length = self.get_array_length(array);
+		let capacity_pointer = self.get_array_field(array, ARRAY_CAPACITY);
+		let capacity = self
+			.builder
+			.build_load(capacity_pointer, "array_capacity")
+			.into_int_value();
+		let full = self
+			.builder
+			.build_int_compare(IntPredicate::EQ, length, capacity, "array_full");
+
+		let current_block = self.builder.get_insert_block().unwrap();
+		let grow_block = self
+			.context
+			.insert_basic_block_after(current_block, "array_grow");
+		let push_block = self
+			.context
+			.insert_basic_block_after(grow_block, "array_push");
+		self.builder
+			.build_conditional_branch(full, grow_block, push_block);
+
+		// Double the capacity of full arrays
+		self.builder.position_at_end(grow_block);
+		let capacity =
+			self.builder
+				.build_int_mul(capacity, i64_type.const_int(2, false), "array_capacity");
+		let size =
+			self.builder
+				.build_int_mul(capacity, self.get_size_of(element_type), "array_size");
+		let data_pointer = self.get_array_field(array, ARRAY_DATA);
+		let data = self.builder.build_pointer_cast(
+			self.builder
+				.build_load(data_pointer, "array_data")
+				.into_pointer_value(),
+			self.string_type(),
+			"array_bytes",
+		);
+		let data = self
+			.builder
+			.build_call(self.get_realloc(), &[data.into(), size.into()], "realloc")
+			.try_as_basic_value()
+			.left()
+			.unwrap()
+			.into_pointer_value();
+		let data = self.builder.build_pointer_cast(
+			data,
+			element_type.ptr_type(AddressSpace::Generic),
+			"array_data",
+		);
+		self.builder.build_store(data_pointer, data);
+		self.builder.build_store(capacity_pointer, capacity);
+		self.builder.build_unconditional_branch(push_block);
+
+		// Push
+		self.builder.position_at_end(push_block);
+		self.builder
+			.build_store(self.get_array_element(array, length), value);
+		let length =
+			self.builder
+				.build_int_add(length, i64_type.const_int(1, false), "array_length");
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_LENGTH), length);
+
+		Ok(())
+	}
+
+	pub fn build_array_pop(&self, array: PointerValue<'a>, method: Token) -> BasicValueEnum<'a> {
+		let i64_type = self.context.i64_type();
+		let length = self.get_array_length(array);
+		let not_empty = self.builder.build_int_compare(
+			IntPredicate::SGT,
+			length,
+			i64_type.const_zero(),
+			"array_not_empty",
+		);
+
+		self.build_runtime_check(
+			not_empty,
+			Error::new_runtime_error(
+				"Unable to pop from an empty array".to_string(),
+				Some(method),
+			)
+			.with_code(ErrorCode::EmptyArray),
+		);
+
+		let length =
+			self.builder
+				.build_int_sub(length, i64_type.const_int(1, false), "array_length");
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_LENGTH), length);
+
+		self.builder
+			.build_load(self.get_array_element(array, length), "array_element")
+	}
+
+	// Copies the elements into a new array
+	pub fn build_array_copy(&self, array: PointerValue<'a>) -> Result<PointerValue<'a>, Error> {
+		let element_type = match self.get_array_element_type(array.get_type()) {
+			Some(element_type) => element_type,
+			None => {
+				return Err(Error::new_compiler_error(
+					"Unsupported array element".to_string(),
+				))
+			}
+		};
+
+		let i64_type = self.context.i64_type();
+		let length = self.get_array_length(array);
+		let minimum_capacity = i64_type.const_int(ARRAY_MINIMUM_CAPACITY, false);
+		let capacity = self
+			.builder
+			.build_select(
+				self.builder.build_int_compare(
+					IntPredicate::SGT,
+					length,
+					minimum_capacity,
+					"array_above_minimum",
+				),
+				length,
+				minimum_capacity,
+				"array_capacity",
+			)
+			.into_int_value();
+
+		let copy = self
+			.builder
+			.build_malloc(self.array_header_type(element_type), "array")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+		let data = self
+			.builder
+			.build_array_malloc(element_type, capacity, "array_data")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+		let size = self
+			.builder
+			.build_int_mul(length, self.get_size_of(element_type), "array_size");
+
+		self.builder
+			.build_memcpy(
+				data,
+				8,
+				self.get_array_element(array, i64_type.const_zero()),
+				8,
+				size,
+			)
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+		self.builder
+			.build_store(self.get_array_field(copy, ARRAY_LENGTH), length);
+		self.builder
+			.build_store(self.get_array_field(copy, ARRAY_CAPACITY), capacity);
+		self.builder
+			.build_store(self.get_array_field(copy, ARRAY_DATA), data);
+
+		Ok(copy)
+	}
+
+	// Removes the element at the given index and moves the following elements back, the index
+	// is not checked.
+	pub fn build_array_remove(
+		&self,
+		array: PointerValue<'a>,
+		index: IntValue<'a>,
+	) -> BasicValueEnum<'a> {
+		let i64_type = self.context.i64_type();
+		let one = i64_type.const_int(1, false);
+		let element = self.get_array_element(array, index);
+		let value = self.builder.build_load(element, "array_element");
+
+		let length = self
+			.builder
+			.build_int_sub(self.get_array_length(array), one, "array_length");
+		let next = self.builder.build_int_add(index, one, "array_next_index");
+		let remaining = self.builder.build_int_sub(length, index, "array_remaining");
+		let size =
+			self.builder
+				.build_int_mul(remaining, self.get_size_of(value.get_type()), "array_size");
+
+		self.builder
+			.build_memmove(element, 8, self.get_array_element(array, next), 8, size)
+			.unwrap();
+		self.builder
+			.build_store(self.get_array_field(array, ARRAY_LENGTH), length);
+
+		value
+	}
+
+	fn array_header_type(&self, element_type: BasicTypeEnum<'a>) -> StructType<'a> {
+		let i64_type = self.context.i64_type();
+
+		self.context.struct_type(
+			&[
+				i64_type.into(),
+				i64_type.into(),
+				element_type.ptr_type(AddressSpace::Generic).into(),
+			],
+			false,
+		)
+	}
+
+	fn get_array_field(&self, array: PointerValue<'a>, index: u32) -> PointerValue<'a> {
+		self.builder
+			.build_struct_gep(array, index, "array_field")
+			.unwrap()
+	}
+
+	// Size in bytes of the type, taken from the offset of the second element after null
+	fn get_size_of(&self, r#type: BasicTypeEnum<'a>) -> IntValue<'a> {
+		let i64_type = self.context.i64_type();
+		let null = r#type.ptr_type(AddressSpace::Generic).const_null();
+		let end = unsafe {
+			self.builder
+				.build_gep(null, &[i64_type.const_int(1, false)], "size_end")
+		};
+
+		self.builder.build_ptr_to_int(end, i64_type, "size")
+	}
+
+	fn get_realloc(&self) -> FunctionValue<'a> {
+		match self.module.get_function("realloc") {
+			Some(fun) => fun,
+			None => {
+				let string_type = self.string_type();
+				let fun_type = string_type
+					.fn_type(&[string_type.into(), self.context.i64_type().into()], false);
+
+				self.module
+					.add_function("realloc", fun_type, Some(Linkage::External))
+			}
+		}
+	}
+}
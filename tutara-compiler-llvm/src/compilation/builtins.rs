@@ -0,0 +1,114 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use tutara_interpreter::{Error, Expression, Literal, Token};
+
+impl<'a> Compiler<'a> {
+	pub fn evaluate_method(
+		&self,
+		object: Expression,
+		method: Token,
+		arguments: Vec<Expression>,
+	) -> Result<Operation<'a>, Error> {
+		use Operation::*;
+
+		let name = match &method.literal {
+			Some(Literal::String(name)) => name.clone(),
+			_ => return Err(Error::new_compiler_error("Unsupported call".to_string())),
+		};
+
+		if let Some(name) = self.get_enum_name(&object) {
+			return self.evaluate_variant(&name, &method, arguments);
+		}
+
+		let object = self.evaluate_expression(object)?;
+		let mut values: Vec<Operation> = Vec::new();
+		for argument in arguments.into_iter() {
+			values.push(self.evaluate_expression(argument)?);
+		}
+
+		match (object, name.as_str(), values.as_slice()) {
+			(IntValue(value), "toFloat", []) => Ok(FloatValue(self.build_int_to_float(*value))),
+			(FloatValue(value), "toInt", []) => {
+				Ok(IntValue(self.build_float_to_int(*value, &method)))
+			}
+			(RangeValue(range), "step", [IntValue(step)]) => {
+				Ok(RangeValue(self.build_range_step(range, *step)))
+			}
+			(ArrayValue(array), "push", [value]) => match value.basic_value() {
+				Some(value) => {
+					self.build_array_push(array, value)?;
+					Ok(NoOp)
+				}
+				None => Err(Error::new_compiler_error(
+					"Unsupported array element".to_string(),
+				)),
+			},
+			(ArrayValue(array), "pop", []) => {
+				match Operation::from_basic_value(self.build_array_pop(array, method)) {
+					Some(operation) => Ok(operation),
+					None => Err(Error::new_compiler_error(
+						"Unsupported array element".to_string(),
+					)),
+				}
+			}
+			(MapValue(map), "remove", [key]) => match key.basic_value() {
+				Some(key) => {
+					match Operation::from_basic_value(self.build_map_remove(map, key, method)?) {
+						Some(operation) => Ok(operation),
+						None => Err(Error::new_compiler_error(
+							"Unsupported map value".to_string(),
+						)),
+					}
+				}
+				None => Err(Error::new_compiler_error("Unsupported map key".to_string())),
+			},
+			(MapValue(map), "contains", [key]) => match key.basic_value() {
+				Some(key) => Ok(BoolValue(self.build_map_contains(map, key)?)),
+				None => Err(Error::new_compiler_error("Unsupported map key".to_string())),
+			},
+			(MapValue(map), "keys", []) => {
+				Ok(ArrayValue(self.build_array_copy(self.get_map_keys(map))?))
+			}
+			(MapValue(map), "values", []) => {
+				Ok(ArrayValue(self.build_array_copy(self.get_map_values(map))?))
+			}
+			_ => Err(Error::new_compiler_error(format!(
+				"Unknown function {}",
+				name
+			))),
+		}
+	}
+
+	pub fn evaluate_get(
+		&self,
+		object: Expression,
+		property: Token,
+	) -> Result<Operation<'a>, Error> {
+		use Operation::*;
+
+		if let Some(name) = self.get_enum_name(&object) {
+			return self.evaluate_variant(&name, &property, Vec::new());
+		}
+
+		let name = match property.literal {
+			Some(Literal::String(name)) => name,
+			_ => {
+				return Err(Error::new_compiler_error(
+					"Unsupported property".to_string(),
+				))
+			}
+		};
+
+		match (self.evaluate_expression(object)?, name.as_str()) {
+			(ArrayValue(array), "length") => Ok(IntValue(self.get_array_length(array))),
+			(RecordValue(record), name) => self.get_record_field(record, name),
+			(MapValue(map), "length") => {
+				Ok(IntValue(self.get_array_length(self.get_map_keys(map))))
+			}
+			_ => Err(Error::new_compiler_error(format!(
+				"Unknown property {}",
+				name
+			))),
+		}
+	}
+}
@@ -2,9 +2,20 @@ use crate::compiler::Compiler;
 use crate::operation::Operation;
 use crate::scope::ScopeContext;
 use crate::Scope;
-use tutara_interpreter::{Error, Expression, Statement};
+use inkwell::{
+	basic_block::BasicBlock,
+	values::{BasicValue, IntValue, PointerValue},
+	FloatPredicate, IntPredicate,
+};
+use tutara_interpreter::{Error, Expression, MatchArm, Statement};
 
-impl Compiler<'_> {
+type MatchArmBlocks<'a> = (
+	BasicBlock<'a>,
+	BasicBlock<'a>,
+	Vec<(String, PointerValue<'a>)>,
+); // Body , Next , Bindings
+
+impl<'a> Compiler<'a> {
 	pub fn evaluate_if(
 		&mut self,
 		condition: Expression,
@@ -58,4 +69,301 @@ impl Compiler<'_> {
 			)),
 		}
 	}
+
+	pub fn evaluate_match(
+		&mut self,
+		subject: Expression,
+		arms: Vec<MatchArm<Statement>>,
+		default: Option<Box<Statement>>,
+	) -> Result<Operation, Error> {
+		let subject = self.evaluate_expression(subject)?;
+		let parent_block = self.builder.get_insert_block().unwrap();
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(parent_block, "match_continuation_block");
+
+		for MatchArm {
+			patterns,
+			guard,
+			body,
+		} in arms
+		{
+			let (body_block, next_block, bindings) =
+				self.build_match_arm(&subject, patterns, guard)?;
+
+			// Arm
+			self.builder.position_at_end(body_block);
+			self.evaluate_match_body(body, bindings, body_block, continuation_block)?;
+
+			// Next arm
+			self.builder.position_at_end(next_block);
+		}
+
+		// Else, matches without one are exhaustive
+		let else_block = self.builder.get_insert_block().unwrap();
+		match default {
+			Some(default) => {
+				self.evaluate_match_body(*default, Vec::new(), else_block, continuation_block)?
+			}
+			None => {
+				self.builder.build_unreachable();
+			}
+		}
+
+		// Continue
+		self.builder.position_at_end(continuation_block);
+
+		Ok(Operation::NoOp)
+	}
+
+	pub fn evaluate_match_expression(
+		&self,
+		subject: Expression,
+		arms: Vec<MatchArm<Expression>>,
+		default: Option<Expression>,
+	) -> Result<Operation<'a>, Error> {
+		let subject = self.evaluate_expression(subject)?;
+		let parent_block = self.builder.get_insert_block().unwrap();
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(parent_block, "match_continuation_block");
+		let mut results: Vec<(Operation<'a>, BasicBlock<'a>)> = Vec::new();
+
+		for MatchArm {
+			patterns,
+			guard,
+			body,
+		} in arms
+		{
+			let (body_block, next_block, bindings) =
+				self.build_match_arm(&subject, patterns, guard)?;
+
+			// Arm
+			self.builder.position_at_end(body_block);
+			let result = self.evaluate_with_bindings(bindings, body)?;
+			results.push((result, self.builder.get_insert_block().unwrap()));
+			self.builder.build_unconditional_branch(continuation_block);
+
+			// Next arm
+			self.builder.position_at_end(next_block);
+		}
+
+		// Else, matches without one are exhaustive
+		match default {
+			Some(default) => {
+				let result = self.evaluate_expression(default)?;
+				results.push((result, self.builder.get_insert_block().unwrap()));
+				self.builder.build_unconditional_branch(continuation_block);
+			}
+			None => {
+				self.builder.build_unreachable();
+			}
+		}
+
+		// Continue
+		self.builder.position_at_end(continuation_block);
+
+		self.build_match_result(results)
+	}
+
+	// Branches to the returned arm block when one of the patterns and the guard match, otherwise to the next block.
+	// Values bound by a variant pattern are returned with the blocks.
+	fn build_match_arm(
+		&self,
+		subject: &Operation<'a>,
+		patterns: Vec<Expression>,
+		guard: Option<Expression>,
+	) -> Result<MatchArmBlocks<'a>, Error> {
+		let test_block = self.builder.get_insert_block().unwrap();
+		let body_block = self
+			.context
+			.insert_basic_block_after(test_block, "match_arm_block");
+		let next_block = self
+			.context
+			.insert_basic_block_after(body_block, "match_next_block");
+
+		let mut condition: Option<IntValue<'a>> = None;
+		let mut bindings: Vec<(String, PointerValue<'a>)> = Vec::new();
+		let pattern_count = patterns.len();
+
+		for pattern in patterns.into_iter() {
+			let matches = match (subject, self.get_variant_pattern(&pattern)) {
+				(Operation::EnumValue(subject), Some((name, variant, variables))) => {
+					if !variables.is_empty() && pattern_count > 1 {
+						return Err(Error::new_compiler_error(
+							"Variant patterns with bindings can not be combined with other patterns"
+								.to_string(),
+						));
+					}
+
+					let (matches, variables) =
+						self.build_variant_match(*subject, &name, &variant, variables)?;
+					bindings.extend(variables);
+
+					matches
+				}
+				_ => {
+					let pattern = self.evaluate_expression(pattern)?;
+					self.build_match_pattern(subject, pattern)?
+				}
+			};
+
+			condition = match condition {
+				Some(condition) => Some(self.builder.build_or(condition, matches, "match_or")),
+				None => Some(matches),
+			};
+		}
+
+		let condition = match condition {
+			Some(condition) => condition,
+			None => return Err(Error::new_compiler_error("Expected pattern".to_string())),
+		};
+
+		match guard {
+			Some(guard) => {
+				let guard_block = self
+					.context
+					.insert_basic_block_after(test_block, "match_guard_block");
+				self.builder
+					.build_conditional_branch(condition, guard_block, next_block);
+
+				self.builder.position_at_end(guard_block);
+				match self.evaluate_with_bindings(bindings.clone(), guard)? {
+					Operation::BoolValue(value) => {
+						self.builder
+							.build_conditional_branch(value, body_block, next_block);
+					}
+					_ => {
+						return Err(Error::new_compiler_error(
+							"Unsupported type in guard".to_string(),
+						))
+					}
+				}
+			}
+			None => {
+				self.builder
+					.build_conditional_branch(condition, body_block, next_block);
+			}
+		}
+
+		Ok((body_block, next_block, bindings))
+	}
+
+	fn evaluate_with_bindings(
+		&self,
+		bindings: Vec<(String, PointerValue<'a>)>,
+		expression: Expression,
+	) -> Result<Operation<'a>, Error> {
+		let count = bindings.len();
+		self.match_bindings.borrow_mut().extend(bindings);
+		let result = self.evaluate_expression(expression);
+
+		let mut match_bindings = self.match_bindings.borrow_mut();
+		let len = match_bindings.len();
+		match_bindings.truncate(len - count);
+
+		result
+	}
+
+	fn build_match_pattern(
+		&self,
+		subject: &Operation<'a>,
+		pattern: Operation<'a>,
+	) -> Result<IntValue<'a>, Error> {
+		use Operation::*;
+
+		match (subject, pattern) {
+			(IntValue(subject), IntValue(pattern)) => Ok(self.builder.build_int_compare(
+				IntPredicate::EQ,
+				*subject,
+				pattern,
+				"match_pattern",
+			)),
+			(FloatValue(subject), FloatValue(pattern)) => Ok(self.builder.build_float_compare(
+				FloatPredicate::OEQ,
+				*subject,
+				pattern,
+				"match_pattern",
+			)),
+			(BoolValue(subject), BoolValue(pattern)) => Ok(self.builder.build_int_compare(
+				IntPredicate::EQ,
+				*subject,
+				pattern,
+				"match_pattern",
+			)),
+			(StringValue(subject), StringValue(pattern)) => {
+				Ok(self.build_string_compare(IntPredicate::EQ, *subject, pattern))
+			}
+			(IntValue(subject), RangeValue(pattern)) => {
+				Ok(self.build_range_contains(pattern, *subject))
+			}
+			_ => Err(Error::new_compiler_error(
+				"Unsupported type in pattern".to_string(),
+			)),
+		}
+	}
+
+	fn build_match_result(
+		&self,
+		results: Vec<(Operation<'a>, BasicBlock<'a>)>,
+	) -> Result<Operation<'a>, Error> {
+		let mut incoming = Vec::new();
+
+		for (result, block) in results.iter() {
+			match result.basic_value() {
+				Some(value) => incoming.push((value, *block)),
+				None => {
+					return Err(Error::new_compiler_error(
+						"Unsupported type in match arm".to_string(),
+					))
+				}
+			}
+		}
+
+		let r#type = incoming[0].0.get_type();
+		if incoming.iter().any(|(value, _)| value.get_type() != r#type) {
+			return Err(Error::new_compiler_error(
+				"Match arms return different types".to_string(),
+			));
+		}
+
+		let phi = self.builder.build_phi(r#type, "match_result");
+		for (value, block) in incoming.iter() {
+			phi.add_incoming(&[(value as &dyn BasicValue<'a>, *block)]);
+		}
+
+		match Operation::from_basic_value(phi.as_basic_value()) {
+			Some(operation) => Ok(operation),
+			None => Err(Error::new_compiler_error(
+				"Unsupported type in match arm".to_string(),
+			)),
+		}
+	}
+
+	fn evaluate_match_body(
+		&mut self,
+		body: Statement,
+		bindings: Vec<(String, PointerValue<'a>)>,
+		body_block: BasicBlock<'a>,
+		continuation_block: BasicBlock<'a>,
+	) -> Result<(), Error> {
+		let mut scope = Scope::new(ScopeContext::If(body_block, continuation_block));
+		scope.variables.extend(bindings);
+
+		self.scope.push(scope);
+		self.evaluate_statement(body)?;
+
+		// Arms ending in a return already have a terminator
+		if self
+			.builder
+			.get_insert_block()
+			.and_then(|block| block.get_terminator())
+			.is_none()
+		{
+			self.builder.build_unconditional_branch(continuation_block);
+		}
+		self.scope.pop();
+
+		Ok(())
+	}
 }
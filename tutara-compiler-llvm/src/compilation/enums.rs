@@ -0,0 +1,256 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use inkwell::{
+	types::{BasicTypeEnum, StructType},
+	values::{BasicValueEnum, IntValue, PointerValue, StructValue},
+	IntPredicate,
+};
+use tutara_interpreter::{Error, Expression, Literal, Token, Type};
+
+// Enums are named structs holding the tag of the variant followed by the values of every variant,
+// values of the variants that are not set stay zeroed.
+pub struct EnumType<'a> {
+	pub(crate) r#type: StructType<'a>,
+	pub(crate) variants: Vec<(String, Vec<Type>)>,
+}
+
+impl Compiler<'_> {
+	pub fn evaluate_enum(
+		&mut self,
+		identifier: Token,
+		variants: Vec<(Token, Vec<Token>)>,
+	) -> Result<Operation, Error> {
+		let name = self.get_name(&identifier)?;
+		let declared = match Type::from_token(&identifier)? {
+			Type::Record(name) => {
+				self.records.contains_key(&name) || self.enums.contains_key(&name)
+			}
+			_ => true,
+		};
+
+		if declared {
+			return Err(Error::new_compiler_error(format!(
+				"Type {} is already declared",
+				name
+			)));
+		}
+
+		let mut enum_variants: Vec<(String, Vec<Type>)> = Vec::new();
+		let mut field_types: Vec<BasicTypeEnum> = vec![self.context.i64_type().into()];
+
+		for (variant, types) in variants.iter() {
+			let variant_name = self.get_name(variant)?;
+
+			if enum_variants
+				.iter()
+				.any(|(existing, _)| *existing == variant_name)
+			{
+				return Err(Error::new_compiler_error(format!(
+					"Variant {} is already declared",
+					variant_name
+				)));
+			}
+
+			let mut variant_types: Vec<Type> = Vec::new();
+
+			for r#type in types.iter() {
+				let r#type = self.get_type_from_token(r#type)?;
+				field_types.push(r#type);
+				variant_types.push(self.get_value_type(r#type)?);
+			}
+
+			enum_variants.push((variant_name, variant_types));
+		}
+
+		let r#type = self.context.opaque_struct_type(&format!("enum.{}", name));
+		r#type.set_body(&field_types, false);
+
+		self.enums.insert(
+			name,
+			EnumType {
+				r#type,
+				variants: enum_variants,
+			},
+		);
+
+		Ok(Operation::NoOp)
+	}
+}
+
+impl<'a> Compiler<'a> {
+	// Status.Ok(1) and Status.NotFound construct variants unless Status is a variable
+	pub fn get_enum_name(&self, object: &Expression) -> Option<String> {
+		match object {
+			Expression::Identifier(Token {
+				literal: Some(Literal::String(name)),
+				..
+			}) if self.enums.contains_key(name) && self.get_variable(name).is_err() => Some(name.clone()),
+			_ => None,
+		}
+	}
+
+	pub fn evaluate_variant(
+		&self,
+		name: &str,
+		variant: &Token,
+		arguments: Vec<Expression>,
+	) -> Result<Operation<'a>, Error> {
+		let variant_name = self.get_name(variant)?;
+		let r#enum = &self.enums[name];
+		let (index, offset) = self.get_variant_slots(r#enum, &variant_name)?;
+		let count = r#enum.variants[index].1.len();
+
+		if count != arguments.len() {
+			return Err(Error::new_compiler_error(format!(
+				"Variant {}.{} expects {} values but got {}",
+				name,
+				variant_name,
+				count,
+				arguments.len()
+			)));
+		}
+
+		let tag = self.context.i64_type().const_int(index as u64, false);
+		let mut value = self
+			.builder
+			.build_insert_value(r#enum.r#type.const_zero(), tag, 0, "variant")
+			.unwrap()
+			.into_struct_value();
+
+		for (slot, argument) in (offset..).zip(arguments.into_iter()) {
+			let argument = match self.evaluate_expression(argument)?.basic_value() {
+				Some(argument) => argument,
+				None => {
+					return Err(Error::new_compiler_error(
+						"Unsupported variant value".to_string(),
+					))
+				}
+			};
+
+			if Some(argument.get_type()) != r#enum.r#type.get_field_type_at_index(slot) {
+				return Err(Error::new_compiler_error(format!(
+					"Unable to use value of a different type for variant {}.{}",
+					name, variant_name
+				)));
+			}
+
+			value = self
+				.builder
+				.build_insert_value(value, argument, slot, "variant")
+				.unwrap()
+				.into_struct_value();
+		}
+
+		Ok(Operation::EnumValue(value))
+	}
+
+	// Variant patterns look like Status.NotFound or Status.Failed(message)
+	pub fn get_variant_pattern(
+		&self,
+		pattern: &Expression,
+	) -> Option<(String, Token, Vec<Expression>)> {
+		match pattern {
+			Expression::Get(object, variant) => {
+				Some((self.get_enum_name(object)?, variant.clone(), Vec::new()))
+			}
+			Expression::Call(function, _, bindings, _) => match &**function {
+				Expression::Get(object, variant) => Some((
+					self.get_enum_name(object)?,
+					variant.clone(),
+					bindings.clone(),
+				)),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	// Compares the tag of the subject and stores the values of the variant for the bindings of the pattern
+	pub fn build_variant_match(
+		&self,
+		subject: StructValue<'a>,
+		name: &str,
+		variant: &Token,
+		bindings: Vec<Expression>,
+	) -> Result<(IntValue<'a>, Vec<(String, PointerValue<'a>)>), Error> {
+		let variant_name = self.get_name(variant)?;
+		let r#enum = &self.enums[name];
+
+		if subject.get_type() != r#enum.r#type {
+			return Err(Error::new_compiler_error(format!(
+				"Unable to match against {}",
+				name
+			)));
+		}
+
+		let (index, offset) = self.get_variant_slots(r#enum, &variant_name)?;
+
+		if r#enum.variants[index].1.len() != bindings.len() {
+			return Err(Error::new_compiler_error(format!(
+				"Variant {}.{} expects {} values but got {}",
+				name,
+				variant_name,
+				r#enum.variants[index].1.len(),
+				bindings.len()
+			)));
+		}
+
+		let tag = self
+			.builder
+			.build_extract_value(subject, 0, "tag")
+			.unwrap()
+			.into_int_value();
+		let matches = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			tag,
+			self.context.i64_type().const_int(index as u64, false),
+			"match_variant",
+		);
+
+		let mut variables: Vec<(String, PointerValue<'a>)> = Vec::new();
+
+		for (slot, binding) in (offset..).zip(bindings.into_iter()) {
+			let binding = match binding {
+				Expression::Identifier(identifier) => self.get_name(&identifier)?,
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Expected identifier in variant pattern".to_string(),
+					))
+				}
+			};
+
+			let value: BasicValueEnum = self
+				.builder
+				.build_extract_value(subject, slot, &binding)
+				.unwrap();
+			let pointer = self.builder.build_alloca(value.get_type(), &binding);
+			self.builder.build_store(pointer, value);
+
+			variables.push((binding, pointer));
+		}
+
+		Ok((matches, variables))
+	}
+
+	// Index of the variant and of its first value in the enum struct
+	fn get_variant_slots(
+		&self,
+		r#enum: &EnumType<'a>,
+		variant: &str,
+	) -> Result<(usize, u32), Error> {
+		let mut offset = 1;
+
+		for (index, (name, types)) in r#enum.variants.iter().enumerate() {
+			if name == variant {
+				return Ok((index, offset));
+			}
+
+			offset += types.len() as u32;
+		}
+
+		Err(Error::new_compiler_error(format!(
+			"Unknown variant {}",
+			variant
+		)))
+	}
+}
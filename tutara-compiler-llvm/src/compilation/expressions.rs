@@ -1,19 +1,32 @@
 use crate::compiler::Compiler;
 use crate::operation::Operation;
 use inkwell::values::BasicValueEnum;
-use tutara_interpreter::{Error, Expression, Literal};
+use tutara_interpreter::{Error, Expression, Literal, Token, TokenType};
 
-impl Compiler<'_> {
-	pub fn evaluate_expression(&self, expression: Expression) -> Result<Operation, Error> {
+impl<'a> Compiler<'a> {
+	pub fn evaluate_expression(&self, expression: Expression) -> Result<Operation<'a>, Error> {
+		let span = expression.span();
+
+		self.compile_expression(expression)
+			.map_err(|error| error.with_span(span))
+	}
+
+	fn compile_expression(&self, expression: Expression) -> Result<Operation<'a>, Error> {
 		use self::Literal::*;
 		use Expression::*;
 		use Operation::*;
 
 		match expression {
 			Literal(token) => match token.literal {
-				Some(Number(number)) => {
+				Some(Integer(integer)) => {
+					let r#type = self.context.i64_type();
+					let literal = r#type.const_int(integer as u64, true);
+
+					Ok(IntValue(literal))
+				}
+				Some(Float(float)) => {
 					let r#type = self.context.f64_type();
-					let literal = r#type.const_float(f64::from(number));
+					let literal = r#type.const_float(float);
 
 					Ok(FloatValue(literal))
 				}
@@ -27,6 +40,7 @@ impl Compiler<'_> {
 
 					Ok(BoolValue(literal))
 				}
+				Some(String(string)) => Ok(StringValue(self.build_string_literal(&string)?)),
 				_ => Err(Error::new_compiler_error("Unsupported literal".to_string())),
 			},
 			Identifier(identifier) => match identifier.literal {
@@ -35,16 +49,22 @@ impl Compiler<'_> {
 						let value = self.builder.build_load(pointer, &name);
 
 						match value {
-							BasicValueEnum::FloatValue(value) => Ok(FloatValue(value)),
 							BasicValueEnum::IntValue(value) => {
-								if value.get_type().get_bit_width() == 1 {
-									Ok(Operation::BoolValue(value))
-								} else {
-									Err(Error::new_compiler_error(
+								match value.get_type().get_bit_width() {
+									1 => Ok(Operation::BoolValue(value)),
+									64 => Ok(Operation::IntValue(value)),
+									_ => Err(Error::new_compiler_error(
 										"Unsupported bit width".to_string(),
-									))
+									)),
 								}
 							}
+							BasicValueEnum::FloatValue(value) => Ok(FloatValue(value)),
+							BasicValueEnum::PointerValue(value) => {
+								Ok(Operation::from_pointer_value(value))
+							}
+							BasicValueEnum::StructValue(value) => {
+								Ok(Operation::from_struct_value(value))
+							}
 							_ => Err(Error::new_compiler_error(
 								"Unsupported type for operation".to_string(),
 							)),
@@ -62,25 +82,48 @@ impl Compiler<'_> {
 					"Unsupported identifier".to_string(),
 				)),
 			},
-			Unary(_, expression) => {
+			Unary(operator, expression) => {
 				let value = self.evaluate_expression(*expression)?;
-				match value {
-					BoolValue(value) => Ok(BoolValue(self.builder.build_not(value, "not"))),
-					FloatValue(value) => Ok(FloatValue(self.builder.build_float_neg(value, "neg"))),
+				match (&operator.r#type, value) {
+					(TokenType::Not, BoolValue(value)) => {
+						Ok(BoolValue(self.builder.build_not(value, "not")))
+					}
+					(TokenType::Minus, IntValue(value)) => {
+						Ok(IntValue(self.build_checked_int_operation(
+							"llvm.ssub.with.overflow.i64",
+							self.context.i64_type().const_zero(),
+							value,
+							&operator,
+						)))
+					}
+					(TokenType::Minus, FloatValue(value)) => {
+						Ok(FloatValue(self.builder.build_float_neg(value, "neg")))
+					}
+					(TokenType::Plus, IntValue(value)) => Ok(IntValue(value)),
+					(TokenType::Plus, FloatValue(value)) => Ok(FloatValue(value)),
 					_ => Err(Error::new_compiler_error(
 						"Unsupported type for operation".to_string(),
 					)),
 				}
 			}
 			Binary(left, operator, right) => self.evaluate_operator(*left, *right, operator),
-			Grouping(expression) => self.evaluate_expression(*expression),
+			Grouping(_, expression, _) => self.evaluate_expression(*expression),
 			Call(function, _, parameters, _) => {
 				let name = match *function {
-					Expression::Identifier(identifier) => match identifier.literal {
-						Some(String(name)) => name,
+					// Variables holding a lambda shadow functions with the same name
+					Expression::Identifier(Token {
+						literal: Some(String(ref name)),
+						..
+					}) if self.get_variable(name).is_err() => name.clone(),
+					Expression::Get(object, method) => {
+						return self.evaluate_method(*object, method, parameters)
+					}
+					function => match self.evaluate_expression(function)? {
+						ClosureValue(closure) => {
+							return self.build_closure_call(closure, parameters)
+						}
 						_ => return Err(Error::new_compiler_error("Unsupported call".to_string())),
 					},
-					_ => return Err(Error::new_compiler_error("Unsupported call".to_string())),
 				};
 
 				let fun = match self.module.get_function(&name) {
@@ -95,8 +138,16 @@ impl Compiler<'_> {
 				let mut args: Vec<_> = Vec::new();
 				for expression in parameters.into_iter() {
 					match self.evaluate_expression(expression)? {
+						IntValue(value) => args.push(value.into()),
 						FloatValue(value) => args.push(value.into()),
 						BoolValue(value) => args.push(value.into()),
+						StringValue(value) => args.push(value.into()),
+						RangeValue(value) => args.push(value.into()),
+						ArrayValue(value) => args.push(value.into()),
+						MapValue(value) => args.push(value.into()),
+						RecordValue(value) | EnumValue(value) | ClosureValue(value) => {
+							args.push(value.into())
+						}
 						_ => {
 							return Err(Error::new_compiler_error(
 								"Unsupported return operation".to_string(),
@@ -111,24 +162,48 @@ impl Compiler<'_> {
 					.try_as_basic_value()
 					.left()
 					.unwrap();
+				self.build_runtime_error_propagation();
 
 				match result {
+					BasicValueEnum::IntValue(value) => match value.get_type().get_bit_width() {
+						1 => Ok(BoolValue(value)),
+						64 => Ok(IntValue(value)),
+						_ => Err(Error::new_compiler_error(
+							"Unsupported bit width".to_string(),
+						)),
+					},
 					BasicValueEnum::FloatValue(value) => Ok(FloatValue(value)),
-					BasicValueEnum::IntValue(value) => {
-						if value.get_type().get_bit_width() == 1 {
-							Ok(BoolValue(value))
-						} else {
-							Err(Error::new_compiler_error(
-								"Unsupported bit width".to_string(),
-							))
-						}
-					}
+					BasicValueEnum::PointerValue(value) => Ok(Operation::from_pointer_value(value)),
+					BasicValueEnum::StructValue(value) => Ok(Operation::from_struct_value(value)),
 					_ => Err(Error::new_compiler_error("Unsupported result".to_string())),
 				}
 			}
-			Get(_source, _target) => Err(Error::new_compiler_error(
-				"Unsupported expression: Get".to_string(),
-			)),
+			Range(start, operator, end) => match (
+				self.evaluate_expression(*start)?,
+				self.evaluate_expression(*end)?,
+			) {
+				(IntValue(start), IntValue(end)) => Ok(RangeValue(self.build_range(
+					start,
+					end,
+					operator.r#type == TokenType::RangeInclusive,
+				))),
+				_ => Err(Error::new_compiler_error(
+					"Unsupported range bounds".to_string(),
+				)),
+			},
+			Match(_token, subject, arms, default, _) => {
+				self.evaluate_match_expression(*subject, arms, default.map(|default| *default))
+			}
+			Get(object, property) => self.evaluate_get(*object, property),
+			Array(_, elements, _) => self.evaluate_array(elements),
+			Map(_, entries, _) => self.evaluate_map(entries),
+			Record(identifier, fields, _) => self.evaluate_record_construction(identifier, fields),
+			Index(indexed, open_square_bracket, index, _) => {
+				self.evaluate_index(*indexed, open_square_bracket, *index)
+			}
+			Set(target, operator, expression) => self.evaluate_set(*target, operator, *expression),
+			Lambda(_, _, arrow, _) => self.evaluate_lambda(&arrow),
+			Template(_, parts) => self.evaluate_template(parts),
 		}
 	}
 }
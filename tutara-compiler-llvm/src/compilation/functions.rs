@@ -1,63 +1,36 @@
 use crate::compiler::*;
 use crate::operation::*;
 use crate::scope::*;
-use inkwell::{types::BasicTypeEnum, values::BasicValue};
-use tutara_interpreter::{Error, Expression, Literal, Statement, Token, TokenType};
-
-impl Compiler<'_> {
-	pub fn evaluate_function(
-		&mut self,
-		identifier: Token,
-		r#type: Option<Token>,
-		parameters: Vec<(Token, Token)>,
-		body: Box<Statement>,
-	) -> Result<Operation, Error> {
+use inkwell::{
+	types::{BasicType, BasicTypeEnum},
+	values::{BasicValue, BasicValueEnum, FunctionValue, PointerValue},
+};
+use tutara_interpreter::{Analyzer, Error, Expression, Literal, Statement, Token, Type};
+
+impl<'a> Compiler<'a> {
+	// Prototypes of the functions of a script are declared before their bodies, so calls can be compiled before
+	// the function they call
+	pub fn declare_function(
+		&self,
+		identifier: &Token,
+		r#type: &Option<Token>,
+		parameters: &[(Token, Token)],
+	) -> Result<FunctionValue<'a>, Error> {
 		// Get parameter types
 		let mut params: Vec<BasicTypeEnum> = Vec::new();
 
 		for parameter in parameters.iter() {
-			let r#type: BasicTypeEnum = match &parameter.1.literal {
-				Some(Literal::String(literal)) => match literal.as_str() {
-					"Int" => self.context.f64_type().into(),
-					"Bool" => self.context.bool_type().into(),
-					_ => {
-						return Err(Error::new_compiler_error(
-							"Invalid token/literal".to_string(),
-						))
-					}
-				},
-				_ => {
-					return Err(Error::new_compiler_error(
-						"Invalid token/literal".to_string(),
-					))
-				}
-			};
-
-			params.push(r#type)
+			params.push(self.get_type_from_token(&parameter.1)?)
 		}
 
 		// Get function return type
 		let fun_type = match r#type {
 			None => self.context.void_type().fn_type(&params, false),
-			Some(token) => match token.r#type {
-				TokenType::Identifier => match token.literal {
-					Some(Literal::String(literal)) => match literal.as_str() {
-						"Int" => self.context.f64_type().fn_type(&params, false),
-						"Bool" => self.context.bool_type().fn_type(&params, false),
-						_ => {
-							return Err(Error::new_compiler_error(
-								"Unknown return type".to_string(),
-							))
-						}
-					},
-					_ => return Err(Error::new_compiler_error("Unexpected literal".to_string())),
-				},
-				_ => return Err(Error::new_compiler_error("Unexpected token".to_string())),
-			},
+			Some(token) => self.get_type_from_token(token)?.fn_type(&params, false),
 		};
 
 		// Get function name
-		let fun_name = match identifier.literal {
+		let fun_name = match &identifier.literal {
 			Some(Literal::String(str)) => str,
 			_ => {
 				return Err(Error::new_compiler_error(
@@ -66,13 +39,31 @@ impl Compiler<'_> {
 			}
 		};
 
-		// Create function
-		let fun = self.module.add_function(fun_name.as_str(), fun_type, None);
+		match self.module.get_function(fun_name) {
+			Some(fun) if fun.count_basic_blocks() == 0 => Ok(fun),
+			_ => Ok(self.module.add_function(fun_name, fun_type, None)),
+		}
+	}
+
+	pub fn evaluate_function(
+		&mut self,
+		identifier: Token,
+		r#type: Option<Token>,
+		parameters: Vec<(Token, Token)>,
+		body: Box<Statement>,
+	) -> Result<Operation, Error> {
+		// Create function, its prototype is reused when it was declared before
+		let fun = self.declare_function(&identifier, &r#type, &parameters)?;
+		let fun_name = fun.get_name().to_string_lossy().into_owned();
 		let body_block = self
 			.context
 			.append_basic_block(fun, format!("{}_entry", fun_name).as_str());
 
-		self.scope.push(Scope::new(ScopeContext::Fun));
+		// Variables captured by lambdas are allocated on the heap as the lambdas can outlive the call
+		let mut scope = Scope::new(ScopeContext::Fun);
+		scope.captured = Analyzer::find_lambda_captures(&body).into_iter().collect();
+
+		self.scope.push(scope);
 		let current = self.builder.get_insert_block();
 		self.builder.position_at_end(body_block);
 
@@ -106,42 +97,104 @@ impl Compiler<'_> {
 		Ok(Operation::NoOp)
 	}
 
-	pub fn evaluate_return(&self, right: Option<Expression>) -> Result<Operation, Error> {
+	pub fn evaluate_return(&self, right: Option<Expression>) -> Result<Operation<'a>, Error> {
 		use Operation::*;
 
 		let len = self.scope.len();
 		for index in 0..len {
-			if let ScopeContext::Fun = self.scope[len - index - 1].scope_context {
-				if let Some(expression) = right {
-					match self.evaluate_expression(expression) {
-						Ok(FloatValue(result)) => self.builder.build_return(Some(&result)),
-						Ok(BoolValue(result)) => self.builder.build_return(Some(&result)),
-						Err(err) => return Err(err),
-						_ => {
+			let scope_context = &self.scope[len - index - 1].scope_context;
+
+			if let ScopeContext::Fun | ScopeContext::Lambda = scope_context {
+				let result = match right {
+					Some(expression) => match self.evaluate_expression(expression)?.basic_value() {
+						Some(result) => Some(result),
+						None => {
 							return Err(Error::new_compiler_error(
 								"Unsupported return operation".to_string(),
 							))
 						}
-					};
-				} else {
-					self.builder.build_return(None);
+					},
+					None => None,
+				};
+
+				// Lambdas have no declared return type, it is inferred from their returns
+				if let ScopeContext::Lambda = scope_context {
+					if let Some(returns) = self.lambda_returns.borrow_mut().last_mut() {
+						returns.push(result.map(|result| result.get_type()));
+					}
 				}
 
+				match result {
+					Some(result) => self.builder.build_return(Some(&result)),
+					None => self.builder.build_return(None),
+				};
+
 				return Ok(Operation::NoOp);
 			}
 		}
 
 		// Return on top-level program - should be removed when top-level program statement is added.
-		match right {
-			Some(expression) => match self.evaluate_expression(expression) {
-				Ok(FloatValue(result)) => Ok(Return(self.builder.build_return(Some(&result)))),
-				Ok(BoolValue(result)) => Ok(Return(self.builder.build_return(Some(&result)))),
-				Err(err) => Err(err),
-				_ => Err(Error::new_compiler_error(
-					"Unsupported return operation".to_string(),
-				)),
+		// The script returns a pointer to its result, the type is kept for the evaluator to read it.
+		let (r#type, result) = match right {
+			Some(expression) => match self.evaluate_expression(expression)? {
+				ClosureValue(_) => {
+					return Err(Error::new_compiler_error(
+						"Unable to return a lambda from the script".to_string(),
+					))
+				}
+				IntValue(value) => (Type::Int, self.build_result(value.into())?),
+				FloatValue(value) => (Type::Float, self.build_result(value.into())?),
+				BoolValue(value) => (Type::Bool, self.build_result(value.into())?),
+				StringValue(value) => (Type::String, value),
+				RangeValue(value) => (Type::Range, self.build_result(value.into())?),
+				RecordValue(value) | EnumValue(value) => (
+					self.get_value_type(value.get_type().into())?,
+					self.build_result(value.into())?,
+				),
+				ArrayValue(value) | MapValue(value) => (
+					self.get_value_type(value.get_type().into())?,
+					self.builder
+						.build_pointer_cast(value, self.string_type(), "result"),
+				),
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Unsupported return operation".to_string(),
+					))
+				}
 			},
-			None => Ok(Return(self.builder.build_return(None))),
+			None => (Type::Void, self.string_type().const_null()),
+		};
+
+		if self.contains_function(&r#type) {
+			return Err(Error::new_compiler_error(
+				"Unable to return a lambda from the script".to_string(),
+			));
 		}
+
+		let mut result_type = self.result_type.borrow_mut();
+
+		if let Some(current) = &*result_type {
+			if current != &r#type {
+				return Err(Error::new_compiler_error(format!(
+					"Script returns both {} and {}",
+					current, r#type
+				)));
+			}
+		}
+
+		*result_type = Some(r#type);
+		Ok(Return(self.builder.build_return(Some(&result))))
+	}
+
+	fn build_result(&self, value: BasicValueEnum<'a>) -> Result<PointerValue<'a>, Error> {
+		let result = self
+			.builder
+			.build_malloc(value.get_type(), "result")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+		self.builder.build_store(result, value);
+
+		Ok(self
+			.builder
+			.build_pointer_cast(result, self.string_type(), "result"))
 	}
 }
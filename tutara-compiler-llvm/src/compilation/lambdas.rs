@@ -0,0 +1,389 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use crate::scope::*;
+use inkwell::{
+	types::{AnyTypeEnum, BasicType, BasicTypeEnum, StructType},
+	values::{BasicValueEnum, FunctionValue, StructValue},
+	AddressSpace,
+};
+use std::convert::TryFrom;
+use tutara_interpreter::{Analyzer, Error, Expression, Statement, Token, Type};
+
+// Lambdas are lifted into functions taking their environment as first parameter. The environment
+// holds the values of the captured val variables and the pointers of the captured var variables.
+pub struct Lambda<'a> {
+	pub(crate) function: FunctionValue<'a>,
+	pub(crate) captures: Vec<(String, bool)>, // Vec<Capture(Name, By reference)>
+	pub(crate) environment: Option<StructType<'a>>,
+}
+
+impl<'a> Compiler<'a> {
+	// Lambdas are lifted before the statement using them is compiled, as compiling a body needs a new scope
+	pub fn lift_lambdas(&mut self, statement: &Statement) -> Result<(), Error> {
+		for lambda in Analyzer::find_lambdas(statement) {
+			if let Expression::Lambda(_, parameters, arrow, body) = lambda {
+				self.lift_lambda(arrow, parameters, body)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn lift_lambda(
+		&mut self,
+		arrow: &Token,
+		parameters: &[(Token, Token)],
+		body: &Statement,
+	) -> Result<(), Error> {
+		let mut parameter_types: Vec<BasicTypeEnum> = vec![self.string_type().into()];
+
+		for (_, parameter_type) in parameters.iter() {
+			parameter_types.push(self.get_type_from_token(parameter_type)?);
+		}
+
+		let mut captures: Vec<(String, bool)> = Vec::new();
+		let mut capture_types: Vec<BasicTypeEnum> = Vec::new();
+
+		for name in Analyzer::find_captures(parameters, body) {
+			if let Ok(pointer) = self.get_variable(&name) {
+				let by_reference = self.is_mutable(&name);
+
+				capture_types.push(if by_reference {
+					pointer.get_type().into()
+				} else {
+					BasicTypeEnum::try_from(pointer.get_type().get_element_type()).map_err(
+						|_| Error::new_compiler_error(format!("Unable to capture {}", name)),
+					)?
+				});
+				captures.push((name, by_reference));
+			}
+		}
+
+		let environment = if capture_types.is_empty() {
+			None
+		} else {
+			Some(self.context.struct_type(&capture_types, false))
+		};
+
+		// The body is compiled once to find the types it returns and once more with the return type
+		let scratch = self.module.add_function(
+			"lambda",
+			self.context.void_type().fn_type(&parameter_types, false),
+			None,
+		);
+		let returns = self.build_lambda_body(scratch, parameters, body, &captures, environment)?;
+		unsafe { scratch.delete() };
+
+		let fun_type = match self.get_lambda_result(returns)? {
+			Some(result) => result.fn_type(&parameter_types, false),
+			None => self.context.void_type().fn_type(&parameter_types, false),
+		};
+		let function = self.module.add_function("lambda", fun_type, None);
+		self.build_lambda_body(function, parameters, body, &captures, environment)?;
+
+		self.lambdas.insert(
+			(arrow.line, arrow.column),
+			Lambda {
+				function,
+				captures,
+				environment,
+			},
+		);
+
+		Ok(())
+	}
+
+	fn build_lambda_body(
+		&mut self,
+		function: FunctionValue<'a>,
+		parameters: &[(Token, Token)],
+		body: &Statement,
+		captures: &[(String, bool)],
+		environment: Option<StructType<'a>>,
+	) -> Result<Vec<Option<BasicTypeEnum<'a>>>, Error> {
+		let current = self.builder.get_insert_block();
+		let body_block = self.context.append_basic_block(function, "lambda_entry");
+		self.builder.position_at_end(body_block);
+
+		let mut scope = Scope::new(ScopeContext::Lambda);
+		scope.captured = Analyzer::find_lambda_captures(body).into_iter().collect();
+
+		let mut values = function.get_param_iter();
+		let environment_value = values.next().unwrap().into_pointer_value();
+		environment_value.set_name("environment");
+
+		// Set captured variables in lambda body
+		if let Some(environment) = environment {
+			let environment_value = self.builder.build_pointer_cast(
+				environment_value,
+				environment.ptr_type(AddressSpace::Generic),
+				"environment",
+			);
+
+			for (index, (name, by_reference)) in captures.iter().enumerate() {
+				let field = self
+					.builder
+					.build_struct_gep(environment_value, index as u32, name)
+					.unwrap();
+				let value = self.builder.build_load(field, name);
+
+				let pointer = if *by_reference {
+					scope.mutable.insert(name.clone());
+					value.into_pointer_value()
+				} else {
+					let alloca = self.builder.build_alloca(value.get_type(), name);
+					self.builder.build_store(alloca, value);
+					alloca
+				};
+
+				scope.variables.insert(name.clone(), pointer);
+			}
+		}
+
+		// Set parameters in lambda body
+		for ((parameter, _), value) in parameters.iter().zip(values) {
+			let name = self.get_name(parameter)?;
+			value.set_name(&name);
+
+			let alloca = self.builder.build_alloca(value.get_type(), &name);
+			self.builder.build_store(alloca, value);
+			scope.variables.insert(name, alloca);
+		}
+
+		self.scope.push(scope);
+		self.lambda_returns.borrow_mut().push(Vec::new());
+		self.evaluate_statement(body.clone())?;
+
+		// Lambdas without a value return at the end of their body
+		if self
+			.builder
+			.get_insert_block()
+			.and_then(|block| block.get_terminator())
+			.is_none()
+		{
+			match function.get_type().get_return_type() {
+				Some(_) => self.builder.build_unreachable(),
+				None => self.builder.build_return(None),
+			};
+		}
+
+		let returns = self.lambda_returns.borrow_mut().pop().unwrap_or_default();
+		self.scope.pop();
+
+		if let Some(current) = current {
+			self.builder.position_at_end(current);
+		}
+
+		Ok(returns)
+	}
+
+	fn get_lambda_result(
+		&self,
+		returns: Vec<Option<BasicTypeEnum<'a>>>,
+	) -> Result<Option<BasicTypeEnum<'a>>, Error> {
+		match returns.split_first() {
+			Some((first, others)) if others.iter().any(|other| other != first) => Err(
+				Error::new_compiler_error("Lambda returns different types".to_string()),
+			),
+			Some((first, _)) => Ok(*first),
+			None => Ok(None),
+		}
+	}
+
+	fn is_mutable(&self, name: &str) -> bool {
+		match self.find_variable_scope(name) {
+			Some(index) => self.scope[index].mutable.contains(name),
+			None => false,
+		}
+	}
+}
+
+impl<'a> Compiler<'a> {
+	pub fn evaluate_lambda(&self, arrow: &Token) -> Result<Operation<'a>, Error> {
+		let lambda = match self.lambdas.get(&(arrow.line, arrow.column)) {
+			Some(lambda) => lambda,
+			None => return Err(Error::new_compiler_error("Unknown lambda".to_string())),
+		};
+
+		let environment = match lambda.environment {
+			Some(environment) => {
+				let pointer = self
+					.builder
+					.build_malloc(environment, "environment")
+					.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+				for (index, (name, by_reference)) in lambda.captures.iter().enumerate() {
+					let variable = self.get_variable(name)?;
+					let field = self
+						.builder
+						.build_struct_gep(pointer, index as u32, name)
+						.unwrap();
+
+					if *by_reference {
+						self.builder.build_store(field, variable);
+					} else {
+						let value = self.builder.build_load(variable, name);
+						self.builder.build_store(field, value);
+					}
+				}
+
+				self.builder
+					.build_pointer_cast(pointer, self.string_type(), "environment")
+			}
+			None => self.string_type().const_null(),
+		};
+
+		let fun_type = lambda.function.get_type();
+		let closure_type =
+			self.closure_type(&fun_type.get_param_types()[1..], fun_type.get_return_type())?;
+
+		let closure = self
+			.builder
+			.build_insert_value(
+				closure_type.const_zero(),
+				lambda.function.as_global_value().as_pointer_value(),
+				0,
+				"closure",
+			)
+			.unwrap()
+			.into_struct_value();
+		let closure = self
+			.builder
+			.build_insert_value(closure, environment, 1, "closure")
+			.unwrap()
+			.into_struct_value();
+
+		Ok(Operation::ClosureValue(closure))
+	}
+
+	pub fn build_closure_call(
+		&self,
+		closure: StructValue<'a>,
+		arguments: Vec<Expression>,
+	) -> Result<Operation<'a>, Error> {
+		let function = self
+			.builder
+			.build_extract_value(closure, 0, "function")
+			.unwrap()
+			.into_pointer_value();
+		let environment = self
+			.builder
+			.build_extract_value(closure, 1, "environment")
+			.unwrap();
+
+		let parameter_types = match function.get_type().get_element_type() {
+			AnyTypeEnum::FunctionType(fun_type) => fun_type.get_param_types(),
+			_ => return Err(Error::new_compiler_error("Unsupported call".to_string())),
+		};
+
+		if parameter_types.len() - 1 != arguments.len() {
+			return Err(Error::new_compiler_error(format!(
+				"Lambda expects {} arguments but got {}",
+				parameter_types.len() - 1,
+				arguments.len()
+			)));
+		}
+
+		let mut args: Vec<BasicValueEnum> = vec![environment];
+
+		for (argument, parameter_type) in arguments.into_iter().zip(&parameter_types[1..]) {
+			let argument = match self.evaluate_expression(argument)?.basic_value() {
+				Some(argument) => argument,
+				None => {
+					return Err(Error::new_compiler_error(
+						"Unsupported argument".to_string(),
+					))
+				}
+			};
+
+			if argument.get_type() != *parameter_type {
+				return Err(Error::new_compiler_error(format!(
+					"Expected {} for parameter but got {}",
+					self.get_value_type(*parameter_type)?,
+					self.get_value_type(argument.get_type())?
+				)));
+			}
+
+			args.push(argument);
+		}
+
+		let result = self
+			.builder
+			.build_call(function, &args, "lambda")
+			.try_as_basic_value()
+			.left();
+		self.build_runtime_error_propagation();
+
+		match result {
+			Some(result) => match Operation::from_basic_value(result) {
+				Some(operation) => Ok(operation),
+				None => Err(Error::new_compiler_error("Unsupported result".to_string())),
+			},
+			None => Ok(Operation::NoOp),
+		}
+	}
+
+	// Closures are named after their type, like Fun<Int, Int>, and hold the function and its environment
+	pub fn closure_type(
+		&self,
+		parameters: &[BasicTypeEnum<'a>],
+		result: Option<BasicTypeEnum<'a>>,
+	) -> Result<StructType<'a>, Error> {
+		let name = Type::Function(
+			parameters
+				.iter()
+				.map(|parameter| self.get_value_type(*parameter))
+				.collect::<Result<Vec<Type>, Error>>()?,
+			Box::new(match result {
+				Some(result) => self.get_value_type(result)?,
+				None => Type::Void,
+			}),
+		)
+		.to_string();
+
+		if let Some(r#type) = self.module.get_struct_type(&name) {
+			return Ok(r#type);
+		}
+
+		let mut parameter_types: Vec<BasicTypeEnum> = vec![self.string_type().into()];
+		parameter_types.extend_from_slice(parameters);
+
+		let fun_type = match result {
+			Some(result) => result.fn_type(&parameter_types, false),
+			None => self.context.void_type().fn_type(&parameter_types, false),
+		};
+
+		let r#type = self.context.opaque_struct_type(&name);
+		r#type.set_body(
+			&[
+				fun_type.ptr_type(AddressSpace::Generic).into(),
+				self.string_type().into(),
+			],
+			false,
+		);
+
+		Ok(r#type)
+	}
+
+	// Reads the type of a closure back from the type of its function
+	pub fn get_closure_type(&self, r#type: StructType<'a>) -> Result<Type, Error> {
+		let fun_type = match r#type.get_field_type_at_index(0) {
+			Some(BasicTypeEnum::PointerType(pointer)) => match pointer.get_element_type() {
+				AnyTypeEnum::FunctionType(fun_type) => fun_type,
+				_ => return Err(Error::new_compiler_error("Unsupported type".to_string())),
+			},
+			_ => return Err(Error::new_compiler_error("Unsupported type".to_string())),
+		};
+
+		let parameters = fun_type.get_param_types()[1..]
+			.iter()
+			.map(|parameter| self.get_value_type(*parameter))
+			.collect::<Result<Vec<Type>, Error>>()?;
+		let result = match fun_type.get_return_type() {
+			Some(result) => self.get_value_type(result)?,
+			None => Type::Void,
+		};
+
+		Ok(Type::Function(parameters, Box::new(result)))
+	}
+}
@@ -1,7 +1,8 @@
 use crate::compiler::*;
 use crate::operation::*;
 use crate::scope::*;
-use tutara_interpreter::{Error, Expression, Statement};
+use inkwell::IntPredicate;
+use tutara_interpreter::{Error, Expression, Literal, Statement, Token};
 
 impl Compiler<'_> {
 	pub fn evaluate_while(
@@ -52,6 +53,164 @@ impl Compiler<'_> {
 		Ok(Operation::NoOp)
 	}
 
+	pub fn evaluate_for(
+		&mut self,
+		identifier: Expression,
+		iterable: Expression,
+		body: Box<Statement>,
+	) -> Result<Operation, Error> {
+		let name = match identifier {
+			Expression::Identifier(Token {
+				literal: Some(Literal::String(name)),
+				..
+			}) => name,
+			_ => {
+				return Err(Error::new_compiler_error(
+					"Unsupported identifier".to_string(),
+				))
+			}
+		};
+
+		let parent_block = self.builder.get_insert_block().unwrap();
+		let body_block = self
+			.context
+			.insert_basic_block_after(parent_block, "for_body_block");
+		let increment_block = self
+			.context
+			.insert_basic_block_after(body_block, "for_increment_block");
+		let evaluation_block = self
+			.context
+			.insert_basic_block_after(increment_block, "for_evaluation_block");
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(evaluation_block, "for_continuation_block");
+
+		let index_type = self.context.i64_type();
+		let index = self.builder.build_alloca(index_type, "for_index");
+		self.builder.build_store(index, index_type.const_zero());
+
+		// Strings advance the index by the number of bytes of the current character
+		let step = self.builder.build_alloca(index_type, "for_step");
+		self.builder.build_store(step, index_type.const_int(1, false));
+
+		let variable = match self.evaluate_expression(iterable)? {
+			Operation::StringValue(string) => {
+				let variable = self.builder.build_alloca(self.string_type(), &name);
+				self.builder.build_unconditional_branch(evaluation_block);
+
+				// Evaluation
+				self.builder.position_at_end(evaluation_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let condition = self.builder.build_int_compare(
+					IntPredicate::SLT,
+					current,
+					self.get_string_length(string),
+					"for_condition",
+				);
+				self.builder
+					.build_conditional_branch(condition, body_block, continuation_block);
+
+				// Element
+				self.builder.position_at_end(body_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let width = self.build_string_character_width(string, current);
+				let character = self.build_string_character(string, current, width)?;
+				self.builder.build_store(step, width);
+				self.builder.build_store(variable, character);
+
+				variable
+			}
+			Operation::RangeValue(range) => {
+				let variable = self.builder.build_alloca(self.context.i64_type(), &name);
+				self.builder.build_unconditional_branch(evaluation_block);
+
+				// Evaluation
+				self.builder.position_at_end(evaluation_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let element = self.build_range_element(range, current);
+				let condition = self.builder.build_and(
+					self.build_range_positive_step(range),
+					self.build_range_contains(range, element),
+					"for_condition",
+				);
+				self.builder
+					.build_conditional_branch(condition, body_block, continuation_block);
+
+				// Element
+				self.builder.position_at_end(body_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let element = self.build_range_element(range, current);
+				self.builder.build_store(variable, element);
+
+				variable
+			}
+			Operation::ArrayValue(array) => {
+				let element_type = match self.get_array_element_type(array.get_type()) {
+					Some(element_type) => element_type,
+					None => {
+						return Err(Error::new_compiler_error(
+							"Unsupported array element".to_string(),
+						))
+					}
+				};
+				let variable = self.builder.build_alloca(element_type, &name);
+				self.builder.build_unconditional_branch(evaluation_block);
+
+				// Evaluation
+				self.builder.position_at_end(evaluation_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let condition = self.builder.build_int_compare(
+					IntPredicate::SLT,
+					current,
+					self.get_array_length(array),
+					"for_condition",
+				);
+				self.builder
+					.build_conditional_branch(condition, body_block, continuation_block);
+
+				// Element
+				self.builder.position_at_end(body_block);
+				let current = self.builder.build_load(index, "index").into_int_value();
+				let element = self
+					.builder
+					.build_load(self.get_array_element(array, current), "element");
+				self.builder.build_store(variable, element);
+
+				variable
+			}
+			_ => {
+				return Err(Error::new_compiler_error(
+					"Unable to iterate over expression".to_string(),
+				))
+			}
+		};
+
+		// Body
+		let mut scope = Scope::new(ScopeContext::While(
+			body_block,
+			increment_block,
+			continuation_block,
+		));
+		scope.variables.insert(name, variable);
+		self.scope.push(scope);
+		self.evaluate_statement(*body)?;
+		self.builder.build_unconditional_branch(increment_block);
+		self.scope.pop();
+
+		// Increment
+		self.builder.position_at_end(increment_block);
+		let current = self.builder.build_load(index, "index").into_int_value();
+		let step = self.builder.build_load(step, "step").into_int_value();
+		let next = self.builder.build_int_add(current, step, "next_index");
+		self.builder.build_store(index, next);
+		self.builder.build_unconditional_branch(evaluation_block);
+
+		// Continue
+		self.builder.position_at_end(continuation_block);
+
+		Ok(Operation::NoOp)
+	}
+
 	pub fn evaluate_continue(&mut self) -> Result<Operation, Error> {
 		let len = self.scope.len();
 		for index in 0..len {
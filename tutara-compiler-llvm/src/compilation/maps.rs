@@ -0,0 +1,357 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use inkwell::{
+	types::{AnyTypeEnum, BasicTypeEnum, PointerType, StructType},
+	values::{BasicValue, BasicValueEnum, IntValue, PointerValue},
+	AddressSpace, IntPredicate,
+};
+use tutara_interpreter::{Error, ErrorCode, Expression, Token, Type};
+
+// Maps are heap allocated headers holding an array of keys and an array of values, a key and
+// its value share the same index.
+const MAP_KEYS: u32 = 0;
+const MAP_VALUES: u32 = 1;
+
+impl<'a> Compiler<'a> {
+	pub fn evaluate_map(
+		&self,
+		entries: Vec<(Expression, Expression)>,
+	) -> Result<Operation<'a>, Error> {
+		let mut values: Vec<(BasicValueEnum<'a>, BasicValueEnum<'a>)> = Vec::new();
+
+		for (key, value) in entries.into_iter() {
+			match (
+				self.evaluate_expression(key)?.basic_value(),
+				self.evaluate_expression(value)?.basic_value(),
+			) {
+				(Some(key), Some(value)) => values.push((key, value)),
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Unsupported map entry".to_string(),
+					))
+				}
+			}
+		}
+
+		let (key_type, value_type) = match values.first() {
+			Some((key, value)) => (key.get_type(), value.get_type()),
+			None => {
+				return Err(Error::new_compiler_error(
+					"Unable to infer type of empty map".to_string(),
+				))
+			}
+		};
+
+		let map = self.build_map(key_type, value_type)?;
+
+		for (key, value) in values.into_iter() {
+			self.build_map_insert(map, key, value)?;
+		}
+
+		Ok(Operation::MapValue(map))
+	}
+
+	// Empty map literals take their key and value type from the type specification
+	pub fn evaluate_empty_map(&self, type_specification: &Token) -> Result<Operation<'a>, Error> {
+		match Type::from_token(type_specification)? {
+			Type::Map(key, value) => Ok(Operation::MapValue(
+				self.build_map(self.get_type(&key)?, self.get_type(&value)?)?,
+			)),
+			r#type => Err(Error::new_compiler_error(format!(
+				"Unable to assign map to variable of type {}",
+				r#type
+			))),
+		}
+	}
+}
+
+impl<'a> Compiler<'a> {
+	pub fn map_type(
+		&self,
+		key_type: BasicTypeEnum<'a>,
+		value_type: BasicTypeEnum<'a>,
+	) -> PointerType<'a> {
+		self.map_header_type(key_type, value_type)
+			.ptr_type(AddressSpace::Generic)
+	}
+
+	pub fn get_map_key_type(&self, map_type: PointerType<'a>) -> Option<BasicTypeEnum<'a>> {
+		self.get_map_array_type(map_type, MAP_KEYS)
+			.and_then(|keys| self.get_array_element_type(keys))
+	}
+
+	pub fn get_map_value_type(&self, map_type: PointerType<'a>) -> Option<BasicTypeEnum<'a>> {
+		self.get_map_array_type(map_type, MAP_VALUES)
+			.and_then(|values| self.get_array_element_type(values))
+	}
+
+	pub fn build_map(
+		&self,
+		key_type: BasicTypeEnum<'a>,
+		value_type: BasicTypeEnum<'a>,
+	) -> Result<PointerValue<'a>, Error> {
+		match self.get_value_type(key_type)? {
+			Type::Int | Type::Bool | Type::String => {}
+			r#type => {
+				return Err(Error::new_compiler_error(format!(
+					"Unable to use {} as map key",
+					r#type
+				)))
+			}
+		}
+
+		let map = self
+			.builder
+			.build_malloc(self.map_header_type(key_type, value_type), "map")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+		self.builder.build_store(
+			self.get_map_field(map, MAP_KEYS),
+			self.build_array(key_type, &[])?,
+		);
+		self.builder.build_store(
+			self.get_map_field(map, MAP_VALUES),
+			self.build_array(value_type, &[])?,
+		);
+
+		Ok(map)
+	}
+
+	pub fn get_map_keys(&self, map: PointerValue<'a>) -> PointerValue<'a> {
+		self.builder
+			.build_load(self.get_map_field(map, MAP_KEYS), "map_keys")
+			.into_pointer_value()
+	}
+
+	pub fn get_map_values(&self, map: PointerValue<'a>) -> PointerValue<'a> {
+		self.builder
+			.build_load(self.get_map_field(map, MAP_VALUES), "map_values")
+			.into_pointer_value()
+	}
+
+	// Index of the key in the map or -1 when the map does not contain the key
+	pub fn build_map_find(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+	) -> Result<IntValue<'a>, Error> {
+		let i64_type = self.context.i64_type();
+		let keys = self.get_map_keys(map);
+		let length = self.get_array_length(keys);
+
+		let entry_block = self.builder.get_insert_block().unwrap();
+		let header_block = self
+			.context
+			.insert_basic_block_after(entry_block, "map_find_header");
+		let body_block = self
+			.context
+			.insert_basic_block_after(header_block, "map_find_body");
+		let end_block = self
+			.context
+			.insert_basic_block_after(body_block, "map_find_end");
+		self.builder.build_unconditional_branch(header_block);
+
+		// Header
+		self.builder.position_at_end(header_block);
+		let index = self.builder.build_phi(i64_type, "map_find_index");
+		index.add_incoming(&[(&i64_type.const_zero() as &dyn BasicValue<'a>, entry_block)]);
+		let index_value = index.as_basic_value().into_int_value();
+		let in_bounds = self.builder.build_int_compare(
+			IntPredicate::SLT,
+			index_value,
+			length,
+			"map_find_in_bounds",
+		);
+		self.builder
+			.build_conditional_branch(in_bounds, body_block, end_block);
+
+		// Body
+		self.builder.position_at_end(body_block);
+		let current = self
+			.builder
+			.build_load(self.get_array_element(keys, index_value), "map_find_key");
+		let equal = self.build_map_key_compare(current, key)?;
+		let next =
+			self.builder
+				.build_int_add(index_value, i64_type.const_int(1, false), "map_find_next");
+		index.add_incoming(&[(&next as &dyn BasicValue<'a>, body_block)]);
+		self.builder
+			.build_conditional_branch(equal, end_block, header_block);
+
+		// End
+		self.builder.position_at_end(end_block);
+		let result = self.builder.build_phi(i64_type, "map_find_result");
+		let missing = i64_type.const_all_ones();
+		result.add_incoming(&[
+			(&missing as &dyn BasicValue<'a>, header_block),
+			(&index_value as &dyn BasicValue<'a>, body_block),
+		]);
+
+		Ok(result.as_basic_value().into_int_value())
+	}
+
+	pub fn build_map_contains(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+	) -> Result<IntValue<'a>, Error> {
+		let index = self.build_map_find(map, key)?;
+
+		Ok(self.build_map_found(index))
+	}
+
+	// Pointer to the value of the key, a runtime error is raised when the key is missing
+	pub fn build_map_value_pointer(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+		open_square_bracket: Token,
+	) -> Result<PointerValue<'a>, Error> {
+		let index = self.build_map_existing_index(map, key, open_square_bracket)?;
+
+		Ok(self.get_array_element(self.get_map_values(map), index))
+	}
+
+	// Inserts the value or replaces the value of an existing key
+	pub fn build_map_insert(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+		value: BasicValueEnum<'a>,
+	) -> Result<(), Error> {
+		if Some(key.get_type()) != self.get_map_key_type(map.get_type())
+			|| Some(value.get_type()) != self.get_map_value_type(map.get_type())
+		{
+			return Err(Error::new_compiler_error(
+				"Unable to insert entry of a different type into map".to_string(),
+			));
+		}
+
+		let index = self.build_map_find(map, key)?;
+		let found = self.build_map_found(index);
+
+		let current_block = self.builder.get_insert_block().unwrap();
+		let replace_block = self
+			.context
+			.insert_basic_block_after(current_block, "map_replace");
+		let insert_block = self
+			.context
+			.insert_basic_block_after(replace_block, "map_insert");
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(insert_block, "map_continuation");
+		self.builder
+			.build_conditional_branch(found, replace_block, insert_block);
+
+		// Replace
+		self.builder.position_at_end(replace_block);
+		self.builder.build_store(
+			self.get_array_element(self.get_map_values(map), index),
+			value,
+		);
+		self.builder.build_unconditional_branch(continuation_block);
+
+		// Insert
+		self.builder.position_at_end(insert_block);
+		self.build_array_push(self.get_map_keys(map), key)?;
+		self.build_array_push(self.get_map_values(map), value)?;
+		self.builder.build_unconditional_branch(continuation_block);
+
+		// Continue
+		self.builder.position_at_end(continuation_block);
+
+		Ok(())
+	}
+
+	// Removes the key and returns its value, a runtime error is raised when the key is missing
+	pub fn build_map_remove(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+		method: Token,
+	) -> Result<BasicValueEnum<'a>, Error> {
+		let index = self.build_map_existing_index(map, key, method)?;
+		self.build_array_remove(self.get_map_keys(map), index);
+
+		Ok(self.build_array_remove(self.get_map_values(map), index))
+	}
+
+	fn build_map_existing_index(
+		&self,
+		map: PointerValue<'a>,
+		key: BasicValueEnum<'a>,
+		token: Token,
+	) -> Result<IntValue<'a>, Error> {
+		if Some(key.get_type()) != self.get_map_key_type(map.get_type()) {
+			return Err(Error::new_compiler_error(
+				"Map key is of a different type".to_string(),
+			));
+		}
+
+		let index = self.build_map_find(map, key)?;
+		let found = self.build_map_found(index);
+
+		self.build_runtime_check(
+			found,
+			Error::new_runtime_error("Key not found in map".to_string(), Some(token))
+				.with_code(ErrorCode::MissingKey),
+		);
+
+		Ok(index)
+	}
+
+	fn build_map_found(&self, index: IntValue<'a>) -> IntValue<'a> {
+		self.builder.build_int_compare(
+			IntPredicate::SGE,
+			index,
+			self.context.i64_type().const_zero(),
+			"map_found",
+		)
+	}
+
+	fn build_map_key_compare(
+		&self,
+		lhs: BasicValueEnum<'a>,
+		rhs: BasicValueEnum<'a>,
+	) -> Result<IntValue<'a>, Error> {
+		match (lhs, rhs) {
+			(BasicValueEnum::IntValue(lhs), BasicValueEnum::IntValue(rhs)) => Ok(self
+				.builder
+				.build_int_compare(IntPredicate::EQ, lhs, rhs, "map_key_equal")),
+			(BasicValueEnum::PointerValue(lhs), BasicValueEnum::PointerValue(rhs)) => {
+				Ok(self.build_string_compare(IntPredicate::EQ, lhs, rhs))
+			}
+			_ => Err(Error::new_compiler_error("Unsupported map key".to_string())),
+		}
+	}
+
+	fn map_header_type(
+		&self,
+		key_type: BasicTypeEnum<'a>,
+		value_type: BasicTypeEnum<'a>,
+	) -> StructType<'a> {
+		self.context.struct_type(
+			&[
+				self.array_type(key_type).into(),
+				self.array_type(value_type).into(),
+			],
+			false,
+		)
+	}
+
+	fn get_map_array_type(&self, map_type: PointerType<'a>, index: u32) -> Option<PointerType<'a>> {
+		match map_type.get_element_type() {
+			AnyTypeEnum::StructType(header) => match header.get_field_type_at_index(index) {
+				Some(BasicTypeEnum::PointerType(array)) => Some(array),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	fn get_map_field(&self, map: PointerValue<'a>, index: u32) -> PointerValue<'a> {
+		self.builder
+			.build_struct_gep(map, index, "map_field")
+			.unwrap()
+	}
+}
@@ -1,20 +1,53 @@
+mod arrays;
+pub use arrays::*;
+
+mod builtins;
+pub use builtins::*;
+
 mod choice;
 pub use choice::*;
 
 mod common;
 pub use common::*;
 
+mod enums;
+pub use enums::*;
+
 mod expressions;
 pub use expressions::*;
 
 mod functions;
 pub use functions::*;
 
+mod lambdas;
+pub use lambdas::*;
+
 mod loops;
 pub use loops::*;
 
+mod maps;
+pub use maps::*;
+
+mod numbers;
+pub use numbers::*;
+
 mod operations;
 pub use operations::*;
 
+mod ranges;
+pub use ranges::*;
+
+mod records;
+pub use records::*;
+
+mod runtime;
+pub use runtime::*;
+
+mod strings;
+pub use strings::*;
+
+mod types;
+pub use types::*;
+
 mod variables;
 pub use variables::*;
@@ -0,0 +1,248 @@
+use crate::compiler::Compiler;
+use inkwell::{
+	types::FunctionType,
+	values::{FloatValue, FunctionValue, IntValue},
+	FloatPredicate, IntPredicate,
+};
+use tutara_interpreter::{Error, ErrorCode, Token};
+
+impl<'a> Compiler<'a> {
+	// Intrinsics are declared once per module
+	pub fn get_intrinsic(&self, name: &str, fun_type: FunctionType<'a>) -> FunctionValue<'a> {
+		match self.module.get_function(name) {
+			Some(fun) => fun,
+			None => self.module.add_function(name, fun_type, None),
+		}
+	}
+
+	// Int operations are checked with the llvm.s*.with.overflow intrinsics,
+	// an overflow raises a runtime error instead of wrapping around.
+	pub fn build_checked_int_operation(
+		&self,
+		intrinsic: &str,
+		lhs: IntValue<'a>,
+		rhs: IntValue<'a>,
+		operator: &Token,
+	) -> IntValue<'a> {
+		let i64_type = self.context.i64_type();
+		let result_type = self
+			.context
+			.struct_type(&[i64_type.into(), self.context.bool_type().into()], false);
+		let fun = self.get_intrinsic(
+			intrinsic,
+			result_type.fn_type(&[i64_type.into(), i64_type.into()], false),
+		);
+
+		let result = self
+			.builder
+			.build_call(fun, &[lhs.into(), rhs.into()], "checked")
+			.try_as_basic_value()
+			.left()
+			.unwrap()
+			.into_struct_value();
+		let overflow = self
+			.builder
+			.build_extract_value(result, 1, "overflow")
+			.unwrap()
+			.into_int_value();
+
+		self.build_runtime_check(
+			self.builder.build_not(overflow, "no_overflow"),
+			Error::new_runtime_error("Integer overflow".to_string(), Some(operator.clone()))
+				.with_code(ErrorCode::IntegerOverflow),
+		);
+
+		self.builder
+			.build_extract_value(result, 0, "checked_value")
+			.unwrap()
+			.into_int_value()
+	}
+
+	// Division and remainder by zero and of the minimum Int by -1 are undefined in LLVM
+	pub fn build_int_division_check(&self, lhs: IntValue<'a>, rhs: IntValue<'a>, operator: &Token) {
+		let i64_type = self.context.i64_type();
+
+		let not_zero = self.builder.build_int_compare(
+			IntPredicate::NE,
+			rhs,
+			i64_type.const_zero(),
+			"division_not_zero",
+		);
+		self.build_runtime_check(
+			not_zero,
+			Error::new_runtime_error("Division by zero".to_string(), Some(operator.clone()))
+				.with_code(ErrorCode::DivisionByZero),
+		);
+
+		let minimum = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			lhs,
+			i64_type.const_int(i64::MIN as u64, true),
+			"division_minimum",
+		);
+		let minus_one = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			rhs,
+			i64_type.const_all_ones(),
+			"division_minus_one",
+		);
+		self.build_runtime_check(
+			self.builder.build_not(
+				self.builder
+					.build_and(minimum, minus_one, "division_overflow"),
+				"division_no_overflow",
+			),
+			Error::new_runtime_error("Integer overflow".to_string(), Some(operator.clone()))
+				.with_code(ErrorCode::IntegerOverflow),
+		);
+	}
+
+	// Exponentiation by squaring, every multiplication is checked for overflow
+	pub fn build_int_pow(
+		&self,
+		base: IntValue<'a>,
+		exponent: IntValue<'a>,
+		operator: &Token,
+	) -> IntValue<'a> {
+		let i64_type = self.context.i64_type();
+		let zero = i64_type.const_zero();
+		let one = i64_type.const_int(1, false);
+
+		let positive =
+			self.builder
+				.build_int_compare(IntPredicate::SGE, exponent, zero, "pow_positive");
+		self.build_runtime_check(
+			positive,
+			Error::new_runtime_error(
+				"Negative exponent on Int".to_string(),
+				Some(operator.clone()),
+			)
+			.with_code(ErrorCode::NegativeExponent),
+		);
+
+		let result_pointer = self.builder.build_alloca(i64_type, "pow_result");
+		let base_pointer = self.builder.build_alloca(i64_type, "pow_base");
+		let exponent_pointer = self.builder.build_alloca(i64_type, "pow_exponent");
+		self.builder.build_store(result_pointer, one);
+		self.builder.build_store(base_pointer, base);
+		self.builder.build_store(exponent_pointer, exponent);
+
+		let fun = self
+			.builder
+			.get_insert_block()
+			.unwrap()
+			.get_parent()
+			.unwrap();
+		let condition_block = self.context.append_basic_block(fun, "pow_condition");
+		let odd_block = self.context.append_basic_block(fun, "pow_odd");
+		let square_block = self.context.append_basic_block(fun, "pow_square");
+		let continuation_block = self.context.append_basic_block(fun, "pow_continuation");
+
+		self.builder.build_unconditional_branch(condition_block);
+
+		// Condition
+		self.builder.position_at_end(condition_block);
+		let exponent = self
+			.builder
+			.build_load(exponent_pointer, "exponent")
+			.into_int_value();
+		let remaining =
+			self.builder
+				.build_int_compare(IntPredicate::SGT, exponent, zero, "pow_remaining");
+		self.builder
+			.build_conditional_branch(remaining, odd_block, continuation_block);
+
+		// Multiply the result by the base when the exponent is odd
+		self.builder.position_at_end(odd_block);
+		let odd = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			self.builder.build_and(exponent, one, "pow_bit"),
+			one,
+			"pow_is_odd",
+		);
+		let multiply_block = self
+			.context
+			.insert_basic_block_after(odd_block, "pow_multiply");
+		let shift_block = self
+			.context
+			.insert_basic_block_after(multiply_block, "pow_shift");
+		self.builder
+			.build_conditional_branch(odd, multiply_block, shift_block);
+
+		self.builder.position_at_end(multiply_block);
+		let result = self.build_checked_int_operation(
+			"llvm.smul.with.overflow.i64",
+			self.builder
+				.build_load(result_pointer, "result")
+				.into_int_value(),
+			self.builder
+				.build_load(base_pointer, "base")
+				.into_int_value(),
+			operator,
+		);
+		self.builder.build_store(result_pointer, result);
+		self.builder.build_unconditional_branch(shift_block);
+
+		// Square the base while bits of the exponent remain
+		self.builder.position_at_end(shift_block);
+		let exponent = self
+			.builder
+			.build_right_shift(exponent, one, false, "pow_shift");
+		self.builder.build_store(exponent_pointer, exponent);
+		let remaining =
+			self.builder
+				.build_int_compare(IntPredicate::SGT, exponent, zero, "pow_remaining");
+		self.builder
+			.build_conditional_branch(remaining, square_block, continuation_block);
+
+		self.builder.position_at_end(square_block);
+		let base = self
+			.builder
+			.build_load(base_pointer, "base")
+			.into_int_value();
+		let base =
+			self.build_checked_int_operation("llvm.smul.with.overflow.i64", base, base, operator);
+		self.builder.build_store(base_pointer, base);
+		self.builder.build_unconditional_branch(condition_block);
+
+		self.builder.position_at_end(continuation_block);
+		self.builder
+			.build_load(result_pointer, "pow")
+			.into_int_value()
+	}
+
+	// Floats are truncated towards zero, NaN and values outside of the Int range can not be converted
+	pub fn build_float_to_int(&self, value: FloatValue<'a>, method: &Token) -> IntValue<'a> {
+		let f64_type = self.context.f64_type();
+		let minimum = f64_type.const_float(i64::MIN as f64);
+
+		let above_minimum = self.builder.build_float_compare(
+			FloatPredicate::OGE,
+			value,
+			minimum,
+			"to_int_above_minimum",
+		);
+		let below_maximum = self.builder.build_float_compare(
+			FloatPredicate::OLT,
+			value,
+			self.builder.build_float_neg(minimum, "maximum"),
+			"to_int_below_maximum",
+		);
+		self.build_runtime_check(
+			self.builder
+				.build_and(above_minimum, below_maximum, "to_int_in_range"),
+			Error::new_runtime_error(
+				"Unable to convert Float to Int".to_string(),
+				Some(method.clone()),
+			),
+		);
+
+		self.builder
+			.build_float_to_signed_int(value, self.context.i64_type(), "to_int")
+	}
+
+	pub fn build_int_to_float(&self, value: IntValue<'a>) -> FloatValue<'a> {
+		self.builder
+			.build_signed_int_to_float(value, self.context.f64_type(), "to_float")
+	}
+}
@@ -3,13 +3,13 @@ use crate::operation::Operation;
 use inkwell::{FloatPredicate, IntPredicate};
 use tutara_interpreter::{Error, Expression, Token, TokenType};
 
-impl Compiler<'_> {
+impl<'a> Compiler<'a> {
 	pub fn evaluate_operator(
 		&self,
 		left: Expression,
 		right: Expression,
 		operator: Token,
-	) -> Result<Operation, Error> {
+	) -> Result<Operation<'a>, Error> {
 		use FloatPredicate::*;
 		use Operation::*;
 		use TokenType::*;
@@ -19,7 +19,78 @@ impl Compiler<'_> {
 			self.evaluate_expression(right)?,
 		);
 
-		if let (FloatValue(lhs), FloatValue(rhs)) = operations {
+		if let (IntValue(lhs), IntValue(rhs)) = operations {
+			match operator.r#type {
+				Plus => Ok(IntValue(self.build_checked_int_operation(
+					"llvm.sadd.with.overflow.i64",
+					lhs,
+					rhs,
+					&operator,
+				))),
+				Minus => Ok(IntValue(self.build_checked_int_operation(
+					"llvm.ssub.with.overflow.i64",
+					lhs,
+					rhs,
+					&operator,
+				))),
+				Multiply => Ok(IntValue(self.build_checked_int_operation(
+					"llvm.smul.with.overflow.i64",
+					lhs,
+					rhs,
+					&operator,
+				))),
+				Division => {
+					self.build_int_division_check(lhs, rhs, &operator);
+					Ok(IntValue(
+						self.builder.build_int_signed_div(lhs, rhs, "tmpdiv"),
+					))
+				}
+				Modulo => {
+					self.build_int_division_check(lhs, rhs, &operator);
+					Ok(IntValue(
+						self.builder.build_int_signed_rem(lhs, rhs, "tmprem"),
+					))
+				}
+				Exponentiation => Ok(IntValue(self.build_int_pow(lhs, rhs, &operator))),
+				Equal => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::EQ,
+					lhs,
+					rhs,
+					"Equal",
+				))),
+				NotEqual => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::NE,
+					lhs,
+					rhs,
+					"NotEqual",
+				))),
+				GreaterOrEqual => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::SGE,
+					lhs,
+					rhs,
+					"GreaterOrEqual",
+				))),
+				LesserOrEqual => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::SLE,
+					lhs,
+					rhs,
+					"LesserOrEqual",
+				))),
+				Greater => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::SGT,
+					lhs,
+					rhs,
+					"Greater",
+				))),
+				Lesser => Ok(BoolValue(self.builder.build_int_compare(
+					IntPredicate::SLT,
+					lhs,
+					rhs,
+					"Lesser",
+				))),
+				_ => Err(Error::new_compiler_error("Unexpected token".to_string())),
+			}
+		} else if let (FloatValue(lhs), FloatValue(rhs)) = operations {
 			match operator.r#type {
 				Plus => Ok(FloatValue(self.builder.build_float_add(lhs, rhs, "tmpadd"))),
 				Minus => Ok(FloatValue(self.builder.build_float_sub(lhs, rhs, "tmpsub"))),
@@ -27,10 +98,9 @@ impl Compiler<'_> {
 				Division => Ok(FloatValue(self.builder.build_float_div(lhs, rhs, "tmpdiv"))),
 				Exponentiation => {
 					let f64_type = self.context.f64_type();
-					let pow_fun = self.module.add_function(
+					let pow_fun = self.get_intrinsic(
 						"llvm.pow.f64",
 						f64_type.fn_type(&[f64_type.into(), f64_type.into()], false),
-						None,
 					);
 					Ok(FloatValue(
 						self.builder
@@ -86,6 +156,35 @@ impl Compiler<'_> {
 				))),
 				_ => Err(Error::new_compiler_error("Unexpected token".to_string())),
 			}
+		} else if let (StringValue(lhs), StringValue(rhs)) = operations {
+			match operator.r#type {
+				Plus => Ok(StringValue(self.build_string_concat(lhs, rhs)?)),
+				Equal => Ok(BoolValue(self.build_string_compare(
+					IntPredicate::EQ,
+					lhs,
+					rhs,
+				))),
+				NotEqual => Ok(BoolValue(self.build_string_compare(
+					IntPredicate::NE,
+					lhs,
+					rhs,
+				))),
+				_ => Err(Error::new_compiler_error("Unexpected token".to_string())),
+			}
+		} else if let (RecordValue(lhs), RecordValue(rhs)) = operations {
+			match operator.r#type {
+				Equal => Ok(BoolValue(self.build_record_compare(
+					IntPredicate::EQ,
+					lhs,
+					rhs,
+				)?)),
+				NotEqual => Ok(BoolValue(self.build_record_compare(
+					IntPredicate::NE,
+					lhs,
+					rhs,
+				)?)),
+				_ => Err(Error::new_compiler_error("Unexpected token".to_string())),
+			}
 		} else {
 			Err(Error::new_compiler_error("Unexpected token".to_string()))
 		}
@@ -0,0 +1,136 @@
+use crate::compiler::Compiler;
+use inkwell::{
+	types::StructType,
+	values::{BasicValueEnum, IntValue, StructValue},
+	IntPredicate,
+};
+
+// Ranges are structs holding the start, end and step followed by whether the end is inclusive.
+const RANGE_START: u32 = 0;
+const RANGE_END: u32 = 1;
+const RANGE_STEP: u32 = 2;
+const RANGE_INCLUSIVE: u32 = 3;
+
+impl<'a> Compiler<'a> {
+	pub fn range_type(&self) -> StructType<'a> {
+		let i64_type = self.context.i64_type();
+
+		self.context.struct_type(
+			&[
+				i64_type.into(),
+				i64_type.into(),
+				i64_type.into(),
+				self.context.bool_type().into(),
+			],
+			false,
+		)
+	}
+
+	pub fn build_range(
+		&self,
+		start: IntValue<'a>,
+		end: IntValue<'a>,
+		inclusive: bool,
+	) -> StructValue<'a> {
+		let bool_type = self.context.bool_type();
+		let inclusive = if inclusive {
+			bool_type.const_all_ones()
+		} else {
+			bool_type.const_zero()
+		};
+
+		let range = self.range_type().get_undef();
+		let range = self.build_range_field(range, start.into(), RANGE_START);
+		let range = self.build_range_field(range, end.into(), RANGE_END);
+		let range = self.build_range_field(
+			range,
+			self.context.i64_type().const_int(1, false).into(),
+			RANGE_STEP,
+		);
+
+		self.build_range_field(range, inclusive.into(), RANGE_INCLUSIVE)
+	}
+
+	pub fn build_range_step(&self, range: StructValue<'a>, step: IntValue<'a>) -> StructValue<'a> {
+		self.build_range_field(range, step.into(), RANGE_STEP)
+	}
+
+	// Value at the given index of the range, start + index * step
+	pub fn build_range_element(&self, range: StructValue<'a>, index: IntValue<'a>) -> IntValue<'a> {
+		let offset = self.builder.build_int_mul(
+			index,
+			self.get_range_field(range, RANGE_STEP),
+			"range_offset",
+		);
+
+		self.builder.build_int_add(
+			self.get_range_field(range, RANGE_START),
+			offset,
+			"range_element",
+		)
+	}
+
+	pub fn build_range_contains(
+		&self,
+		range: StructValue<'a>,
+		value: IntValue<'a>,
+	) -> IntValue<'a> {
+		let end = self.get_range_field(range, RANGE_END);
+		let inclusive = self
+			.builder
+			.build_extract_value(range, RANGE_INCLUSIVE, "range_inclusive")
+			.unwrap()
+			.into_int_value();
+
+		let lesser = self
+			.builder
+			.build_int_compare(IntPredicate::SLT, value, end, "range_lesser");
+		let equal = self
+			.builder
+			.build_int_compare(IntPredicate::EQ, value, end, "range_equal");
+		let inclusive_equal = self
+			.builder
+			.build_and(inclusive, equal, "range_inclusive_equal");
+		let below_end = self
+			.builder
+			.build_or(lesser, inclusive_equal, "range_below_end");
+		let above_start = self.builder.build_int_compare(
+			IntPredicate::SGE,
+			value,
+			self.get_range_field(range, RANGE_START),
+			"range_above_start",
+		);
+
+		self.builder
+			.build_and(above_start, below_end, "range_contains")
+	}
+
+	// Ranges without a positive step are empty when iterated
+	pub fn build_range_positive_step(&self, range: StructValue<'a>) -> IntValue<'a> {
+		self.builder.build_int_compare(
+			IntPredicate::SGT,
+			self.get_range_field(range, RANGE_STEP),
+			self.context.i64_type().const_zero(),
+			"range_positive_step",
+		)
+	}
+
+	fn get_range_field(&self, range: StructValue<'a>, index: u32) -> IntValue<'a> {
+		self.builder
+			.build_extract_value(range, index, "range_field")
+			.unwrap()
+			.into_int_value()
+	}
+
+	fn build_range_field(
+		&self,
+		range: StructValue<'a>,
+		value: BasicValueEnum<'a>,
+		index: u32,
+	) -> StructValue<'a> {
+		self.builder
+			.build_insert_value(range, value, index, "range")
+			.unwrap()
+			.into_struct_value()
+	}
+}
@@ -0,0 +1,318 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use inkwell::{
+	types::{AnyTypeEnum, BasicTypeEnum, StructType},
+	values::{BasicValueEnum, IntValue, PointerValue, StructValue},
+	FloatPredicate, IntPredicate,
+};
+use std::convert::TryFrom;
+use tutara_interpreter::{Error, Expression, Literal, Token, Type};
+
+// Records are named structs holding their fields in declaration order.
+pub struct RecordType<'a> {
+	pub(crate) r#type: StructType<'a>,
+	pub(crate) fields: Vec<(String, Type)>,
+}
+
+impl Compiler<'_> {
+	pub fn evaluate_record(
+		&mut self,
+		identifier: Token,
+		fields: Vec<(Token, Token)>,
+	) -> Result<Operation, Error> {
+		let name = self.get_name(&identifier)?;
+		let declared = match Type::from_token(&identifier)? {
+			Type::Record(name) => {
+				self.records.contains_key(&name) || self.enums.contains_key(&name)
+			}
+			_ => true,
+		};
+
+		if declared {
+			return Err(Error::new_compiler_error(format!(
+				"Type {} is already declared",
+				name
+			)));
+		}
+
+		let mut record_fields: Vec<(String, Type)> = Vec::new();
+		let mut field_types: Vec<BasicTypeEnum> = Vec::new();
+
+		for (field, field_type) in fields.iter() {
+			let field_name = self.get_name(field)?;
+
+			if record_fields
+				.iter()
+				.any(|(existing, _)| *existing == field_name)
+			{
+				return Err(Error::new_compiler_error(format!(
+					"Field {} is already declared",
+					field_name
+				)));
+			}
+
+			let field_type = self.get_type_from_token(field_type)?;
+			field_types.push(field_type);
+			record_fields.push((field_name, self.get_value_type(field_type)?));
+		}
+
+		let r#type = self.context.opaque_struct_type(&name);
+		r#type.set_body(&field_types, false);
+
+		self.records.insert(
+			name,
+			RecordType {
+				r#type,
+				fields: record_fields,
+			},
+		);
+
+		Ok(Operation::NoOp)
+	}
+}
+
+impl<'a> Compiler<'a> {
+	pub fn evaluate_record_construction(
+		&self,
+		identifier: Token,
+		fields: Vec<(Token, Expression)>,
+	) -> Result<Operation<'a>, Error> {
+		let name = self.get_name(&identifier)?;
+		let record = match self.records.get(&name) {
+			Some(record) => record,
+			None => return Err(Error::new_compiler_error(format!("Unknown type {}", name))),
+		};
+
+		let mut values: Vec<(String, BasicValueEnum<'a>)> = Vec::new();
+
+		for (field, expression) in fields.into_iter() {
+			let field_name = self.get_name(&field)?;
+			let index = self.get_record_field_index(record, &field_name)?;
+
+			if values.iter().any(|(existing, _)| *existing == field_name) {
+				return Err(Error::new_compiler_error(format!(
+					"Field {} is already assigned",
+					field_name
+				)));
+			}
+
+			let value = match self.evaluate_expression(expression)?.basic_value() {
+				Some(value) => value,
+				None => {
+					return Err(Error::new_compiler_error(
+						"Unsupported field value".to_string(),
+					))
+				}
+			};
+
+			if Some(value.get_type()) != record.r#type.get_field_type_at_index(index) {
+				return Err(Error::new_compiler_error(format!(
+					"Unable to assign value of a different type to field {}",
+					field_name
+				)));
+			}
+
+			values.push((field_name, value));
+		}
+
+		let mut value = record.r#type.get_undef();
+
+		for (index, (field_name, _)) in record.fields.iter().enumerate() {
+			let field = match values.iter().find(|(assigned, _)| assigned == field_name) {
+				Some((_, field)) => *field,
+				None => {
+					return Err(Error::new_compiler_error(format!(
+						"Missing field {} for {}",
+						field_name, name
+					)))
+				}
+			};
+
+			value = self
+				.builder
+				.build_insert_value(value, field, index as u32, "record")
+				.unwrap()
+				.into_struct_value();
+		}
+
+		Ok(Operation::RecordValue(value))
+	}
+
+	pub fn get_record_field(
+		&self,
+		record: StructValue<'a>,
+		property: &str,
+	) -> Result<Operation<'a>, Error> {
+		let index =
+			self.get_record_field_index(self.get_record_type(record.get_type())?, property)?;
+		let value = self
+			.builder
+			.build_extract_value(record, index, property)
+			.unwrap();
+
+		match Operation::from_basic_value(value) {
+			Some(operation) => Ok(operation),
+			None => Err(Error::new_compiler_error("Unsupported field".to_string())),
+		}
+	}
+
+	// Records are values, a field is assigned by storing into the variable, array element or map value
+	// that holds the record
+	pub fn set_record_field(
+		&self,
+		object: Expression,
+		property: Token,
+		expression: Expression,
+	) -> Result<Operation<'a>, Error> {
+		let mut path = vec![self.get_name(&property)?];
+		let mut current = object;
+
+		let mut pointer = loop {
+			match current {
+				Expression::Get(object, property) => {
+					path.push(self.get_name(&property)?);
+					current = *object;
+				}
+				Expression::Identifier(identifier) => {
+					break self.get_variable(&self.get_name(&identifier)?)?
+				}
+				Expression::Index(indexed, open_square_bracket, index, _) => {
+					break self.build_index_pointer(*indexed, open_square_bracket, *index)?
+				}
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Unable to assign to a field of a temporary value".to_string(),
+					))
+				}
+			}
+		};
+
+		for name in path.iter().rev() {
+			let index = match pointer.get_type().get_element_type() {
+				AnyTypeEnum::StructType(r#type) => {
+					self.get_record_field_index(self.get_record_type(r#type)?, name)?
+				}
+				_ => {
+					return Err(Error::new_compiler_error(format!(
+						"Unable to get field {}",
+						name
+					)))
+				}
+			};
+
+			pointer = self.builder.build_struct_gep(pointer, index, name).unwrap();
+		}
+
+		let value = match self.evaluate_expression(expression)?.basic_value() {
+			Some(value) => value,
+			None => {
+				return Err(Error::new_compiler_error(
+					"Unsupported assignment operation".to_string(),
+				))
+			}
+		};
+
+		if BasicTypeEnum::try_from(pointer.get_type().get_element_type()) != Ok(value.get_type()) {
+			return Err(Error::new_compiler_error(
+				"Unable to assign value of a different type to field".to_string(),
+			));
+		}
+
+		self.builder.build_store(pointer, value);
+		Ok(Operation::NoOp)
+	}
+
+	// Records are equal when all of their fields are equal
+	pub fn build_record_compare(
+		&self,
+		predicate: IntPredicate,
+		lhs: StructValue<'a>,
+		rhs: StructValue<'a>,
+	) -> Result<IntValue<'a>, Error> {
+		if lhs.get_type() != rhs.get_type() {
+			return Err(Error::new_compiler_error(
+				"Unable to compare records of a different type".to_string(),
+			));
+		}
+
+		let mut equal = self.context.bool_type().const_all_ones();
+
+		for index in 0..lhs.get_type().count_fields() {
+			let field_equal = match (
+				self.builder
+					.build_extract_value(lhs, index, "lhs_field")
+					.unwrap(),
+				self.builder
+					.build_extract_value(rhs, index, "rhs_field")
+					.unwrap(),
+			) {
+				(BasicValueEnum::FloatValue(lhs), BasicValueEnum::FloatValue(rhs)) => self
+					.builder
+					.build_float_compare(FloatPredicate::OEQ, lhs, rhs, "field_equal"),
+				(BasicValueEnum::IntValue(lhs), BasicValueEnum::IntValue(rhs)) => self
+					.builder
+					.build_int_compare(IntPredicate::EQ, lhs, rhs, "field_equal"),
+				(BasicValueEnum::PointerValue(lhs), BasicValueEnum::PointerValue(rhs))
+					if self.is_string(lhs) =>
+				{
+					self.build_string_compare(IntPredicate::EQ, lhs, rhs)
+				}
+				(BasicValueEnum::StructValue(lhs), BasicValueEnum::StructValue(rhs))
+					if lhs.get_type().get_name().is_some() =>
+				{
+					self.build_record_compare(IntPredicate::EQ, lhs, rhs)?
+				}
+				_ => {
+					return Err(Error::new_compiler_error(
+						"Unable to compare record fields".to_string(),
+					))
+				}
+			};
+
+			equal = self.builder.build_and(equal, field_equal, "record_equal");
+		}
+
+		match predicate {
+			IntPredicate::EQ => Ok(equal),
+			_ => Ok(self.builder.build_not(equal, "record_not_equal")),
+		}
+	}
+
+	pub fn get_record_type(&self, r#type: StructType<'a>) -> Result<&RecordType<'a>, Error> {
+		let name = match r#type.get_name() {
+			Some(name) => name.to_string_lossy().into_owned(),
+			None => return Err(Error::new_compiler_error("Expected record".to_string())),
+		};
+
+		match self.records.get(&name) {
+			Some(record) => Ok(record),
+			None => Err(Error::new_compiler_error(format!("Unknown type {}", name))),
+		}
+	}
+
+	fn get_record_field_index(&self, record: &RecordType<'a>, field: &str) -> Result<u32, Error> {
+		match record.fields.iter().position(|(name, _)| name == field) {
+			Some(index) => Ok(index as u32),
+			None => Err(Error::new_compiler_error(format!(
+				"Unknown field {}",
+				field
+			))),
+		}
+	}
+
+	fn is_string(&self, value: PointerValue<'a>) -> bool {
+		matches!(
+			Operation::from_pointer_value(value),
+			Operation::StringValue(_)
+		)
+	}
+
+	pub(crate) fn get_name(&self, token: &Token) -> Result<String, Error> {
+		match &token.literal {
+			Some(Literal::String(name)) => Ok(name.clone()),
+			_ => Err(Error::new_compiler_error(
+				"Invalid token/literal".to_string(),
+			)),
+		}
+	}
+}
@@ -0,0 +1,130 @@
+use crate::compiler::Compiler;
+use inkwell::{
+	module::Linkage,
+	types::BasicTypeEnum,
+	values::{FunctionValue, GlobalValue, IntValue},
+};
+use tutara_interpreter::Error;
+
+pub const RUNTIME_ERROR_FUNCTION: &str = "tutara_runtime_error";
+const RUNTIME_ERROR_FLAG: &str = "tutara_runtime_error_flag";
+
+impl<'a> Compiler<'a> {
+	// Returns early when the condition does not hold, the error is reported to the evaluator
+	// by its index in the runtime errors of the compiler.
+	pub fn build_runtime_check(&self, condition: IntValue<'a>, error: Error) {
+		let current_block = self.builder.get_insert_block().unwrap();
+		let error_block = self
+			.context
+			.insert_basic_block_after(current_block, "runtime_error");
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(error_block, "runtime_continuation");
+		self.builder
+			.build_conditional_branch(condition, continuation_block, error_block);
+
+		// Error
+		self.builder.position_at_end(error_block);
+		let mut runtime_errors = self.runtime_errors.borrow_mut();
+		let index = self
+			.context
+			.i32_type()
+			.const_int(runtime_errors.len() as u64, false);
+		runtime_errors.push(error);
+
+		self.builder.build_call(
+			self.get_runtime_error_function(),
+			&[index.into()],
+			"runtime_error",
+		);
+		self.builder.build_store(
+			self.get_runtime_error_flag().as_pointer_value(),
+			self.context.bool_type().const_all_ones(),
+		);
+		self.build_runtime_error_return();
+
+		// Continue
+		self.builder.position_at_end(continuation_block);
+	}
+
+	// Callers return early as well when a called function ran into a runtime error
+	pub fn build_runtime_error_propagation(&self) {
+		let flag = self
+			.builder
+			.build_load(
+				self.get_runtime_error_flag().as_pointer_value(),
+				"runtime_error_flag",
+			)
+			.into_int_value();
+
+		let current_block = self.builder.get_insert_block().unwrap();
+		let error_block = self
+			.context
+			.insert_basic_block_after(current_block, "runtime_error_propagation");
+		let continuation_block = self
+			.context
+			.insert_basic_block_after(error_block, "runtime_continuation");
+		self.builder
+			.build_conditional_branch(flag, error_block, continuation_block);
+
+		self.builder.position_at_end(error_block);
+		self.build_runtime_error_return();
+
+		self.builder.position_at_end(continuation_block);
+	}
+
+	// The returned value is never read as the evaluator reports the error instead
+	fn build_runtime_error_return(&self) {
+		let fun = self
+			.builder
+			.get_insert_block()
+			.unwrap()
+			.get_parent()
+			.unwrap();
+
+		match fun.get_type().get_return_type() {
+			Some(BasicTypeEnum::FloatType(r#type)) => {
+				self.builder.build_return(Some(&r#type.get_undef()))
+			}
+			Some(BasicTypeEnum::IntType(r#type)) => {
+				self.builder.build_return(Some(&r#type.get_undef()))
+			}
+			Some(BasicTypeEnum::PointerType(r#type)) => {
+				self.builder.build_return(Some(&r#type.get_undef()))
+			}
+			Some(BasicTypeEnum::StructType(r#type)) => {
+				self.builder.build_return(Some(&r#type.get_undef()))
+			}
+			Some(_) => self.builder.build_unreachable(),
+			None => self.builder.build_return(None),
+		};
+	}
+
+	fn get_runtime_error_function(&self) -> FunctionValue<'a> {
+		match self.module.get_function(RUNTIME_ERROR_FUNCTION) {
+			Some(fun) => fun,
+			None => {
+				let fun_type = self
+					.context
+					.void_type()
+					.fn_type(&[self.context.i32_type().into()], false);
+
+				self.module
+					.add_function(RUNTIME_ERROR_FUNCTION, fun_type, Some(Linkage::External))
+			}
+		}
+	}
+
+	fn get_runtime_error_flag(&self) -> GlobalValue<'a> {
+		match self.module.get_global(RUNTIME_ERROR_FLAG) {
+			Some(flag) => flag,
+			None => {
+				let bool_type = self.context.bool_type();
+				let flag = self.module.add_global(bool_type, None, RUNTIME_ERROR_FLAG);
+				flag.set_initializer(&bool_type.const_zero());
+
+				flag
+			}
+		}
+	}
+}
@@ -0,0 +1,335 @@
+use crate::compiler::Compiler;
+use crate::operation::Operation;
+use inkwell::{
+	module::Linkage,
+	types::PointerType,
+	values::{BasicValueEnum, FunctionValue, IntValue, PointerValue},
+	AddressSpace, IntPredicate,
+};
+use tutara_interpreter::{Error, Expression};
+
+// Strings are heap allocated blocks starting with an i64 length followed by the bytes.
+const STRING_HEADER_SIZE: u64 = 8;
+
+// Numbers are converted to text by the evaluator, so they are written the same way as by the interpreter.
+// The conversions write at most STRING_CONVERSION_SIZE bytes and return the length of the text.
+pub const INT_TO_STRING_FUNCTION: &str = "tutara_int_to_string";
+pub const FLOAT_TO_STRING_FUNCTION: &str = "tutara_float_to_string";
+pub const STRING_CONVERSION_SIZE: u64 = 32;
+
+impl<'a> Compiler<'a> {
+	pub fn string_type(&self) -> PointerType<'a> {
+		self.context.i8_type().ptr_type(AddressSpace::Generic)
+	}
+
+	pub fn build_string_literal(&self, value: &str) -> Result<PointerValue<'a>, Error> {
+		let length = self.context.i64_type().const_int(value.len() as u64, false);
+		let string = self.build_string_alloc(length)?;
+		let source = self
+			.builder
+			.build_global_string_ptr(value, "string_literal")
+			.as_pointer_value();
+
+		self.build_string_copy(self.get_string_data(string), source, length)?;
+
+		Ok(string)
+	}
+
+	pub fn build_string_concat(
+		&self,
+		lhs: PointerValue<'a>,
+		rhs: PointerValue<'a>,
+	) -> Result<PointerValue<'a>, Error> {
+		let lhs_length = self.get_string_length(lhs);
+		let rhs_length = self.get_string_length(rhs);
+		let length = self
+			.builder
+			.build_int_add(lhs_length, rhs_length, "concat_length");
+		let string = self.build_string_alloc(length)?;
+		let data = self.get_string_data(string);
+
+		self.build_string_copy(data, self.get_string_data(lhs), lhs_length)?;
+		let rhs_data = unsafe {
+			self.builder
+				.build_in_bounds_gep(data, &[lhs_length], "concat_rhs_data")
+		};
+		self.build_string_copy(rhs_data, self.get_string_data(rhs), rhs_length)?;
+
+		Ok(string)
+	}
+
+	pub fn evaluate_template(&self, parts: Vec<Expression>) -> Result<Operation<'a>, Error> {
+		let mut text = self.build_string_literal("")?;
+
+		for part in parts.into_iter() {
+			let part = self.evaluate_expression(part)?;
+			text = self.build_string_concat(text, self.build_to_string(part)?)?;
+		}
+
+		Ok(Operation::StringValue(text))
+	}
+
+	// Ints, floats, bools and strings are converted to text, other values can not be interpolated
+	pub fn build_to_string(&self, operation: Operation<'a>) -> Result<PointerValue<'a>, Error> {
+		match operation {
+			Operation::StringValue(value) => Ok(value),
+			Operation::BoolValue(value) => Ok(self
+				.builder
+				.build_select(
+					value,
+					self.build_string_literal("true")?,
+					self.build_string_literal("false")?,
+					"bool_text",
+				)
+				.into_pointer_value()),
+			Operation::IntValue(value) => {
+				self.build_number_to_string(INT_TO_STRING_FUNCTION, value.into())
+			}
+			Operation::FloatValue(value) => {
+				self.build_number_to_string(FLOAT_TO_STRING_FUNCTION, value.into())
+			}
+			operation => match operation.basic_value() {
+				Some(value) => Err(Error::new_compiler_error(format!(
+					"Unable to interpolate {}",
+					self.get_value_type(value.get_type())?
+				))),
+				None => Err(Error::new_compiler_error(
+					"Unable to interpolate value".to_string(),
+				)),
+			},
+		}
+	}
+
+	fn build_number_to_string(
+		&self,
+		function: &str,
+		value: BasicValueEnum<'a>,
+	) -> Result<PointerValue<'a>, Error> {
+		let i64_type = self.context.i64_type();
+		let string = self.build_string_alloc(i64_type.const_int(STRING_CONVERSION_SIZE, false))?;
+
+		let fun = match self.module.get_function(function) {
+			Some(fun) => fun,
+			None => {
+				let fun_type =
+					i64_type.fn_type(&[value.get_type(), self.string_type().into()], false);
+
+				self.module
+					.add_function(function, fun_type, Some(Linkage::External))
+			}
+		};
+
+		let length = self
+			.builder
+			.build_call(
+				fun,
+				&[value, self.get_string_data(string).into()],
+				"to_string",
+			)
+			.try_as_basic_value()
+			.left()
+			.unwrap()
+			.into_int_value();
+
+		let length_pointer = self.builder.build_pointer_cast(
+			string,
+			i64_type.ptr_type(AddressSpace::Generic),
+			"length_pointer",
+		);
+		self.builder.build_store(length_pointer, length);
+
+		Ok(string)
+	}
+
+	// Strings are iterated per character like in the interpreter, the number of bytes of a UTF-8 encoded
+	// character follows from its first byte
+	pub fn build_string_character_width(
+		&self,
+		string: PointerValue<'a>,
+		index: IntValue<'a>,
+	) -> IntValue<'a> {
+		let i64_type = self.context.i64_type();
+		let first_byte_pointer = unsafe {
+			self.builder.build_in_bounds_gep(
+				self.get_string_data(string),
+				&[index],
+				"first_byte_pointer",
+			)
+		};
+		let first_byte = self
+			.builder
+			.build_load(first_byte_pointer, "first_byte")
+			.into_int_value();
+		let first_byte = self
+			.builder
+			.build_int_z_extend(first_byte, i64_type, "first_byte");
+
+		let mut width = i64_type.const_int(4, false);
+
+		for (bound, bound_width) in [(0xF0, 3), (0xE0, 2), (0x80, 1)].iter() {
+			let below = self.builder.build_int_compare(
+				IntPredicate::ULT,
+				first_byte,
+				i64_type.const_int(*bound, false),
+				"below_bound",
+			);
+			width = self
+				.builder
+				.build_select(
+					below,
+					i64_type.const_int(*bound_width, false),
+					width,
+					"character_width",
+				)
+				.into_int_value();
+		}
+
+		width
+	}
+
+	pub fn build_string_character(
+		&self,
+		string: PointerValue<'a>,
+		index: IntValue<'a>,
+		width: IntValue<'a>,
+	) -> Result<PointerValue<'a>, Error> {
+		let character = self.build_string_alloc(width)?;
+		let source = unsafe {
+			self.builder.build_in_bounds_gep(
+				self.get_string_data(string),
+				&[index],
+				"character_data",
+			)
+		};
+
+		self.build_string_copy(self.get_string_data(character), source, width)?;
+
+		Ok(character)
+	}
+
+	pub fn build_string_compare(
+		&self,
+		predicate: IntPredicate,
+		lhs: PointerValue<'a>,
+		rhs: PointerValue<'a>,
+	) -> IntValue<'a> {
+		let lhs_length = self.get_string_length(lhs);
+		let rhs_length = self.get_string_length(rhs);
+		let equal_length = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			lhs_length,
+			rhs_length,
+			"equal_length",
+		);
+
+		// Only compare the bytes when both strings have the same length
+		let compare_length = self
+			.builder
+			.build_select(
+				equal_length,
+				lhs_length,
+				self.context.i64_type().const_zero(),
+				"compare_length",
+			)
+			.into_int_value();
+		let difference = self
+			.builder
+			.build_call(
+				self.get_memcmp(),
+				&[
+					self.get_string_data(lhs).into(),
+					self.get_string_data(rhs).into(),
+					compare_length.into(),
+				],
+				"memcmp",
+			)
+			.try_as_basic_value()
+			.left()
+			.unwrap()
+			.into_int_value();
+		let equal_data = self.builder.build_int_compare(
+			IntPredicate::EQ,
+			difference,
+			self.context.i32_type().const_zero(),
+			"equal_data",
+		);
+		let equal = self.builder.build_and(equal_length, equal_data, "equal");
+
+		match predicate {
+			IntPredicate::NE => self.builder.build_not(equal, "not_equal"),
+			_ => equal,
+		}
+	}
+
+	pub fn get_string_length(&self, string: PointerValue<'a>) -> IntValue<'a> {
+		let length_pointer = self.builder.build_pointer_cast(
+			string,
+			self.context.i64_type().ptr_type(AddressSpace::Generic),
+			"length_pointer",
+		);
+
+		self.builder
+			.build_load(length_pointer, "length")
+			.into_int_value()
+	}
+
+	pub fn get_string_data(&self, string: PointerValue<'a>) -> PointerValue<'a> {
+		let header_size = self.context.i64_type().const_int(STRING_HEADER_SIZE, false);
+
+		unsafe {
+			self.builder
+				.build_in_bounds_gep(string, &[header_size], "data")
+		}
+	}
+
+	fn build_string_alloc(&self, length: IntValue<'a>) -> Result<PointerValue<'a>, Error> {
+		let header_size = self.context.i64_type().const_int(STRING_HEADER_SIZE, false);
+		let size = self.builder.build_int_add(length, header_size, "size");
+		let string = self
+			.builder
+			.build_array_malloc(self.context.i8_type(), size, "string")
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+		let length_pointer = self.builder.build_pointer_cast(
+			string,
+			self.context.i64_type().ptr_type(AddressSpace::Generic),
+			"length_pointer",
+		);
+		self.builder.build_store(length_pointer, length);
+
+		Ok(string)
+	}
+
+	fn build_string_copy(
+		&self,
+		destination: PointerValue<'a>,
+		source: PointerValue<'a>,
+		length: IntValue<'a>,
+	) -> Result<(), Error> {
+		self.builder
+			.build_memcpy(destination, 1, source, 1, length)
+			.map_err(|err| Error::new_compiler_error(err.to_string()))?;
+
+		Ok(())
+	}
+
+	fn get_memcmp(&self) -> FunctionValue<'a> {
+		match self.module.get_function("memcmp") {
+			Some(fun) => fun,
+			None => {
+				let string_type = self.string_type();
+				let fun_type = self.context.i32_type().fn_type(
+					&[
+						string_type.into(),
+						string_type.into(),
+						self.context.i64_type().into(),
+					],
+					false,
+				);
+
+				self.module
+					.add_function("memcmp", fun_type, Some(Linkage::External))
+			}
+		}
+	}
+}
@@ -0,0 +1,107 @@
+use crate::compiler::Compiler;
+use inkwell::types::{AnyTypeEnum, BasicTypeEnum};
+use tutara_interpreter::{Error, Token, Type};
+
+impl<'a> Compiler<'a> {
+	pub fn get_type(&self, r#type: &Type) -> Result<BasicTypeEnum<'a>, Error> {
+		match r#type {
+			Type::Int => Ok(self.context.i64_type().into()),
+			Type::Float => Ok(self.context.f64_type().into()),
+			Type::Bool => Ok(self.context.bool_type().into()),
+			Type::String => Ok(self.string_type().into()),
+			Type::Range => Ok(self.range_type().into()),
+			Type::Array(element) => Ok(self.array_type(self.get_type(element)?).into()),
+			Type::Map(key, value) => Ok(self
+				.map_type(self.get_type(key)?, self.get_type(value)?)
+				.into()),
+			Type::Record(name) => match (self.records.get(name), self.enums.get(name)) {
+				(Some(record), _) => Ok(record.r#type.into()),
+				(None, Some(r#enum)) => Ok(r#enum.r#type.into()),
+				(None, None) => Err(Error::new_compiler_error(format!("Unknown type {}", name))),
+			},
+			Type::Enum(name) => match self.enums.get(name) {
+				Some(r#enum) => Ok(r#enum.r#type.into()),
+				None => Err(Error::new_compiler_error(format!("Unknown type {}", name))),
+			},
+			Type::Function(parameters, result) => {
+				let parameters = parameters
+					.iter()
+					.map(|parameter| self.get_type(parameter))
+					.collect::<Result<Vec<BasicTypeEnum>, Error>>()?;
+				let result = match **result {
+					Type::Void => None,
+					ref result => Some(self.get_type(result)?),
+				};
+
+				Ok(self.closure_type(&parameters, result)?.into())
+			}
+			Type::Void => Err(Error::new_compiler_error(
+				"Void can not be used as value type".to_string(),
+			)),
+		}
+	}
+
+	// Lambdas can not be read by the evaluator, so they can not be part of the result of a script
+	pub fn contains_function(&self, r#type: &Type) -> bool {
+		match r#type {
+			Type::Function(..) => true,
+			Type::Array(element) => self.contains_function(element),
+			Type::Map(key, value) => self.contains_function(key) || self.contains_function(value),
+			Type::Record(name) => self.records[name]
+				.fields
+				.iter()
+				.any(|(_, field)| self.contains_function(field)),
+			Type::Enum(name) => self.enums[name]
+				.variants
+				.iter()
+				.flat_map(|(_, types)| types.iter())
+				.any(|r#type| self.contains_function(r#type)),
+			_ => false,
+		}
+	}
+
+	pub fn get_type_from_token(&self, token: &Token) -> Result<BasicTypeEnum<'a>, Error> {
+		self.get_type(&Type::from_token(token)?)
+	}
+
+	// Maps the LLVM type of a value back to the type known by the interpreter
+	pub fn get_value_type(&self, r#type: BasicTypeEnum<'a>) -> Result<Type, Error> {
+		match r#type {
+			BasicTypeEnum::IntType(r#type) if r#type.get_bit_width() == 64 => Ok(Type::Int),
+			BasicTypeEnum::IntType(r#type) if r#type.get_bit_width() == 1 => Ok(Type::Bool),
+			BasicTypeEnum::FloatType(_) => Ok(Type::Float),
+			BasicTypeEnum::StructType(r#type) => match r#type.get_name() {
+				Some(name) if name.to_bytes().starts_with(b"Fun<") => self.get_closure_type(r#type),
+				Some(name) => {
+					let name = name.to_string_lossy();
+
+					match name.strip_prefix("enum.") {
+						Some(name) => Ok(Type::Enum(name.to_string())),
+						None => Ok(Type::Record(name.into_owned())),
+					}
+				}
+				None => Ok(Type::Range),
+			},
+			BasicTypeEnum::PointerType(pointer) => match pointer.get_element_type() {
+				AnyTypeEnum::StructType(header) if header.count_fields() == 2 => {
+					match (
+						self.get_map_key_type(pointer),
+						self.get_map_value_type(pointer),
+					) {
+						(Some(key), Some(value)) => Ok(Type::Map(
+							Box::new(self.get_value_type(key)?),
+							Box::new(self.get_value_type(value)?),
+						)),
+						_ => Err(Error::new_compiler_error("Unsupported type".to_string())),
+					}
+				}
+				AnyTypeEnum::StructType(_) => match self.get_array_element_type(pointer) {
+					Some(element) => Ok(Type::Array(Box::new(self.get_value_type(element)?))),
+					None => Err(Error::new_compiler_error("Unsupported type".to_string())),
+				},
+				_ => Ok(Type::String),
+			},
+			_ => Err(Error::new_compiler_error("Unsupported type".to_string())),
+		}
+	}
+}
@@ -1,20 +1,73 @@
 use crate::compiler::*;
 use crate::operation::*;
-use inkwell::values::PointerValue;
-use tutara_interpreter::{Error, Expression, Token, TokenType, Literal};
+use crate::scope::*;
+use inkwell::{types::BasicType, values::PointerValue};
+use tutara_interpreter::{Error, Expression, Literal, Token, TokenType};
 
-impl Compiler<'_> {
-	pub fn get_variable(&self, name: &str) -> Result<PointerValue, Error> {
-		let len = self.scope.len();
-		for index in 0..len {
-			if let Some(pointer) = self.scope[len - index - 1].variables.get(name) {
-				return Ok(*pointer);
+impl<'a> Compiler<'a> {
+	pub fn get_variable(&self, name: &str) -> Result<PointerValue<'a>, Error> {
+		// Values bound by variant patterns in guards and match expressions
+		if let Some((_, pointer)) = self
+			.match_bindings
+			.borrow()
+			.iter()
+			.rev()
+			.find(|(binding, _)| binding == name)
+		{
+			return Ok(*pointer);
+		}
+
+		match self.find_variable_scope(name) {
+			Some(index) => Ok(self.scope[index].variables[name]),
+			None => Err(Error::new_compiler_error(
+				"Variable not found in this scope".to_string(),
+			)),
+		}
+	}
+
+	// Functions and lambdas can not use the variables of their caller, lambdas capture them instead
+	pub(crate) fn find_variable_scope(&self, name: &str) -> Option<usize> {
+		for index in (0..self.scope.len()).rev() {
+			if self.scope[index].variables.contains_key(name) {
+				return Some(index);
+			}
+
+			if let ScopeContext::Fun | ScopeContext::Lambda = self.scope[index].scope_context {
+				break;
 			}
 		}
 
-		Err(Error::new_compiler_error(
-			"Variable not found in this scope".to_string(),
-		))
+		None
+	}
+
+	// Var variables captured by the lambdas of a function body can outlive the call,
+	// so they are allocated on the heap.
+	fn build_variable<T: BasicType<'a>>(
+		&self,
+		r#type: T,
+		name: &str,
+		mutable: bool,
+	) -> Result<PointerValue<'a>, Error> {
+		let captured = mutable
+			&& self
+				.scope
+				.iter()
+				.rev()
+				.find(|scope| {
+					matches!(
+						scope.scope_context,
+						ScopeContext::Fun | ScopeContext::Lambda
+					)
+				})
+				.map_or(false, |scope| scope.captured.contains(name));
+
+		if captured {
+			self.builder
+				.build_malloc(r#type, name)
+				.map_err(|err| Error::new_compiler_error(err.to_string()))
+		} else {
+			Ok(self.builder.build_alloca(r#type, name))
+		}
 	}
 
 	pub fn set_variable(
@@ -22,7 +75,7 @@ impl Compiler<'_> {
 		name: &str,
 		operator: Token,
 		expression: Expression,
-	) -> Result<Operation, Error> {
+	) -> Result<Operation<'a>, Error> {
 		use Operation::*;
 
 		let value = if operator.r#type == TokenType::Assign {
@@ -35,6 +88,10 @@ impl Compiler<'_> {
 
 		match self.get_variable(&name) {
 			Ok(pointer) => match value {
+				IntValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
 				FloatValue(value) => {
 					self.builder.build_store(pointer, value);
 					Ok(NoOp)
@@ -43,6 +100,26 @@ impl Compiler<'_> {
 					self.builder.build_store(pointer, value);
 					Ok(NoOp)
 				}
+				StringValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
+				RangeValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
+				ArrayValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
+				MapValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
+				RecordValue(value) | EnumValue(value) | ClosureValue(value) => {
+					self.builder.build_store(pointer, value);
+					Ok(NoOp)
+				}
 				_ => Err(Error::new_compiler_error(
 					"Unsupported assignment operation".to_string(),
 				)),
@@ -55,7 +132,12 @@ impl Compiler<'_> {
 }
 
 impl Compiler<'_> {
-	pub fn evaluate_declaration(&mut self, expression: Expression) -> Result<Operation, Error> {
+	pub fn evaluate_declaration(
+		&mut self,
+		mutability: Token,
+		type_specification: Option<Token>,
+		expression: Expression,
+	) -> Result<Operation, Error> {
 		use self::Literal::*;
 		use Expression::*;
 		use Operation::*;
@@ -63,15 +145,61 @@ impl Compiler<'_> {
 		match expression {
 			Assignment(identifier, _operator, inner_expression) => match identifier.literal {
 				Some(String(name)) => {
+					let mutable = mutability.r#type == TokenType::Var;
 					let pointer;
 
-					match self.evaluate_expression(*inner_expression)? {
+					let value = match (&type_specification, *inner_expression) {
+						(Some(token), Array(_, elements, _)) if elements.is_empty() => {
+							self.evaluate_empty_array(token)?
+						}
+						(Some(token), Map(_, entries, _)) if entries.is_empty() => {
+							self.evaluate_empty_map(token)?
+						}
+						(_, inner_expression) => self.evaluate_expression(inner_expression)?,
+					};
+
+					if let (Some(token), Some(value)) = (&type_specification, value.basic_value()) {
+						let expected = self.get_type_from_token(token)?;
+
+						if value.get_type() != expected {
+							return Err(Error::new_compiler_error(format!(
+								"Can not assign {} to variable of type {}",
+								self.get_value_type(value.get_type())?,
+								self.get_value_type(expected)?
+							)));
+						}
+					}
+
+					match value {
+						IntValue(value) => {
+							pointer =
+								self.build_variable(self.context.i64_type(), &name, mutable)?;
+							self.builder.build_store(pointer, value);
+						}
 						FloatValue(value) => {
-							pointer = self.builder.build_alloca(self.context.f64_type(), &name);
+							pointer =
+								self.build_variable(self.context.f64_type(), &name, mutable)?;
 							self.builder.build_store(pointer, value);
 						}
 						BoolValue(value) => {
-							pointer = self.builder.build_alloca(self.context.bool_type(), &name);
+							pointer =
+								self.build_variable(self.context.bool_type(), &name, mutable)?;
+							self.builder.build_store(pointer, value);
+						}
+						StringValue(value) => {
+							pointer = self.build_variable(self.string_type(), &name, mutable)?;
+							self.builder.build_store(pointer, value);
+						}
+						RangeValue(value) => {
+							pointer = self.build_variable(self.range_type(), &name, mutable)?;
+							self.builder.build_store(pointer, value);
+						}
+						ArrayValue(value) | MapValue(value) => {
+							pointer = self.build_variable(value.get_type(), &name, mutable)?;
+							self.builder.build_store(pointer, value);
+						}
+						RecordValue(value) | EnumValue(value) | ClosureValue(value) => {
+							pointer = self.build_variable(value.get_type(), &name, mutable)?;
 							self.builder.build_store(pointer, value);
 						}
 						_ => {
@@ -82,6 +210,12 @@ impl Compiler<'_> {
 					};
 					let scope_index = self.scope.len() - 1;
 
+					if mutable {
+						self.scope[scope_index].mutable.insert(name.to_string());
+					} else {
+						self.scope[scope_index].mutable.remove(&name);
+					}
+
 					self.scope[scope_index]
 						.variables
 						.insert(name.to_string(), pointer);
@@ -1,8 +1,16 @@
 use super::operation::Operation;
 use super::scope::ScopeContext;
-use crate::Scope;
-use inkwell::{builder::Builder, context::Context, module::Module, values::FunctionValue};
-use tutara_interpreter::{parser::Parser, Analyzer, Error, Statement};
+use crate::{EnumType, Lambda, RecordType, Scope};
+use inkwell::{
+	builder::Builder,
+	context::Context,
+	module::Module,
+	types::BasicTypeEnum,
+	values::{FunctionValue, PointerValue},
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use tutara_interpreter::{parser::Parser, Analyzer, Error, ErrorCode, Statement, Type};
 
 pub struct Compiler<'a> {
 	pub(super) context: &'a Context,
@@ -10,65 +18,129 @@ pub struct Compiler<'a> {
 	pub(super) builder: Builder<'a>,
 	pub(super) analyzer: Analyzer,
 	pub(super) scope: Vec<Scope<'a>>,
+	pub(super) result_type: RefCell<Option<Type>>,
+	pub(super) runtime_errors: RefCell<Vec<Error>>,
+	pub(super) records: HashMap<String, RecordType<'a>>,
+	pub(super) enums: HashMap<String, EnumType<'a>>,
+	pub(super) match_bindings: RefCell<Vec<(String, PointerValue<'a>)>>,
+	pub(super) lambdas: HashMap<(u32, u32), Lambda<'a>>, // Lambdas by the position of their arrow
+	pub(super) lambda_returns: RefCell<Vec<Vec<Option<BasicTypeEnum<'a>>>>>, // Return types of the lambdas being compiled
 }
 
 impl Compiler<'_> {
 	pub fn compile<'b>(&mut self, parser: Parser<'b>) -> Result<FunctionValue, Error> {
-		let fun_type = self.context.f64_type().fn_type(&[], false);
+		let fun_type = self.string_type().fn_type(&[], false);
 		let fun = self.module.add_function("main", fun_type, None);
 		let body = self.context.append_basic_block(fun, "entry");
 		self.builder.position_at_end(body);
 		self.scope.push(Scope::new(ScopeContext::Main));
 
-		for result in parser {
-			match result {
-				Ok(statement) => {
-					if let Operation::Return(_) = self.evaluate_statement(statement)? {
-						match self.module.verify() {
-							Ok(_) => return Ok(fun),
-							Err(err) => return Err(Error::new_compiler_error(err.to_string())),
-						}
-					}
+		let statements = parser.collect::<Result<Vec<Statement>, Error>>()?;
+
+		// Every type error is found before any code is generated, the first one is returned
+		if let Some(error) = self.analyzer.check(&statements).into_iter().next() {
+			return Err(error);
+		}
+
+		// Types are compiled first, the prototypes of the functions use them
+		let declarations = Analyzer::hoist(statements);
+
+		for statement in declarations.types {
+			self.evaluate_statement(statement)?;
+		}
+
+		for statement in declarations.functions.iter() {
+			if let Statement::Function(_, r#type, identifier, parameters, _) =
+				Analyzer::get_declaration(statement)
+			{
+				self.declare_function(identifier, r#type, parameters)?;
+			}
+		}
+
+		let statements = declarations
+			.functions
+			.into_iter()
+			.chain(declarations.statements);
+
+		for statement in statements {
+			if let Operation::Return(_) = self.evaluate_statement(statement)? {
+				match self.module.verify() {
+					Ok(_) => return Ok(fun),
+					Err(err) => return Err(Error::new_compiler_error(err.to_string())),
 				}
-				Err(error) => return Err(error),
-			};
+			}
 		}
 
-		Err(Error::new_compiler_error(
-			"No return statement found in script".to_string(),
-		))
+		Err(
+			Error::new_compiler_error("No return statement found in script".to_string())
+				.with_code(ErrorCode::MissingScriptReturn),
+		)
 	}
 
 	pub fn evaluate_statement(&mut self, statement: Statement) -> Result<Operation, Error> {
+		let span = statement.span();
+
+		self.compile_statement(statement)
+			.map_err(|error| error.with_span(span))
+	}
+
+	fn compile_statement(&mut self, statement: Statement) -> Result<Operation, Error> {
 		use Statement::*;
 
 		let analyzed_statement = self.analyzer.analyze(statement)?;
 
+		// Lambda bodies are compiled twice to infer their return type, declarations would be duplicated
+		if let Function(..) | Record(..) | Enum(..) = analyzed_statement {
+			if self.is_in_lambda() {
+				return Err(Error::new_compiler_error(
+					"Unable to declare functions and types in a lambda".to_string(),
+				));
+			}
+		}
+
+		self.lift_lambdas(&analyzed_statement)?;
+
 		match analyzed_statement {
-			Break => self.evaluate_break(),
-			Continue => self.evaluate_continue(),
-			While(condition, body) => self.evaluate_while(condition, body),
-			If(condition, true_branch, false_branch) => {
+			Break(_) => self.evaluate_break(),
+			Continue(_) => self.evaluate_continue(),
+			While(_, condition, body) => self.evaluate_while(condition, body),
+			If(_, condition, true_branch, false_branch) => {
 				self.evaluate_if(condition, true_branch, false_branch)
 			}
 			Expression(expression) => self.evaluate_expression(expression),
-			Declaration(_mutability, _type_specification, expression) => {
-				self.evaluate_declaration(expression)?;
+			Declaration(mutability, type_specification, expression) => {
+				self.evaluate_declaration(mutability, type_specification, expression)?;
 				Ok(Operation::NoOp)
 			}
-			Body(statements) => self.evaluate_body(statements),
-			Return(expression) => self.evaluate_return(expression),
+			Body(_, statements, _) => self.evaluate_body(statements),
+			Return(_, expression) => self.evaluate_return(expression),
 			Comment(_) => Ok(Operation::NoOp),
-			Function(r#type, identifier, parameters, body) => {
+			Match(_token, subject, arms, default, _) => self.evaluate_match(subject, arms, default),
+			Function(_, r#type, identifier, parameters, body) => {
 				self.evaluate_function(identifier, r#type, parameters, body)
 			}
-			For(_identifier, _iterable, _body) => Err(Error::new_compiler_error(
-				"Unsupported statement: for".to_string(),
-			)),
+			For(_, identifier, iterable, body) => self.evaluate_for(identifier, iterable, body),
+			Record(_, identifier, fields, _) => self.evaluate_record(identifier, fields),
+			Enum(_, identifier, variants, _) => self.evaluate_enum(identifier, variants),
 			// Statements converted by analyzer
-			Loop(_body) => Err(Error::new_compiler_error(
+			Documentation(..) => Err(Error::new_compiler_error(
+				"Unexpected statement: documentation".to_string(),
+			)),
+			Loop(..) => Err(Error::new_compiler_error(
 				"Unexpected statement: loop".to_string(),
 			)),
 		}
 	}
+
+	fn is_in_lambda(&self) -> bool {
+		self.scope
+			.iter()
+			.rev()
+			.find_map(|scope| match scope.scope_context {
+				ScopeContext::Fun => Some(false),
+				ScopeContext::Lambda => Some(true),
+				_ => None,
+			})
+			.unwrap_or(false)
+	}
 }
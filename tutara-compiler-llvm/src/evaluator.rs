@@ -1,12 +1,45 @@
-use crate::Compiler;
+use crate::{
+	Compiler, FLOAT_TO_STRING_FUNCTION, INT_TO_STRING_FUNCTION, RUNTIME_ERROR_FUNCTION,
+	STRING_CONVERSION_SIZE,
+};
 use inkwell::context::Context;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::path::Path;
-use tutara_interpreter::{parser::Parser, Analyzer, Error};
+use std::rc::Rc;
+use tutara_interpreter::{
+	parser::Parser, Analyzer, Error, ErrorCode, Range, Record, Type, Value, Variant,
+};
+
+thread_local! {
+	static RUNTIME_ERROR: Cell<Option<u32>> = Cell::new(None);
+}
+
+// Called by compiled scripts with the index of the runtime error they ran into
+extern "C" fn tutara_runtime_error(index: u32) {
+	RUNTIME_ERROR.with(|error| error.set(Some(index)));
+}
+
+// Called by compiled scripts to convert numbers to text, the buffer holds STRING_CONVERSION_SIZE bytes
+extern "C" fn tutara_int_to_string(value: i64, buffer: *mut u8) -> i64 {
+	write_text(&Value::Integer(value).to_string(), buffer)
+}
+
+extern "C" fn tutara_float_to_string(value: f64, buffer: *mut u8) -> i64 {
+	write_text(&Value::Float(value).to_string(), buffer)
+}
+
+fn write_text(text: &str, buffer: *mut u8) -> i64 {
+	let length = text.len().min(STRING_CONVERSION_SIZE as usize);
+	unsafe { std::ptr::copy_nonoverlapping(text.as_ptr(), buffer, length) };
+
+	length as i64
+}
 
 pub struct Evaluator {}
 
 impl Evaluator {
-	pub fn evaluate(parser: Parser<'_>) -> Result<f64, Error> {
+	pub fn evaluate(parser: Parser<'_>) -> Result<Value, Error> {
 		let context = Context::create();
 		let module = context.create_module("init");
 		let builder = context.create_builder();
@@ -18,15 +51,46 @@ impl Evaluator {
 			builder,
 			analyzer,
 			scope: Vec::new(),
+			result_type: RefCell::new(None),
+			runtime_errors: RefCell::new(Vec::new()),
+			records: HashMap::new(),
+			enums: HashMap::new(),
+			match_bindings: RefCell::new(Vec::new()),
+			lambdas: HashMap::new(),
+			lambda_returns: RefCell::new(Vec::new()),
 		};
 
 		let engine = compiler
 			.module
 			.create_jit_execution_engine(inkwell::OptimizationLevel::None)
 			.unwrap();
-		match compiler.compile(parser) {
-			Ok(fun) => unsafe { Ok(engine.run_function(fun, &[]).as_float(&context.f64_type())) },
-			Err(err) => Err(err),
+		let fun = compiler.compile(parser)?;
+
+		if let Some(runtime_error) = compiler.module.get_function(RUNTIME_ERROR_FUNCTION) {
+			engine.add_global_mapping(&runtime_error, tutara_runtime_error as usize);
+		}
+
+		if let Some(int_to_string) = compiler.module.get_function(INT_TO_STRING_FUNCTION) {
+			engine.add_global_mapping(&int_to_string, tutara_int_to_string as usize);
+		}
+
+		if let Some(float_to_string) = compiler.module.get_function(FLOAT_TO_STRING_FUNCTION) {
+			engine.add_global_mapping(&float_to_string, tutara_float_to_string as usize);
+		}
+
+		RUNTIME_ERROR.with(|error| error.set(None));
+		let result = unsafe { engine.run_function(fun, &[]).into_pointer::<u8>() };
+
+		if let Some(index) = RUNTIME_ERROR.with(|error| error.take()) {
+			return Err(compiler.runtime_errors.into_inner().remove(index as usize));
+		}
+
+		match compiler.result_type.take() {
+			Some(r#type) => Ok(unsafe { Evaluator::read_result(r#type, result, &compiler) }),
+			None => Err(Error::new_compiler_error(
+				"No return statement found in script".to_string(),
+			)
+			.with_code(ErrorCode::MissingScriptReturn)),
 		}
 	}
 
@@ -42,6 +106,13 @@ impl Evaluator {
 			builder,
 			analyzer,
 			scope: Vec::new(),
+			result_type: RefCell::new(None),
+			runtime_errors: RefCell::new(Vec::new()),
+			records: HashMap::new(),
+			enums: HashMap::new(),
+			match_bindings: RefCell::new(Vec::new()),
+			lambdas: HashMap::new(),
+			lambda_returns: RefCell::new(Vec::new()),
 		};
 
 		match compiler.compile(parser) {
@@ -53,4 +124,196 @@ impl Evaluator {
 			Err(err) => Some(err),
 		}
 	}
+
+	// Reads the value the script result pointer refers to, strings are length prefixed,
+	// ranges are laid out as { i64 start, i64 end, i64 step, i1 inclusive }, arrays
+	// as { i64 length, i64 capacity, T* data }, maps as { K-array* keys, V-array* values },
+	// records as a struct of their fields and enums as { i64 tag, values of every variant }.
+	unsafe fn read_result(r#type: Type, result: *const u8, compiler: &Compiler) -> Value {
+		match r#type {
+			Type::Int => Value::Integer(*(result as *const i64)),
+			Type::Float => Value::Float(*(result as *const f64)),
+			Type::Bool => Value::Boolean(*result != 0),
+			Type::String => {
+				let length = *(result as *const i64) as usize;
+				let bytes = std::slice::from_raw_parts(result.add(8), length);
+
+				Value::String(String::from_utf8_lossy(bytes).into_owned())
+			}
+			Type::Range => {
+				let fields = result as *const i64;
+
+				Value::Range(Range {
+					start: *fields,
+					end: *fields.add(1),
+					step: *fields.add(2),
+					inclusive: *result.add(24) != 0,
+				})
+			}
+			Type::Array(element) => {
+				let length = *(result as *const i64) as usize;
+				let data = *(result.add(16) as *const *const u8);
+				let size = Evaluator::size_of(&element, compiler);
+				let elements = (0..length)
+					.map(|index| {
+						Evaluator::read_element(
+							(*element).clone(),
+							data.add(index * size),
+							compiler,
+						)
+					})
+					.collect();
+
+				Value::Array(Rc::new(RefCell::new(elements)))
+			}
+			Type::Map(key, value) => {
+				let keys = *(result as *const *const u8);
+				let values = *(result.add(8) as *const *const u8);
+				let entries = match (
+					Evaluator::read_result(Type::Array(key), keys, compiler),
+					Evaluator::read_result(Type::Array(value), values, compiler),
+				) {
+					(Value::Array(keys), Value::Array(values)) => keys
+						.borrow()
+						.iter()
+						.cloned()
+						.zip(values.borrow().iter().cloned())
+						.collect(),
+					_ => Vec::new(),
+				};
+
+				Value::Map(Rc::new(RefCell::new(entries)))
+			}
+			Type::Record(name) => {
+				let types = Evaluator::get_fields(&Type::Record(name.clone()), compiler);
+				let offsets = Evaluator::get_offsets(&types, compiler);
+				let fields = compiler.records[&name]
+					.fields
+					.iter()
+					.zip(types.into_iter().zip(offsets))
+					.map(|((field, _), (r#type, offset))| {
+						(
+							field.clone(),
+							Evaluator::read_element(r#type, result.add(offset), compiler),
+						)
+					})
+					.collect();
+
+				Value::Record(Record { name, fields })
+			}
+			Type::Enum(name) => {
+				let types = Evaluator::get_fields(&Type::Enum(name.clone()), compiler);
+				let offsets = Evaluator::get_offsets(&types, compiler);
+				let variants = &compiler.enums[&name].variants;
+				let tag = *(result as *const i64) as usize;
+
+				// Values of a variant follow the tag and the values of the variants before it,
+				// only the values of the variant that is set are read.
+				let first = 1 + variants[..tag]
+					.iter()
+					.map(|(_, types)| types.len())
+					.sum::<usize>();
+				let (variant, variant_types) = &variants[tag];
+				let values = (first..first + variant_types.len())
+					.map(|index| {
+						Evaluator::read_element(
+							types[index].clone(),
+							result.add(offsets[index]),
+							compiler,
+						)
+					})
+					.collect();
+
+				Value::Enum(Variant {
+					r#enum: name,
+					name: variant.clone(),
+					values,
+				})
+			}
+			// Scripts can not return lambdas, the compiler rejects them
+			Type::Function(..) => unreachable!(),
+			Type::Void => Value::Void,
+		}
+	}
+
+	// Strings, arrays and maps are stored as pointers inside arrays, records and enums
+	unsafe fn read_element(r#type: Type, element: *const u8, compiler: &Compiler) -> Value {
+		match r#type {
+			Type::String | Type::Array(_) | Type::Map(_, _) => {
+				Evaluator::read_result(r#type, *(element as *const *const u8), compiler)
+			}
+			_ => Evaluator::read_result(r#type, element, compiler),
+		}
+	}
+
+	// Fields of records and enums are laid out with their natural alignment
+	fn get_offsets(types: &[Type], compiler: &Compiler) -> Vec<usize> {
+		let mut offset = 0;
+		let mut offsets = Vec::new();
+
+		for r#type in types.iter() {
+			offset = Evaluator::align(offset, Evaluator::align_of(r#type, compiler));
+			offsets.push(offset);
+			offset += Evaluator::size_of(r#type, compiler);
+		}
+
+		offsets
+	}
+
+	// The tag of an enum is read as an Int, both take 8 bytes
+	fn get_fields(r#type: &Type, compiler: &Compiler) -> Vec<Type> {
+		match r#type {
+			Type::Record(name) => compiler.records[name]
+				.fields
+				.iter()
+				.map(|(_, r#type)| r#type.clone())
+				.collect(),
+			Type::Enum(name) => std::iter::once(Type::Int)
+				.chain(
+					compiler.enums[name]
+						.variants
+						.iter()
+						.flat_map(|(_, types)| types.iter().cloned()),
+				)
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	fn size_of(r#type: &Type, compiler: &Compiler) -> usize {
+		match r#type {
+			Type::Int | Type::Float | Type::String | Type::Array(_) | Type::Map(_, _) => 8,
+			Type::Bool => 1,
+			Type::Range => 32,
+			Type::Function(..) => 16,
+			Type::Record(_) | Type::Enum(_) => {
+				let size =
+					Evaluator::get_fields(r#type, compiler)
+						.iter()
+						.fold(0, |offset, field| {
+							Evaluator::align(offset, Evaluator::align_of(field, compiler))
+								+ Evaluator::size_of(field, compiler)
+						});
+
+				Evaluator::align(size, Evaluator::align_of(r#type, compiler))
+			}
+			Type::Void => 0,
+		}
+	}
+
+	fn align_of(r#type: &Type, compiler: &Compiler) -> usize {
+		match r#type {
+			Type::Bool | Type::Void => 1,
+			Type::Record(_) | Type::Enum(_) => Evaluator::get_fields(r#type, compiler)
+				.iter()
+				.map(|field| Evaluator::align_of(field, compiler))
+				.max()
+				.unwrap_or(1),
+			_ => 8,
+		}
+	}
+
+	fn align(offset: usize, alignment: usize) -> usize {
+		(offset + alignment - 1) / alignment * alignment
+	}
 }
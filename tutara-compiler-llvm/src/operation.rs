@@ -1,8 +1,75 @@
-use inkwell::values::{FloatValue, InstructionValue, IntValue};
+use inkwell::{
+	types::AnyTypeEnum,
+	values::{BasicValueEnum, FloatValue, InstructionValue, IntValue, PointerValue, StructValue},
+};
 
 pub enum Operation<'a> {
+	IntValue(IntValue<'a>),
 	FloatValue(FloatValue<'a>),
 	BoolValue(IntValue<'a>),
+	StringValue(PointerValue<'a>),
+	RangeValue(StructValue<'a>),
+	ArrayValue(PointerValue<'a>),
+	MapValue(PointerValue<'a>),
+	RecordValue(StructValue<'a>),
+	EnumValue(StructValue<'a>),
+	ClosureValue(StructValue<'a>),
 	Return(InstructionValue<'a>),
 	NoOp,
 }
+
+impl<'a> Operation<'a> {
+	pub fn from_basic_value(value: BasicValueEnum<'a>) -> Option<Operation<'a>> {
+		match value {
+			BasicValueEnum::IntValue(value) if value.get_type().get_bit_width() == 64 => {
+				Some(Operation::IntValue(value))
+			}
+			BasicValueEnum::FloatValue(value) => Some(Operation::FloatValue(value)),
+			BasicValueEnum::IntValue(value) if value.get_type().get_bit_width() == 1 => {
+				Some(Operation::BoolValue(value))
+			}
+			BasicValueEnum::PointerValue(value) => Some(Operation::from_pointer_value(value)),
+			BasicValueEnum::StructValue(value) => Some(Operation::from_struct_value(value)),
+			_ => None,
+		}
+	}
+
+	// Strings point to bytes while arrays and maps point to their header struct,
+	// map headers only hold the arrays of keys and values.
+	pub fn from_pointer_value(value: PointerValue<'a>) -> Operation<'a> {
+		match value.get_type().get_element_type() {
+			AnyTypeEnum::StructType(header) if header.count_fields() == 2 => {
+				Operation::MapValue(value)
+			}
+			AnyTypeEnum::StructType(_) => Operation::ArrayValue(value),
+			_ => Operation::StringValue(value),
+		}
+	}
+
+	// Records, enums and closures are named structs while ranges are anonymous,
+	// enum names are prefixed to keep them apart from records and closures are named by their type.
+	pub fn from_struct_value(value: StructValue<'a>) -> Operation<'a> {
+		match value.get_type().get_name() {
+			Some(name) if name.to_bytes().starts_with(b"enum.") => Operation::EnumValue(value),
+			Some(name) if name.to_bytes().starts_with(b"Fun<") => Operation::ClosureValue(value),
+			Some(_) => Operation::RecordValue(value),
+			None => Operation::RangeValue(value),
+		}
+	}
+
+	pub fn basic_value(&self) -> Option<BasicValueEnum<'a>> {
+		match self {
+			Operation::IntValue(value) => Some((*value).into()),
+			Operation::FloatValue(value) => Some((*value).into()),
+			Operation::BoolValue(value) => Some((*value).into()),
+			Operation::StringValue(value) => Some((*value).into()),
+			Operation::RangeValue(value) => Some((*value).into()),
+			Operation::ArrayValue(value) => Some((*value).into()),
+			Operation::MapValue(value) => Some((*value).into()),
+			Operation::RecordValue(value) => Some((*value).into()),
+			Operation::EnumValue(value) => Some((*value).into()),
+			Operation::ClosureValue(value) => Some((*value).into()),
+			_ => None,
+		}
+	}
+}
@@ -1,9 +1,11 @@
 use inkwell::{basic_block::BasicBlock, values::PointerValue};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct Scope<'a> {
 	pub(crate) scope_context: ScopeContext<'a>,
 	pub(crate) variables: HashMap<String, PointerValue<'a>>,
+	pub(crate) mutable: HashSet<String>, // Variables declared with var
+	pub(crate) captured: HashSet<String>, // Variables captured by the lambdas of a function body
 }
 
 impl<'a> Scope<'_> {
@@ -11,6 +13,8 @@ impl<'a> Scope<'_> {
 		Scope {
 			scope_context,
 			variables: HashMap::new(),
+			mutable: HashSet::new(),
+			captured: HashSet::new(),
 		}
 	}
 }
@@ -19,5 +23,6 @@ pub enum ScopeContext<'a> {
 	While(BasicBlock<'a>, BasicBlock<'a>, BasicBlock<'a>), // Body , Evaluation , Continuation
 	If(BasicBlock<'a>, BasicBlock<'a>),                    // Body , Continuation
 	Fun,
+	Lambda,
 	Main,
 }
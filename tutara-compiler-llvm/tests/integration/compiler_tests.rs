@@ -1,9 +1,9 @@
 use std::fs;
 use std::path::PathBuf;
 use tutara_compiler_llvm::Evaluator;
-use tutara_interpreter::{parser::Parser, Tokenizer};
+use tutara_interpreter::{parser::Parser, Tokenizer, Value};
 
-fn test_compiler(name: &str, result: f64) {
+fn test_compiler(name: &str, result: Value) {
 	let mut script_path: PathBuf = ["tests", "scripts", name].iter().collect();
 	script_path.set_extension("ttr");
 
@@ -18,30 +18,30 @@ fn test_compiler(name: &str, result: f64) {
 
 #[test]
 fn test_math_function() {
-	test_compiler("math_function", 3.0);
+	test_compiler("math_function", Value::Integer(3));
 }
 
 #[test]
 fn test_continue() {
-	test_compiler("continue", 5.0);
+	test_compiler("continue", Value::Integer(5));
 }
 
 #[test]
 fn test_break() {
-	test_compiler("break", 9.0);
+	test_compiler("break", Value::Integer(9));
 }
 
 #[test]
 fn test_if_else() {
-	test_compiler("if_else", 10.0);
+	test_compiler("if_else", Value::Integer(10));
 }
 
 #[test]
 fn test_assignment_operations() {
-	test_compiler("assignment_operations", 3137.0);
+	test_compiler("assignment_operations", Value::Integer(3137));
 }
 
 #[test]
 fn test_logic_function() {
-	test_compiler("logic_function", 1.0);
+	test_compiler("logic_function", Value::Integer(1));
 }
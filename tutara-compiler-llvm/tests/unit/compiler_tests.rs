@@ -1,7 +1,7 @@
-use tutara_interpreter::{parser::Parser, Error, Tokenizer};
+use tutara_interpreter::{parser::Parser, Error, Tokenizer, Value};
 use tutara_compiler_llvm::Evaluator;
 
-fn resolve(src: &str) -> Result<f64, Error>{
+fn resolve(src: &str) -> Result<Value, Error>{
 	let tokenizer = Tokenizer::new(&src);
 	let parser = Parser::new(tokenizer.peekable());
 
@@ -64,13 +64,13 @@ fn test_invalid_unexpected_paramater_name() {
 #[test]
 fn test_return_1() {
 	let result = resolve("return 1");
-	assert_eq!(1.0, result.unwrap())
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_return_1_plus_1() {
 	let result = resolve("return 1 + 1");
-	assert_eq!(2.0, result.unwrap())
+	assert_eq!(Value::Integer(2), result.unwrap())
 }
 
 #[test]
@@ -88,115 +88,145 @@ fn test_return_false_minus_true() {
 #[test]
 fn test_return_1_divide_2() {
 	let result = resolve("return 1 / 2");
-	assert_eq!(0.5, result.unwrap())
+	assert_eq!(Value::Integer(0), result.unwrap())
+}
+
+#[test]
+fn test_return_float_divide() {
+	let result = resolve("return 1.0 / 2.0");
+	assert_eq!(Value::Float(0.5), result.unwrap())
+}
+
+#[test]
+fn test_numeric_conversions() {
+	let result = resolve("return 9.toFloat() / (-2.75).toInt().toFloat()");
+	assert_eq!(Value::Float(-4.5), result.unwrap())
+}
+
+#[test]
+fn test_int_pow() {
+	let result = resolve("return 3 ** 5");
+	assert_eq!(Value::Integer(243), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_int_overflow() {
+	resolve_panic("return 9223372036854775807 + 1");
+}
+
+#[test]
+#[should_panic]
+fn test_int_division_by_zero() {
+	resolve_panic("return 1 % 0");
 }
 
 #[test]
 fn test_return_2_minus_1() {
 	let result = resolve("return 2 - 1");
-	assert_eq!(1.0, result.unwrap())
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_return_2_times_3() {
 	let result = resolve("return 2 * 3");
-	assert_eq!(6.0, result.unwrap())
+	assert_eq!(Value::Integer(6), result.unwrap())
 }
 
 #[test]
 fn test_return_6_over_2() {
 	let result = resolve("return 6 / 2");
-	assert_eq!(3.0, result.unwrap())
+	assert_eq!(Value::Integer(3), result.unwrap())
 }
 
 #[test]
 fn test_return_7_rest_2() {
 	let result = resolve("return 7 % 2");
-	assert_eq!(1.0, result.unwrap())
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_return_2_pow_4() {
 	let result = resolve("return 2 ** 4");
-	assert_eq!(16.0, result.unwrap())
+	assert_eq!(Value::Integer(16), result.unwrap())
 }
 
 #[test]
 fn test_return_minus_3_plus_8() {
 	let result = resolve("return -3 + 8");
-	assert_eq!(5.0, result.unwrap())
+	assert_eq!(Value::Integer(5), result.unwrap())
 }
 
 #[test]
 fn test_bool_equals_operator() {
-	let result = resolve("val a = 0 if(true == false){ a = 1 } return a");
-	assert_eq!(0.0, result.unwrap())
+	let result = resolve("var a = 0 if(true == false){ a = 1 } return a");
+	assert_eq!(Value::Integer(0), result.unwrap())
 }
 
 #[test]
 fn test_bool_not_equals_operator() {
-	let result = resolve("val a = 0 if(true != false){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(true != false){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_bool_not_operator() {
-	let result = resolve("val a = 0 if(true == !false){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(true == !false){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_equals_operator() {
-	let result = resolve("val a = 0 if(12 == 24){ a = 1 } return a");
-	assert_eq!(0.0, result.unwrap())
+	let result = resolve("var a = 0 if(12 == 24){ a = 1 } return a");
+	assert_eq!(Value::Integer(0), result.unwrap())
 }
 
 #[test]
 fn test_int_not_equals_operator() {
-	let result = resolve("val a = 0 if(12 != 24){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(12 != 24){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_not_operator() {
-	let result = resolve("val a = 0 if(!(12 == 24)){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(!(12 == 24)){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_lesser_operator() {
-	let result = resolve("val a = 0 if(4 < 5){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(4 < 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_greater_operator() {
-	let result = resolve("val a = 0 if(4 > 5){ a = 1 } return a");
-	assert_eq!(0.0, result.unwrap())
+	let result = resolve("var a = 0 if(4 > 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(0), result.unwrap())
 }
 
 #[test]
 fn test_int_lesser_or_equal_operator_lower() {
-	let result = resolve("val a = 0 if(4 <= 5){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(4 <= 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_lesser_or_equal_operator_equal() {
-	let result = resolve("val a = 0 if(5 <= 5){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(5 <= 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_greater_or_equal_operator_lower() {
-	let result = resolve("val a = 0 if(5 >= 4){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(5 >= 4){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
 fn test_int_greater_or_equal_operator_equal() {
-	let result = resolve("val a = 0 if(5 >= 5){ a = 1 } return a");
-	assert_eq!(1.0, result.unwrap())
+	let result = resolve("var a = 0 if(5 >= 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
 }
 
 #[test]
@@ -234,3 +264,370 @@ fn test_invalid_declaration() {
 fn test_invalid_call() {
 	resolve_panic("1()");
 }
+
+#[test]
+fn test_return_string() {
+	let result = resolve("return 'foo'");
+	assert_eq!(Value::String("foo".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_return_bool() {
+	let result = resolve("return !false");
+	assert_eq!(Value::Boolean(true), result.unwrap())
+}
+
+#[test]
+fn test_string_concatenation() {
+	let result = resolve("val a = 'foo' val b = a + 'bar' return b + ''");
+	assert_eq!(Value::String("foobar".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_string_equals_operator() {
+	let result = resolve("var a = 0 if('foo' == 'f' + 'oo'){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
+}
+
+#[test]
+fn test_string_not_equals_operator() {
+	let result = resolve("var a = 0 if('foo' != 'fo'){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
+}
+
+#[test]
+fn test_string_function() {
+	let result = resolve("fun: String greet(name: String) { return 'Hello ' + name } return greet('Tutara')");
+	assert_eq!(Value::String("Hello Tutara".to_string()), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_return_string_minus_string() {
+	resolve_panic("return 'foo' - 'bar'");
+}
+
+#[test]
+#[should_panic]
+fn test_mismatched_script_return() {
+	resolve_panic("if (true) { return 'foo' } return 1");
+}
+
+#[test]
+fn test_for_loop() {
+	let result = resolve("var a = '' for (c in 'hello') { a = c + '-' + a } return a");
+	assert_eq!(Value::String("o-l-l-e-h-".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_for_loop_characters() {
+	let result = resolve("var a = 0 var b = '' for (c in 'héllo') { a += 1 b = c + b } return '$b$a'");
+	assert_eq!(Value::String("olléh5".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_for_loop_break_continue() {
+	let result = resolve("var a = '' for (c in 'hello!world') { if (c == 'l') { continue } if (c == '!') { break } a = a + c } return a");
+	assert_eq!(Value::String("heo".to_string()), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_for_iterable() {
+	resolve_panic("for (c in true) {} return 1");
+}
+
+#[test]
+fn test_for_range() {
+	let result = resolve("var a = 0 for (i in 0..10) { a += i } return a");
+	assert_eq!(Value::Integer(45), result.unwrap())
+}
+
+#[test]
+fn test_for_inclusive_range() {
+	let result = resolve("var a = 0 for (i in 0..=10) { a += i } return a");
+	assert_eq!(Value::Integer(55), result.unwrap())
+}
+
+#[test]
+fn test_for_range_step() {
+	let result = resolve("var a = 0 for (i in (0..10).step(2)) { a += i } return a");
+	assert_eq!(Value::Integer(20), result.unwrap())
+}
+
+#[test]
+fn test_range_variable() {
+	let result = resolve("val r = 1..3 var a = 0 for (i in r) { a += 1 } for (i in r) { a += 1 } return a - 1");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+fn test_return_range() {
+	let result = resolve("return (0..=10).step(5)");
+	assert_eq!("(0..=10).step(5)", result.unwrap().to_string())
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_range_bounds() {
+	resolve_panic("return 0..'a'");
+}
+
+#[test]
+fn test_match_expression() {
+	let src = "fun: String size(n: Int) { return match (n) { 0 -> 'none', 1, 2 -> 'few', 3..=9 -> 'some', else -> 'many' } }";
+
+	for (n, expected) in [(0, "none"), (2, "few"), (9, "some"), (10, "many")].iter() {
+		let result = resolve(&format!("{} return size({})", src, n));
+		assert_eq!(Value::String(expected.to_string()), result.unwrap())
+	}
+}
+
+#[test]
+fn test_match_guard() {
+	let result = resolve("val a = 3 return match (a) { 0..10 if a % 2 == 0 -> 'even', 0..10 -> 'odd', else -> 'big' }");
+	assert_eq!(Value::String("odd".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_match_statement() {
+	let result = resolve("var a = 0 for (c in 'a-b') { match (c) { '-' -> { continue } else -> a += 6 } } return a");
+	assert_eq!(Value::Integer(12), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_mismatched_match_arms() {
+	resolve_panic("return match (1) { 1 -> 'a', else -> 2 }");
+}
+
+#[test]
+fn test_array_index() {
+	let result = resolve("val xs = [1, 2, 3] return xs[1]");
+	assert_eq!(Value::Integer(2), result.unwrap())
+}
+
+#[test]
+fn test_array_assignment() {
+	let result = resolve("var xs = [1, 2, 3] xs[0] = 10 xs[1] += 5 return xs");
+	assert_eq!("[10, 7, 3]", result.unwrap().to_string())
+}
+
+#[test]
+fn test_array_methods() {
+	let result = resolve("var: Array<Int> xs = [] for (i in 0..10) { xs.push(i) } val last = xs.pop() return last + xs.length");
+	assert_eq!(Value::Integer(18), result.unwrap())
+}
+
+#[test]
+fn test_array_parameter() {
+	let result = resolve("fun: Int sum(xs: Array<Int>) { var a = 0 for (x in xs) { a += x } return a } return sum([1, 2, 3])");
+	assert_eq!(Value::Integer(6), result.unwrap())
+}
+
+#[test]
+fn test_array_out_of_bounds() {
+	let result = resolve("fun: Int get(xs: Array<Int>) { return xs[2] } return get([1, 2])");
+	assert_eq!("Index out of bounds", result.unwrap_err().message)
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_array_elements() {
+	resolve_panic("return [1, 'a']");
+}
+
+#[test]
+fn test_map_index() {
+	let result = resolve("val m = { 'a': 1, 'b': 2 } return m['b']");
+	assert_eq!(Value::Integer(2), result.unwrap())
+}
+
+#[test]
+fn test_map_assignment() {
+	let result = resolve("var: Map<String, Int> m = {} m['a'] = 1 m['b'] = 2 m['a'] += 10 return m");
+	assert_eq!("{a: 11, b: 2}", result.unwrap().to_string())
+}
+
+#[test]
+fn test_map_methods() {
+	let result = resolve("val m = { 1: 10, 2: 20 } val removed = m.remove(1) if (m.contains(1)) { return 0 } return removed + m.length * 11");
+	assert_eq!(Value::Integer(21), result.unwrap())
+}
+
+#[test]
+fn test_map_iteration() {
+	let result = resolve("val m = { 1: 10, 2: 20 } var a = 0 for (k in m.keys()) { a += k } for (v in m.values()) { a += v } return a");
+	assert_eq!(Value::Integer(33), result.unwrap())
+}
+
+#[test]
+fn test_map_parameter() {
+	let result = resolve("fun: Int count(m: Map<String, Int>) { return m.length } return count({ 'a': 1, 'b': 2, 'c': 3 })");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+fn test_map_missing_key() {
+	let result = resolve("fun: Int get(m: Map<String, Int>) { return m['b'] } return get({ 'a': 1 })");
+	assert_eq!("Key not found in map", result.unwrap_err().message)
+}
+
+#[test]
+fn test_record_field() {
+	let result = resolve("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } return p.x + p.y");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+fn test_record_field_assignment() {
+	let result = resolve("type Point { x: Int, y: Int } type Line { start: Point, end: Point } var line = Line { start: Point { x: 1, y: 2 }, end: Point { y: 4, x: 3 } } line.end.y = 10 line.end.y += 4 return line");
+	assert_eq!(
+		"Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 14 } }",
+		result.unwrap().to_string()
+	)
+}
+
+#[test]
+fn test_record_equality() {
+	let result = resolve("type Point { x: Int, y: Int } val a = Point { x: 1, y: 2 } return a == Point { y: 2, x: 1 } && a != Point { x: 2, y: 2 }");
+	assert_eq!(Value::Boolean(true), result.unwrap())
+}
+
+#[test]
+fn test_record_parameter() {
+	let result = resolve("type Point { x: Int, y: Int } fun: Point shift(p: Point) { var q = p q.x += 5 return q } return shift(Point { x: 1, y: 2 }).x");
+	assert_eq!(Value::Integer(6), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_missing_record_field() {
+	resolve_panic("type Point { x: Int, y: Int } return Point { x: 1 }");
+}
+
+#[test]
+fn test_enum_match() {
+	let result = resolve("enum Status { Ok(Int), NotFound, Failed(String) } fun: Int score(status: Status) { return match (status) { Status.Ok(n) if n > 9 -> n * 2, Status.Ok(n) -> n, Status.NotFound -> 0, Status.Failed(message) -> 0 - 1 } } return score(Status.Ok(10)) + score(Status.Ok(3)) + score(Status.NotFound)");
+	assert_eq!(Value::Integer(23), result.unwrap())
+}
+
+#[test]
+fn test_enum_result() {
+	let result = resolve("enum Status { Ok(Int), NotFound, Failed(String) } val s = Status.Failed('timeout') return s");
+	assert_eq!("Status.Failed(timeout)", result.unwrap().to_string())
+}
+
+#[test]
+#[should_panic]
+fn test_non_exhaustive_match() {
+	resolve_panic("enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(n) -> n }");
+}
+
+#[test]
+fn test_lambda_call() {
+	let result = resolve("val add = (a: Int, b: Int) -> a + b return add(2, 3) + ((a: Int) -> a * 2)(5)");
+	assert_eq!(Value::Integer(15), result.unwrap())
+}
+
+#[test]
+fn test_lambda_parameter() {
+	let result = resolve("fun: Array<Int> map(xs: Array<Int>, f: Fun<Int, Int>) { var: Array<Int> ys = [] for (x in xs) { ys.push(f(x)) } return ys } val offset = 10 return map([1, 2, 3], (x: Int) -> x * x + offset)");
+	assert_eq!("[11, 14, 19]", result.unwrap().to_string())
+}
+
+#[test]
+fn test_lambda_captures() {
+	let result = resolve("fun: Fun<Int> counter() { var count = 0 return () -> { count += 1 return count } } val next = counter() next() next() return next()");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_lambda_result() {
+	resolve_panic("return (a: Int) -> a");
+}
+
+#[test]
+fn test_template() {
+	let result = resolve("val a = 'foo' val b = 2 return '$a ${b * 3} ${1.5} ${b > 1}'");
+	assert_eq!(Value::String("foo 6 1.5 true".to_string()), result.unwrap())
+}
+
+#[test]
+fn test_multiline_string() {
+	let result = resolve("val a = 'foo' return '''\n  $a\n    r'\\'\n  '''");
+	assert_eq!(Value::String("foo\n  r'\\'".to_string()), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_template() {
+	resolve_panic("val a = [1] return '$a'");
+}
+
+#[test]
+fn test_comments() {
+	let result = resolve("/* a /* nested */\ncomment */\n/// Doc\nval a = 3\nreturn a // done");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_val_assignment() {
+	resolve_panic("val a = 1 if (true) { a = 2 } return a");
+}
+
+#[test]
+#[should_panic]
+fn test_declared_type() {
+	resolve_panic("fun: Int f() { return 1 } val: Bool x = f() return x");
+}
+
+#[test]
+fn test_var_assignment() {
+	let result = resolve("var: Float a = 1.5 a = a + 1.0 return a");
+	assert_eq!(Value::Float(2.5), result.unwrap())
+}
+
+#[test]
+#[should_panic]
+fn test_call_arguments() {
+	resolve_panic("fun: Int f(a: Int) { return a } return f('a')");
+}
+
+#[test]
+#[should_panic]
+fn test_return_type() {
+	resolve_panic("fun: Int f() { return 'a' } return f()");
+}
+
+#[test]
+#[should_panic]
+fn test_condition_type() {
+	resolve_panic("while (1) { } return 1");
+}
+
+#[test]
+fn test_forward_call() {
+	let result = resolve("val a = double(2) fun: Int double(n: Int) { return n * 2 } return a");
+	assert_eq!(Value::Integer(4), result.unwrap());
+}
+
+#[test]
+fn test_mutual_recursion() {
+	let result = resolve("fun: Bool isEven(n: Int) { if (n == 0) { return true } return isOdd(n - 1) } fun: Bool isOdd(n: Int) { if (n == 0) { return false } return isEven(n - 1) } return isEven(10)");
+	assert_eq!(Value::Boolean(true), result.unwrap());
+}
+
+#[test]
+#[should_panic]
+fn test_unknown_variable() {
+	resolve_panic("val a = 1 return a + b");
+}
+
+#[test]
+#[should_panic]
+fn test_missing_return() {
+	resolve_panic("fun: Int f(a: Int) { if (a > 0) { return a } } return f(1)");
+}
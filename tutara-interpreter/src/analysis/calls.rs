@@ -0,0 +1,356 @@
+use crate::analyzer::Analyzer;
+use crate::{ErrorCode, Expression, Token, Type};
+
+impl Analyzer {
+	pub(super) fn infer_call(
+		&mut self,
+		function: &Expression,
+		open_parenthesis: &Token,
+		arguments: &[Expression],
+	) -> Option<Type> {
+		let identifier = match function {
+			Expression::Identifier(identifier) => identifier,
+			Expression::Get(object, method) => {
+				if let Some(name) = self.get_enum_name(object) {
+					let arguments = self.infer_types(arguments);
+					return self.check_variant(&name, method, &arguments);
+				}
+
+				let object = self.infer_type(object);
+				let arguments = self.infer_types(arguments);
+				return self.infer_method(object, method, &arguments);
+			}
+			_ => {
+				let closure = self.infer_type(function);
+				let arguments = self.infer_types(arguments);
+				return self.check_closure_call(closure, open_parenthesis, &arguments);
+			}
+		};
+
+		let name = Analyzer::get_name(identifier)?;
+		let arguments = self.infer_types(arguments);
+
+		// Variables holding a lambda shadow functions with the same name
+		if let Some(binding) = self.find_binding(&name) {
+			let closure = binding.r#type.clone();
+			return self.check_closure_call(closure, open_parenthesis, &arguments);
+		}
+
+		match self.functions.get(&name).cloned() {
+			Some(signature) => {
+				self.check_arguments(
+					&format!("Function {}", name),
+					&signature.parameters,
+					open_parenthesis,
+					&arguments,
+				);
+				signature.result
+			}
+			None if name == "print" => Some(Type::Void),
+			None => {
+				self.report(
+					ErrorCode::UnknownFunction,
+					format!("Unknown function {}", name),
+					identifier,
+				);
+				None
+			}
+		}
+	}
+
+	fn infer_types(&mut self, expressions: &[Expression]) -> Vec<Option<Type>> {
+		expressions
+			.iter()
+			.map(|expression| self.infer_type(expression))
+			.collect()
+	}
+
+	// Lambdas have no declared result type, the result of calling a Fun<..., Void> is not known
+	fn check_closure_call(
+		&mut self,
+		closure: Option<Type>,
+		open_parenthesis: &Token,
+		arguments: &[Option<Type>],
+	) -> Option<Type> {
+		match closure? {
+			Type::Function(parameters, result) => {
+				let parameters: Vec<(String, Option<Type>)> = parameters
+					.into_iter()
+					.enumerate()
+					.map(|(index, parameter)| ((index + 1).to_string(), Some(parameter)))
+					.collect();
+
+				self.check_arguments("Lambda", &parameters, open_parenthesis, arguments);
+				Analyzer::known_type(*result)
+			}
+			closure => {
+				self.report(
+					ErrorCode::UnsupportedType,
+					format!("Unable to call {}", closure),
+					open_parenthesis,
+				);
+				None
+			}
+		}
+	}
+
+	fn check_arguments(
+		&mut self,
+		callee: &str,
+		parameters: &[(String, Option<Type>)],
+		token: &Token,
+		arguments: &[Option<Type>],
+	) {
+		if parameters.len() != arguments.len() {
+			return self.report(
+				ErrorCode::ArgumentCount,
+				format!(
+					"{} expects {} arguments but got {}",
+					callee,
+					parameters.len(),
+					arguments.len()
+				),
+				token,
+			);
+		}
+
+		for ((parameter_name, parameter_type), argument) in parameters.iter().zip(arguments) {
+			if let (Some(parameter_type), Some(argument)) = (parameter_type, argument) {
+				if !parameter_type.accepts(argument) {
+					self.report(
+						ErrorCode::MismatchedTypes,
+						format!(
+							"Expected {} for parameter {} but got {}",
+							parameter_type, parameter_name, argument
+						),
+						token,
+					);
+				}
+			}
+		}
+	}
+
+	fn infer_method(
+		&mut self,
+		object: Option<Type>,
+		method: &Token,
+		arguments: &[Option<Type>],
+	) -> Option<Type> {
+		let object = object?;
+		let name = Analyzer::get_name(method)?;
+
+		let (parameters, result) = match (&object, name.as_str()) {
+			(Type::Int, "toFloat") => (vec![], Some(Type::Float)),
+			(Type::Float, "toInt") => (vec![], Some(Type::Int)),
+			(Type::Range, "step") => (vec![("step", Some(Type::Int))], Some(Type::Range)),
+			(Type::Array(element), "push") => (
+				vec![("value", Analyzer::known_type(*element.clone()))],
+				Some(Type::Void),
+			),
+			(Type::Array(element), "pop") => (vec![], Analyzer::known_type(*element.clone())),
+			(Type::Map(key, value), "remove") => (
+				vec![("key", Analyzer::known_type(*key.clone()))],
+				Analyzer::known_type(*value.clone()),
+			),
+			(Type::Map(key, _), "contains") => (
+				vec![("key", Analyzer::known_type(*key.clone()))],
+				Some(Type::Bool),
+			),
+			(Type::Map(key, _), "keys") => (vec![], Some(Type::Array(key.clone()))),
+			(Type::Map(_, value), "values") => (vec![], Some(Type::Array(value.clone()))),
+			_ => {
+				self.report(
+					ErrorCode::UnknownFunction,
+					format!("Unknown function {} on {}", name, object),
+					method,
+				);
+				return None;
+			}
+		};
+
+		let parameters: Vec<(String, Option<Type>)> = parameters
+			.into_iter()
+			.map(|(parameter, r#type)| (parameter.to_string(), r#type))
+			.collect();
+
+		self.check_arguments(
+			&format!("Function {}", name),
+			&parameters,
+			method,
+			arguments,
+		);
+		result
+	}
+
+	pub(super) fn check_variant(
+		&mut self,
+		name: &str,
+		variant: &Token,
+		arguments: &[Option<Type>],
+	) -> Option<Type> {
+		let variant_name = Analyzer::get_name(variant)?;
+		let types = self.variants[name]
+			.iter()
+			.find(|(declared, _)| *declared == variant_name)
+			.map(|(_, types)| types.clone());
+
+		match types {
+			Some(types) if types.len() != arguments.len() => self.report(
+				ErrorCode::ArgumentCount,
+				format!(
+					"Variant {}.{} expects {} values but got {}",
+					name,
+					variant_name,
+					types.len(),
+					arguments.len()
+				),
+				variant,
+			),
+			Some(types) => {
+				for (r#type, argument) in types.iter().zip(arguments) {
+					if let (Some(r#type), Some(argument)) = (r#type, argument) {
+						if !r#type.accepts(argument) {
+							self.report(
+								ErrorCode::MismatchedTypes,
+								format!(
+									"Expected {} for variant {}.{} but got {}",
+									r#type, name, variant_name, argument
+								),
+								variant,
+							);
+						}
+					}
+				}
+			}
+			None => self.report(
+				ErrorCode::UnknownMember,
+				format!("Unknown variant {} of {}", variant_name, name),
+				variant,
+			),
+		}
+
+		Some(Type::Enum(name.to_string()))
+	}
+
+	pub(super) fn infer_get(&mut self, object: &Expression, property: &Token) -> Option<Type> {
+		if let Some(name) = self.get_enum_name(object) {
+			return self.check_variant(&name, property, &[]);
+		}
+
+		let object = self.infer_type(object)?;
+		let name = Analyzer::get_name(property)?;
+
+		match (&object, name.as_str()) {
+			(Type::Array(_), "length") | (Type::Map(..), "length") => Some(Type::Int),
+			(Type::Record(record), _) => {
+				let field = self
+					.records
+					.get(record)?
+					.iter()
+					.find(|(field, _)| *field == name)
+					.map(|(_, r#type)| r#type.clone());
+
+				match field {
+					Some(r#type) => r#type,
+					None => {
+						self.report(
+							ErrorCode::UnknownMember,
+							format!("Unknown field {} for {}", name, record),
+							property,
+						);
+						None
+					}
+				}
+			}
+			_ => {
+				self.report(
+					ErrorCode::UnknownMember,
+					format!("Unknown property {} on {}", name, object),
+					property,
+				);
+				None
+			}
+		}
+	}
+
+	pub(super) fn infer_record(
+		&mut self,
+		identifier: &Token,
+		fields: &[(Token, Expression)],
+	) -> Option<Type> {
+		let name = Analyzer::get_name(identifier)?;
+		let values: Vec<Option<Type>> = fields
+			.iter()
+			.map(|(_, value)| self.infer_type(value))
+			.collect();
+
+		let declared = match self.records.get(&name) {
+			Some(declared) => declared.clone(),
+			None => {
+				self.report(
+					ErrorCode::UnknownType,
+					format!("Unknown type {}", name),
+					identifier,
+				);
+				return None;
+			}
+		};
+
+		let mut assigned: Vec<String> = Vec::new();
+
+		for ((field, _), value) in fields.iter().zip(values) {
+			let field_name = Analyzer::get_name(field)?;
+
+			let field_type = match declared
+				.iter()
+				.find(|(declared, _)| *declared == field_name)
+			{
+				Some((_, field_type)) => field_type,
+				None => {
+					self.report(
+						ErrorCode::UnknownMember,
+						format!("Unknown field {} for {}", field_name, name),
+						field,
+					);
+					continue;
+				}
+			};
+
+			if assigned.contains(&field_name) {
+				self.report(
+					ErrorCode::Semantic,
+					format!("Field {} is already assigned", field_name),
+					field,
+				);
+				continue;
+			}
+
+			if let (Some(field_type), Some(value)) = (field_type, value) {
+				if !field_type.accepts(&value) {
+					self.report(
+						ErrorCode::MismatchedTypes,
+						format!(
+							"Can not assign {} to field {} of type {}",
+							value, field_name, field_type
+						),
+						field,
+					);
+				}
+			}
+
+			assigned.push(field_name);
+		}
+
+		for (field_name, _) in declared.iter() {
+			if !assigned.contains(field_name) {
+				self.report(
+					ErrorCode::Semantic,
+					format!("Missing field {} for {}", field_name, name),
+					identifier,
+				);
+			}
+		}
+
+		Some(Type::Record(name))
+	}
+}
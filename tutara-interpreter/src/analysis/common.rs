@@ -0,0 +1,153 @@
+use crate::analyzer::{Analyzer, Binding};
+use crate::{Error, ErrorCode, Expression, Literal, Span, Token, Type};
+
+use std::collections::HashMap;
+
+impl Analyzer {
+	pub(super) fn report(&mut self, code: ErrorCode, message: String, token: &Token) {
+		self.errors
+			.push(Error::new_semantic_error(message, token.span()).with_code(code));
+	}
+
+	pub(super) fn warn(&mut self, message: String, span: Span) {
+		self.warnings
+			.push(Error::new_semantic_warning(message, span));
+	}
+
+	pub(super) fn get_name(token: &Token) -> Option<String> {
+		match &token.literal {
+			Some(Literal::String(name)) => Some(name.clone()),
+			_ => None,
+		}
+	}
+
+	// Token that points to the expression in errors
+	pub(crate) fn get_token(expression: &Expression) -> &Token {
+		use Expression::*;
+
+		match expression {
+			Literal(token)
+			| Identifier(token)
+			| Assignment(token, ..)
+			| Match(token, ..)
+			| Array(token, ..)
+			| Map(token, ..)
+			| Record(token, ..)
+			| Lambda(_, _, token, _)
+			| Template(token, _)
+			| Unary(token, _) => token,
+			Binary(_, operator, _)
+			| Range(_, operator, _)
+			| Index(_, operator, ..)
+			| Set(_, operator, _)
+			| Call(_, operator, _, _)
+			| Get(_, operator) => operator,
+			Grouping(_, expression, _) => Analyzer::get_token(expression),
+		}
+	}
+
+	// Token that points to the statement in warnings, bodies point to their first statement
+	// Empty arrays and maps have Void elements, the type of their elements is not known
+	pub(super) fn known_type(r#type: Type) -> Option<Type> {
+		match r#type {
+			Type::Void => None,
+			r#type => Some(r#type),
+		}
+	}
+
+	// Resolves the type of a declaration and reports unknown types
+	pub(super) fn check_type(&mut self, token: &Token) -> Option<Type> {
+		let r#type = match Type::from_token(token) {
+			Ok(r#type) => self.resolve_enums(r#type),
+			Err(error) => {
+				self.report(ErrorCode::UnknownType, error.message, token);
+				return None;
+			}
+		};
+
+		match self.find_unknown_record(&r#type) {
+			Some(name) => {
+				self.report(
+					ErrorCode::UnknownType,
+					format!("Unknown type {}", name),
+					token,
+				);
+				None
+			}
+			None => Some(r#type),
+		}
+	}
+
+	pub(super) fn resolve_type(&self, token: &Token) -> Option<Type> {
+		Type::from_token(token)
+			.ok()
+			.map(|r#type| self.resolve_enums(r#type))
+	}
+
+	// Type names can not tell records and enums apart, declared enums are resolved by name
+	fn resolve_enums(&self, r#type: Type) -> Type {
+		match r#type {
+			Type::Record(name) if self.variants.contains_key(&name) => Type::Enum(name),
+			Type::Array(element) => Type::Array(Box::new(self.resolve_enums(*element))),
+			Type::Map(key, value) => Type::Map(
+				Box::new(self.resolve_enums(*key)),
+				Box::new(self.resolve_enums(*value)),
+			),
+			Type::Function(parameters, result) => Type::Function(
+				parameters
+					.into_iter()
+					.map(|parameter| self.resolve_enums(parameter))
+					.collect(),
+				Box::new(self.resolve_enums(*result)),
+			),
+			r#type => r#type,
+		}
+	}
+
+	fn find_unknown_record(&self, r#type: &Type) -> Option<String> {
+		match r#type {
+			Type::Record(name) if !self.records.contains_key(name) => Some(name.clone()),
+			Type::Array(element) => self.find_unknown_record(element),
+			Type::Map(key, value) => self
+				.find_unknown_record(key)
+				.or_else(|| self.find_unknown_record(value)),
+			Type::Function(parameters, result) => parameters
+				.iter()
+				.chain(std::iter::once(&**result))
+				.find_map(|r#type| self.find_unknown_record(r#type)),
+			_ => None,
+		}
+	}
+
+	pub(super) fn find_binding(&self, name: &str) -> Option<&Binding> {
+		self.bindings.iter().rev().find_map(|scope| scope.get(name))
+	}
+
+	pub(super) fn insert_binding(
+		scope: &mut HashMap<String, Binding>,
+		identifier: &Token,
+		mutable: bool,
+		r#type: Option<Type>,
+	) {
+		if let Some(name) = Analyzer::get_name(identifier) {
+			scope.insert(
+				name,
+				Binding {
+					mutable,
+					identifier: identifier.clone(),
+					r#type,
+				},
+			);
+		}
+	}
+
+	// Enums are used by name, unless a variable has the same name
+	pub(super) fn get_enum_name(&self, expression: &Expression) -> Option<String> {
+		match expression {
+			Expression::Identifier(identifier) => Analyzer::get_name(identifier).filter(|name| {
+				self.variants.contains_key(name) && self.find_binding(name).is_none()
+			}),
+			_ => None,
+		}
+	}
+}
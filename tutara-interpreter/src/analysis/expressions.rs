@@ -0,0 +1,470 @@
+use crate::analyzer::{Analyzer, ReturnContext};
+use crate::{Error, ErrorCode, Expression, Statement, Token, TokenType, Type};
+
+impl Analyzer {
+	// Type of an expression when it is known without evaluating it, errors are reported along the way
+	pub(super) fn infer_type(&mut self, expression: &Expression) -> Option<Type> {
+		use Expression::*;
+
+		match expression {
+			self::Expression::Literal(token) => match &token.literal {
+				Some(crate::Literal::Integer(_)) => Some(Type::Int),
+				Some(crate::Literal::Float(_)) => Some(Type::Float),
+				Some(crate::Literal::Boolean(_)) => Some(Type::Bool),
+				Some(crate::Literal::String(_)) | Some(crate::Literal::Template(_)) => {
+					Some(Type::String)
+				}
+				None => None,
+			},
+			Identifier(identifier) => self
+				.resolve_variable(identifier)
+				.and_then(|binding| binding.r#type.clone()),
+			Binary(left, operator, right) => {
+				let left = self.infer_type(left);
+				let right = self.infer_type(right);
+				self.infer_operation(&operator.r#type, operator, left, right)
+			}
+			Unary(operator, expression) => {
+				let operand = self.infer_type(expression);
+				self.infer_unary(operator, operand)
+			}
+			Grouping(_, expression, _) => self.infer_type(expression),
+			Assignment(identifier, operator, value) => {
+				self.check_assignment(identifier, operator, value);
+				None
+			}
+			Get(object, property) => self.infer_get(object, property),
+			Call(function, open_parenthesis, arguments, _) => {
+				self.infer_call(function, open_parenthesis, arguments)
+			}
+			Range(start, operator, end) => self.infer_range(start, operator, end),
+			Match(token, subject, arms, default, _) => {
+				let mut types = self.check_arms(token, subject, arms, Analyzer::infer_type);
+
+				if let Some(default) = default {
+					types.push(self.infer_type(default));
+				}
+
+				Analyzer::common_type(types)
+			}
+			Array(open_square_bracket, elements, _) => {
+				self.infer_array(open_square_bracket, elements)
+			}
+			Map(open_curly_bracket, entries, _) => self.infer_map(open_curly_bracket, entries),
+			Index(indexed, open_square_bracket, index, _) => {
+				self.infer_index(indexed, open_square_bracket, index)
+			}
+			Record(identifier, fields, _) => self.infer_record(identifier, fields),
+			Set(target, operator, value) => {
+				self.check_set(target, operator, value);
+				None
+			}
+			Lambda(_, parameters, _, body) => self.infer_lambda(parameters, body),
+			Template(token, parts) => self.infer_template(token, parts),
+		}
+	}
+
+	// Type shared by all of the types, like the results of match arms
+	fn common_type(types: Vec<Option<Type>>) -> Option<Type> {
+		let first = types.first()?.clone()?;
+
+		if types.iter().all(|r#type| r#type.as_ref() == Some(&first)) {
+			Some(first)
+		} else {
+			None
+		}
+	}
+
+	// Operator of a compound assignment, like Plus for +=
+	fn get_operation(assignment: &TokenType) -> Option<TokenType> {
+		match assignment {
+			TokenType::AssignPlus => Some(TokenType::Plus),
+			TokenType::AssignMinus => Some(TokenType::Minus),
+			TokenType::AssignMultiply => Some(TokenType::Multiply),
+			TokenType::AssignDivision => Some(TokenType::Division),
+			TokenType::AssignExponentiation => Some(TokenType::Exponentiation),
+			TokenType::AssignModulo => Some(TokenType::Modulo),
+			_ => None,
+		}
+	}
+
+	fn infer_operation(
+		&mut self,
+		operator: &TokenType,
+		token: &Token,
+		left: Option<Type>,
+		right: Option<Type>,
+	) -> Option<Type> {
+		let is_comparison = matches!(
+			operator,
+			TokenType::GreaterOrEqual
+				| TokenType::LesserOrEqual
+				| TokenType::Greater
+				| TokenType::Lesser
+		);
+		let is_logical = matches!(operator, TokenType::And | TokenType::Or);
+		let is_equality = matches!(operator, TokenType::Equal | TokenType::NotEqual);
+
+		let (left, right) = match (left, right) {
+			(Some(left), Some(right)) => (left, right),
+			_ if is_comparison || is_logical || is_equality => return Some(Type::Bool),
+			_ => return None,
+		};
+
+		match (&left, &right) {
+			(Type::Int, Type::Int) | (Type::Float, Type::Float)
+				if TokenType::is_operation(operator) =>
+			{
+				Some(left.clone())
+			}
+			(Type::Int, Type::Int) | (Type::Float, Type::Float) if is_comparison => {
+				Some(Type::Bool)
+			}
+			(Type::Bool, Type::Bool) if is_logical => Some(Type::Bool),
+			(Type::String, Type::String) if *operator == TokenType::Plus => Some(Type::String),
+			_ if is_equality && (left.accepts(&right) || right.accepts(&left)) => Some(Type::Bool),
+			_ => {
+				self.report(
+					ErrorCode::UnsupportedOperation,
+					format!(
+						"Unsupported operation {} on {} and {}",
+						operator, left, right
+					),
+					token,
+				);
+				None
+			}
+		}
+	}
+
+	fn infer_unary(&mut self, operator: &Token, operand: Option<Type>) -> Option<Type> {
+		let operand = match operand {
+			Some(operand) => operand,
+			None if operator.r#type == TokenType::Not => return Some(Type::Bool),
+			None => return None,
+		};
+
+		match (&operator.r#type, &operand) {
+			(TokenType::Not, Type::Bool) => Some(Type::Bool),
+			(TokenType::Minus, Type::Int)
+			| (TokenType::Minus, Type::Float)
+			| (TokenType::Plus, Type::Int)
+			| (TokenType::Plus, Type::Float) => Some(operand.clone()),
+			_ => {
+				self.report(
+					ErrorCode::UnsupportedOperation,
+					format!("Unsupported operation {} on {}", operator.r#type, operand),
+					operator,
+				);
+				None
+			}
+		}
+	}
+
+	fn check_assignment(&mut self, identifier: &Token, operator: &Token, value: &Expression) {
+		let value_type = self.infer_type(value);
+
+		let binding = match self.resolve_variable(identifier) {
+			Some(binding) => binding,
+			None => return,
+		};
+
+		let name = Analyzer::get_name(identifier).unwrap_or_default();
+		let declaration = binding.identifier.clone();
+		let declared_type = binding.r#type.clone();
+
+		if !binding.mutable {
+			return self.report_val_assignment(identifier, &declaration);
+		}
+
+		let value_type = match Analyzer::get_operation(&operator.r#type) {
+			Some(operation) => {
+				self.infer_operation(&operation, operator, declared_type.clone(), value_type)
+			}
+			None => value_type,
+		};
+
+		if let (Some(declared_type), Some(value_type)) = (&declared_type, &value_type) {
+			if !declared_type.accepts(value_type) {
+				let message = format!(
+					"Can not assign {} to variable {} of type {} declared at line {} on column {}",
+					value_type, name, declared_type, declaration.line, declaration.column
+				);
+
+				self.errors.push(
+					Error::new_semantic_error(message, identifier.span())
+						.with_code(ErrorCode::MismatchedTypes)
+						.with_label(
+							declaration.span(),
+							format!("{} is declared as {} here", name, declared_type),
+						),
+				);
+			}
+		}
+	}
+
+	fn check_set(&mut self, target: &Expression, operator: &Token, value: &Expression) {
+		let target_type = self.infer_type(target);
+		let value_type = self.infer_type(value);
+
+		// Records are values, so the fields of a record held by a val binding can't be assigned. Arrays and
+		// maps are shared instead, like the records they hold, so their elements can always be assigned.
+		if let Expression::Get(..) = target {
+			if let Some(identifier) = Analyzer::get_record_variable(target) {
+				let declaration = Analyzer::get_name(identifier)
+					.and_then(|name| self.find_binding(&name))
+					.filter(|binding| !binding.mutable)
+					.map(|binding| binding.identifier.clone());
+
+				if let Some(declaration) = declaration {
+					return self.report_val_assignment(identifier, &declaration);
+				}
+			}
+		}
+
+		let value_type = match Analyzer::get_operation(&operator.r#type) {
+			Some(operation) => {
+				self.infer_operation(&operation, operator, target_type.clone(), value_type)
+			}
+			None => value_type,
+		};
+
+		if let (Some(target_type), Some(value_type)) = (&target_type, &value_type) {
+			if !target_type.accepts(value_type) {
+				let target = match target {
+					Expression::Get(..) => "field",
+					_ => "element",
+				};
+
+				self.report(
+					ErrorCode::MismatchedTypes,
+					format!(
+						"Can not assign {} to {} of type {}",
+						value_type, target, target_type
+					),
+					operator,
+				);
+			}
+		}
+	}
+
+	// Variable holding the record of a field like line.start.x, fields of a record in an array or map like
+	// points[0].x belong to the element instead
+	fn get_record_variable(target: &Expression) -> Option<&Token> {
+		match target {
+			Expression::Identifier(identifier) => Some(identifier),
+			Expression::Get(object, _) => Analyzer::get_record_variable(object),
+			_ => None,
+		}
+	}
+
+	fn report_val_assignment(&mut self, identifier: &Token, declaration: &Token) {
+		let name = Analyzer::get_name(identifier).unwrap_or_default();
+		let message = format!(
+			"Can not assign to val {} declared at line {} on column {}",
+			name, declaration.line, declaration.column
+		);
+
+		self.errors.push(
+			Error::new_semantic_error(message, identifier.span())
+				.with_code(ErrorCode::ValAssignment)
+				.with_label(
+					declaration.span(),
+					format!("{} is declared as val here", name),
+				),
+		);
+	}
+
+	fn infer_range(
+		&mut self,
+		start: &Expression,
+		operator: &Token,
+		end: &Expression,
+	) -> Option<Type> {
+		match (self.infer_type(start), self.infer_type(end)) {
+			(Some(Type::Int), Some(Type::Int)) | (None, _) | (_, None) => {}
+			(Some(start), Some(end)) => self.report(
+				ErrorCode::UnsupportedOperation,
+				format!("Unsupported range from {} to {}", start, end),
+				operator,
+			),
+		}
+
+		Some(Type::Range)
+	}
+
+	fn infer_array(
+		&mut self,
+		open_square_bracket: &Token,
+		elements: &[Expression],
+	) -> Option<Type> {
+		let mut element_type: Option<Type> = None;
+
+		for element in elements.iter() {
+			let r#type = match self.infer_type(element) {
+				Some(r#type) => r#type,
+				None => continue,
+			};
+
+			match &element_type {
+				_ if r#type == Type::Void => self.report(
+					ErrorCode::Semantic,
+					"Array elements can not be Void".to_string(),
+					open_square_bracket,
+				),
+				None => element_type = Some(r#type),
+				Some(first) if !first.accepts(&r#type) && !r#type.accepts(first) => {
+					let message = format!(
+						"Array elements should be of type {} but got {}",
+						first, r#type
+					);
+					self.report(ErrorCode::Semantic, message, open_square_bracket)
+				}
+				_ => {}
+			}
+		}
+
+		Some(Type::Array(Box::new(element_type.unwrap_or(Type::Void))))
+	}
+
+	// Maps with entries of unknown types are accepted like empty maps
+	fn infer_map(
+		&mut self,
+		open_curly_bracket: &Token,
+		entries: &[(Expression, Expression)],
+	) -> Option<Type> {
+		let mut entry_type: Option<(Type, Type)> = None;
+		let mut is_known = true;
+
+		for (key, value) in entries.iter() {
+			let (key, value) = match (self.infer_type(key), self.infer_type(value)) {
+				(Some(key), _) if !matches!(key, Type::Int | Type::Bool | Type::String) => {
+					self.report(
+						ErrorCode::UnsupportedType,
+						format!("Unable to use {} as map key", key),
+						open_curly_bracket,
+					);
+					continue;
+				}
+				(_, Some(Type::Void)) => {
+					self.report(
+						ErrorCode::Semantic,
+						"Map values can not be Void".to_string(),
+						open_curly_bracket,
+					);
+					continue;
+				}
+				(Some(key), Some(value)) => (key, value),
+				_ => {
+					is_known = false;
+					continue;
+				}
+			};
+
+			match &entry_type {
+				None => entry_type = Some((key, value)),
+				Some((first_key, first_value))
+					if !first_key.accepts(&key) && !key.accepts(first_key)
+						|| !first_value.accepts(&value) && !value.accepts(first_value) =>
+				{
+					let message = format!(
+						"Can not insert {} and {} into Map<{}, {}>",
+						key, value, first_key, first_value
+					);
+					self.report(ErrorCode::Semantic, message, open_curly_bracket)
+				}
+				_ => {}
+			}
+		}
+
+		match entry_type {
+			Some((key, value)) if is_known => Some(Type::Map(Box::new(key), Box::new(value))),
+			_ => Some(Type::Map(Box::new(Type::Void), Box::new(Type::Void))),
+		}
+	}
+
+	fn infer_index(
+		&mut self,
+		indexed: &Expression,
+		open_square_bracket: &Token,
+		index: &Expression,
+	) -> Option<Type> {
+		let indexed = self.infer_type(indexed);
+		let index = self.infer_type(index);
+
+		match (indexed?, index) {
+			(Type::Array(element), index) => {
+				if let Some(index) = index.filter(|index| *index != Type::Int) {
+					self.report(
+						ErrorCode::Semantic,
+						format!("Array index should be of type Int but got {}", index),
+						open_square_bracket,
+					);
+				}
+
+				Analyzer::known_type(*element)
+			}
+			(Type::Map(key, value), index) => {
+				if let Some(index) = index.filter(|index| *key != Type::Void && !key.accepts(index))
+				{
+					self.report(
+						ErrorCode::UnsupportedType,
+						format!("Unable to use {} as key of Map<{}, {}>", index, key, value),
+						open_square_bracket,
+					);
+				}
+
+				Analyzer::known_type(*value)
+			}
+			(indexed, _) => {
+				self.report(
+					ErrorCode::UnsupportedType,
+					format!("Unable to index {}", indexed),
+					open_square_bracket,
+				);
+				None
+			}
+		}
+	}
+
+	// Lambdas can use the variables of the scope they are created in
+	fn infer_lambda(&mut self, parameters: &[(Token, Token)], body: &Statement) -> Option<Type> {
+		let bindings = self.parameter_bindings(parameters);
+		let parameter_types = parameters
+			.iter()
+			.map(|(_, r#type)| self.resolve_type(r#type))
+			.collect::<Option<Vec<Type>>>();
+
+		self.returns.push(ReturnContext::Lambda(Vec::new()));
+		self.bindings.push(bindings);
+
+		self.check_types(body);
+
+		self.bindings.pop();
+		let result = match self.returns.pop() {
+			Some(ReturnContext::Lambda(types)) => Analyzer::common_type(types),
+			_ => None,
+		};
+
+		Some(Type::Function(
+			parameter_types?,
+			Box::new(result.unwrap_or(Type::Void)),
+		))
+	}
+
+	fn infer_template(&mut self, token: &Token, parts: &[Expression]) -> Option<Type> {
+		for part in parts.iter() {
+			match self.infer_type(part) {
+				Some(Type::Int) | Some(Type::Float) | Some(Type::Bool) | Some(Type::String)
+				| None => {}
+				Some(r#type) => self.report(
+					ErrorCode::UnsupportedType,
+					format!("Unable to interpolate {}", r#type),
+					token,
+				),
+			}
+		}
+
+		Some(Type::String)
+	}
+}
@@ -0,0 +1,219 @@
+use crate::analyzer::Analyzer;
+use crate::{Error, ErrorCode, Expression, Literal, Statement, Type};
+
+// Ways control can leave a statement
+#[derive(Clone, Copy)]
+struct Flow {
+	completes: bool, // Control continues with the next statement
+	breaks: bool,    // Control breaks out of the enclosing loop
+}
+
+impl Flow {
+	const COMPLETES: Flow = Flow {
+		completes: true,
+		breaks: false,
+	};
+
+	const EXITS: Flow = Flow {
+		completes: false,
+		breaks: false,
+	};
+
+	// Control takes one of both paths
+	fn or(self, other: Flow) -> Flow {
+		Flow {
+			completes: self.completes || other.completes,
+			breaks: self.breaks || other.breaks,
+		}
+	}
+}
+
+// Control flow checks, the paths through the statements are followed without evaluating them
+impl Analyzer {
+	pub(super) fn check_flow(&mut self, statements: &[Statement]) {
+		let (declarations, statements): (Vec<&Statement>, Vec<&Statement>) = statements
+			.iter()
+			.partition(|statement| Analyzer::is_declaration(statement));
+
+		// Declarations are hoisted, they are never unreachable
+		for declaration in declarations {
+			self.follow_statement(declaration, false);
+		}
+
+		self.follow_statements(statements, false);
+	}
+
+	fn is_declaration(statement: &Statement) -> bool {
+		matches!(
+			Analyzer::get_declaration(statement),
+			Statement::Function(..) | Statement::Record(..) | Statement::Enum(..)
+		)
+	}
+
+	// Only the first unreachable statement of a body is reported
+	fn follow_statements<'a>(
+		&mut self,
+		statements: impl IntoIterator<Item = &'a Statement>,
+		in_loop: bool,
+	) -> Flow {
+		let mut flow = Flow::COMPLETES;
+		let mut reported = false;
+
+		for statement in statements {
+			if !flow.completes && !reported && !matches!(statement, Statement::Comment(_)) {
+				self.warn("Unreachable statement".to_string(), statement.span());
+				reported = true;
+			}
+
+			let next = self.follow_statement(statement, in_loop);
+
+			if flow.completes {
+				flow = Flow {
+					completes: next.completes,
+					breaks: flow.breaks || next.breaks,
+				};
+			}
+		}
+
+		flow
+	}
+
+	fn follow_statement(&mut self, statement: &Statement, in_loop: bool) -> Flow {
+		use Statement::*;
+
+		// Lambdas are called later, break and continue can not leave them
+		for lambda in Analyzer::find_lambdas(statement) {
+			if let self::Expression::Lambda(open_parenthesis, _, arrow, body) = lambda {
+				let body_flow = self.follow_statement(body, false);
+
+				if body_flow.completes && Analyzer::returns_value(body) {
+					self.errors.push(
+						Error::new_semantic_error(
+							"Lambda does not return a value on every path".to_string(),
+							open_parenthesis.span().to(arrow.span()),
+						)
+						.with_code(ErrorCode::MissingReturn)
+						.with_note(
+							"The end of the body can be reached without a return statement"
+								.to_string(),
+						),
+					);
+				}
+			}
+		}
+
+		match statement {
+			Body(_, statements, _) => self.follow_statements(statements, in_loop),
+			Documentation(_, declaration) => self.follow_statement(declaration, in_loop),
+			Function(_, r#type, identifier, _, body) => {
+				let body = self.follow_statement(body, false);
+
+				let result = match r#type {
+					Some(r#type) => self.resolve_type(r#type),
+					None => Some(Type::Void),
+				};
+
+				match result {
+					Some(result) if body.completes && result != Type::Void => {
+						let message = format!(
+							"Function {} does not return {} on every path",
+							Analyzer::get_name(identifier).unwrap_or_default(),
+							result
+						);
+
+						self.errors.push(
+							Error::new_semantic_error(message, identifier.span())
+								.with_code(ErrorCode::MissingReturn)
+								.with_note(
+									"The end of the body can be reached without a return statement"
+										.to_string(),
+								),
+						);
+					}
+					_ => {}
+				}
+
+				Flow::COMPLETES
+			}
+			Loop(_, body) => Flow {
+				completes: self.follow_statement(body, true).breaks,
+				breaks: false,
+			},
+			While(_, condition, body) => {
+				let body = self.follow_statement(body, true);
+
+				Flow {
+					completes: !Analyzer::is_always_true(condition) || body.breaks,
+					breaks: false,
+				}
+			}
+			For(_, _, _, body) => {
+				self.follow_statement(body, true);
+				Flow::COMPLETES
+			}
+			If(_, _, true_branch, false_branch) => {
+				let true_branch = self.follow_statement(true_branch, in_loop);
+
+				match false_branch {
+					Some(false_branch) => {
+						true_branch.or(self.follow_statement(false_branch, in_loop))
+					}
+					None => true_branch.or(Flow::COMPLETES),
+				}
+			}
+			// Matches are exhaustive, one of the arms is taken
+			Match(_, _, arms, default, _) => arms
+				.iter()
+				.map(|arm| &arm.body)
+				.chain(default.as_deref())
+				.fold(Flow::EXITS, |flow, body| {
+					flow.or(self.follow_statement(body, in_loop))
+				}),
+			Return(..) => Flow::EXITS,
+			Break(token) => {
+				if !in_loop {
+					self.report(
+						ErrorCode::OutsideOfLoop,
+						"Unable to break outside of a loop".to_string(),
+						token,
+					);
+				}
+
+				Flow {
+					completes: false,
+					breaks: in_loop,
+				}
+			}
+			Continue(token) => {
+				if !in_loop {
+					self.report(
+						ErrorCode::OutsideOfLoop,
+						"Unable to continue outside of a loop".to_string(),
+						token,
+					);
+				}
+
+				Flow::EXITS
+			}
+			_ => Flow::COMPLETES,
+		}
+	}
+
+	// Lambdas have no declared type, they return a value when one of their return statements does
+	fn returns_value(statement: &Statement) -> bool {
+		match statement {
+			Statement::Return(_, expression) => expression.is_some(),
+			statement => Analyzer::get_bodies(statement)
+				.into_iter()
+				.any(Analyzer::returns_value),
+		}
+	}
+
+	fn is_always_true(condition: &Expression) -> bool {
+		match condition {
+			Expression::Literal(token) => matches!(token.literal, Some(Literal::Boolean(true))),
+			Expression::Grouping(_, expression, _) => Analyzer::is_always_true(expression),
+			_ => false,
+		}
+	}
+}
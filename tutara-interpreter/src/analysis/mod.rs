@@ -0,0 +1,12 @@
+mod calls;
+
+mod common;
+
+mod expressions;
+
+mod flow;
+
+mod resolver;
+pub use resolver::*;
+
+mod statements;
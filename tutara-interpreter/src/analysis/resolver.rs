@@ -0,0 +1,219 @@
+use crate::analyzer::{Analyzer, Binding, Signature};
+use crate::{Error, ErrorCode, Literal, Statement, Token, TokenType, Type};
+
+use std::collections::HashMap;
+
+// Top level declarations of a script, they are evaluated before the other statements of the script
+pub struct Declarations {
+	pub types: Vec<Statement>,      // Records and enums
+	pub functions: Vec<Statement>,  // Functions
+	pub statements: Vec<Statement>, // Every other statement
+}
+
+// Name resolution, identifiers are resolved to the declarations of the script
+impl Analyzer {
+	// Records, enums and functions can be used before they are declared, their order in the script is kept
+	pub fn hoist(statements: Vec<Statement>) -> Declarations {
+		let mut declarations = Declarations {
+			types: Vec::new(),
+			functions: Vec::new(),
+			statements: Vec::new(),
+		};
+
+		for statement in statements {
+			match Analyzer::get_declaration(&statement) {
+				Statement::Record(..) | Statement::Enum(..) => declarations.types.push(statement),
+				Statement::Function(..) => declarations.functions.push(statement),
+				_ => declarations.statements.push(statement),
+			}
+		}
+
+		declarations
+	}
+
+	// Documentation is attached to the declaration it describes
+	pub fn get_declaration(statement: &Statement) -> &Statement {
+		match statement {
+			Statement::Documentation(_, statement) => Analyzer::get_declaration(statement),
+			statement => statement,
+		}
+	}
+
+	// Variables that are not declared by the script, like the inputs of the interpreter
+	pub fn declare_variable(&mut self, name: &str, r#type: Type) {
+		let identifier = Token::new(
+			TokenType::Identifier,
+			Some(Literal::String(name.to_string())),
+			0,
+			0,
+			name.len() as u32,
+			0,
+		);
+
+		Analyzer::insert_binding(&mut self.bindings[0], &identifier, true, Some(r#type));
+	}
+
+	// Records, enums and functions are declared up front, so functions can use the ones declared after them
+	pub(super) fn declare(&mut self, statements: &[Statement]) {
+		let mut declarations: Vec<&Statement> = Vec::new();
+
+		for statement in statements.iter() {
+			Analyzer::find_declarations(statement, &mut declarations);
+		}
+
+		self.check_duplicates(&declarations);
+
+		// Enum names are needed to resolve the types used by the declarations
+		for declaration in declarations.iter() {
+			if let Statement::Enum(_, identifier, _, _) = declaration {
+				if let Some(name) = Analyzer::get_name(identifier) {
+					self.variants.insert(name, Vec::new());
+				}
+			}
+		}
+
+		for declaration in declarations {
+			match declaration {
+				Statement::Record(_, identifier, fields, _) => {
+					let fields = fields
+						.iter()
+						.filter_map(|(field, r#type)| {
+							Some((Analyzer::get_name(field)?, self.resolve_type(r#type)))
+						})
+						.collect();
+
+					if let Some(name) = Analyzer::get_name(identifier) {
+						self.records.insert(name, fields);
+					}
+				}
+				Statement::Enum(_, identifier, variants, _) => {
+					let variants = variants
+						.iter()
+						.filter_map(|(variant, types)| {
+							Some((
+								Analyzer::get_name(variant)?,
+								types
+									.iter()
+									.map(|r#type| self.resolve_type(r#type))
+									.collect(),
+							))
+						})
+						.collect();
+
+					if let Some(name) = Analyzer::get_name(identifier) {
+						self.variants.insert(name, variants);
+					}
+				}
+				Statement::Function(_, r#type, identifier, parameters, _) => {
+					let signature = Signature {
+						parameters: parameters
+							.iter()
+							.filter_map(|(parameter, r#type)| {
+								Some((Analyzer::get_name(parameter)?, self.resolve_type(r#type)))
+							})
+							.collect(),
+						result: match r#type {
+							Some(r#type) => self.resolve_type(r#type),
+							None => Some(Type::Void),
+						},
+					};
+
+					if let Some(name) = Analyzer::get_name(identifier) {
+						self.functions.insert(name, signature);
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+
+	// Records and enums share the names of types, functions have names of their own
+	fn check_duplicates(&mut self, declarations: &[&Statement]) {
+		let mut types: HashMap<String, &Token> = HashMap::new();
+		let mut functions: HashMap<String, &Token> = HashMap::new();
+
+		for declaration in declarations {
+			match declaration {
+				Statement::Record(_, identifier, fields, _) => {
+					self.check_duplicate("Type", identifier, &mut types);
+
+					let mut names = HashMap::new();
+					for (field, _) in fields.iter() {
+						self.check_duplicate("Field", field, &mut names);
+					}
+				}
+				Statement::Enum(_, identifier, variants, _) => {
+					self.check_duplicate("Type", identifier, &mut types);
+
+					let mut names = HashMap::new();
+					for (variant, _) in variants.iter() {
+						self.check_duplicate("Variant", variant, &mut names);
+					}
+				}
+				Statement::Function(_, _, identifier, _, _) => {
+					self.check_duplicate("Function", identifier, &mut functions);
+				}
+				_ => {}
+			}
+		}
+	}
+
+	fn check_duplicate<'a>(
+		&mut self,
+		kind: &str,
+		identifier: &'a Token,
+		declared: &mut HashMap<String, &'a Token>,
+	) {
+		let name = match Analyzer::get_name(identifier) {
+			Some(name) => name,
+			None => return,
+		};
+
+		match declared.get(&name) {
+			Some(declaration) => self.errors.push(
+				Error::new_semantic_error(
+					format!("{} {} is already declared", kind, name),
+					identifier.span(),
+				)
+				.with_code(ErrorCode::DuplicateDeclaration)
+				.with_label(
+					declaration.span(),
+					format!("{} is first declared here", name),
+				),
+			),
+			None => {
+				declared.insert(name, identifier);
+			}
+		}
+	}
+
+	fn find_declarations<'a>(statement: &'a Statement, declarations: &mut Vec<&'a Statement>) {
+		match statement {
+			Statement::Record(..) | Statement::Enum(..) => declarations.push(statement),
+			Statement::Function(_, _, _, _, body) => {
+				declarations.push(statement);
+				Analyzer::find_declarations(body, declarations);
+			}
+			_ => {
+				for body in Analyzer::get_bodies(statement) {
+					Analyzer::find_declarations(body, declarations);
+				}
+			}
+		}
+	}
+
+	// Reports variables that are not declared in the scope of the identifier
+	pub(super) fn resolve_variable(&mut self, identifier: &Token) -> Option<&Binding> {
+		let name = Analyzer::get_name(identifier)?;
+
+		if self.find_binding(&name).is_none() {
+			self.report(
+				ErrorCode::UnknownVariable,
+				format!("Unknown variable {}", name),
+				identifier,
+			);
+		}
+
+		self.find_binding(&name)
+	}
+}
@@ -0,0 +1,341 @@
+use crate::analyzer::{Analyzer, Binding, ReturnContext};
+use crate::{Error, ErrorCode, Expression, MatchArm, Statement, Token, TokenType, Type};
+
+use std::collections::HashMap;
+
+// Type checks, the statements of a script are checked together before they are evaluated
+impl Analyzer {
+	// Checks the statements and returns every error that was found
+	pub fn check(&mut self, statements: &[Statement]) -> Vec<Error> {
+		self.declare(statements);
+
+		for statement in statements.iter() {
+			self.check_types(statement);
+		}
+
+		self.check_flow(statements);
+
+		std::mem::take(&mut self.errors)
+	}
+
+	// Warnings do not stop the script from being evaluated, they are kept until they are taken
+	pub fn take_warnings(&mut self) -> Vec<Error> {
+		std::mem::take(&mut self.warnings)
+	}
+
+	pub(super) fn check_types(&mut self, statement: &Statement) {
+		use Statement::*;
+
+		match statement {
+			Expression(expression) => {
+				self.infer_type(expression);
+			}
+			Declaration(mutability, type_specification, expression) => {
+				self.check_declaration(mutability, type_specification, expression)
+			}
+			Body(_, statements, _) => {
+				self.bindings.push(HashMap::new());
+
+				for statement in statements.iter() {
+					self.check_types(statement);
+				}
+
+				self.bindings.pop();
+			}
+			Function(_, r#type, identifier, parameters, body) => {
+				self.check_function(r#type, identifier, parameters, body)
+			}
+			If(_, condition, true_branch, false_branch) => {
+				self.check_condition(condition, "condition");
+				self.check_types(true_branch);
+
+				if let Some(false_branch) = false_branch {
+					self.check_types(false_branch);
+				}
+			}
+			While(_, condition, body) => {
+				self.check_condition(condition, "condition");
+				self.check_types(body);
+			}
+			For(_, identifier, iterable, body) => {
+				let item_type = self.infer_item_type(iterable);
+
+				let mut bindings = HashMap::new();
+				if let self::Expression::Identifier(identifier) = identifier {
+					Analyzer::insert_binding(&mut bindings, identifier, false, item_type);
+				}
+
+				self.bindings.push(bindings);
+				self.check_types(body);
+				self.bindings.pop();
+			}
+			Match(token, subject, arms, default, _) => {
+				self.check_arms(token, subject, arms, |analyzer, body| {
+					analyzer.check_types(body);
+					None
+				});
+
+				if let Some(default) = default {
+					self.check_types(default);
+				}
+			}
+			Return(token, expression) => self.check_return(token, expression),
+			Record(_, _, fields, _) => {
+				for (_, r#type) in fields.iter() {
+					self.check_type(r#type);
+				}
+			}
+			Enum(_, _, variants, _) => {
+				for r#type in variants.iter().flat_map(|(_, types)| types) {
+					self.check_type(r#type);
+				}
+			}
+			Loop(_, body) | Documentation(_, body) => self.check_types(body),
+			Comment(_) | Break(_) | Continue(_) => {}
+		}
+	}
+
+	fn check_declaration(
+		&mut self,
+		mutability: &Token,
+		type_specification: &Option<Token>,
+		expression: &Expression,
+	) {
+		let declared_type = match type_specification {
+			Some(type_specification) => self.check_type(type_specification),
+			None => None,
+		};
+
+		let (identifier, value_type) = match expression {
+			Expression::Assignment(identifier, _, value) => (identifier, self.infer_type(value)),
+			Expression::Identifier(identifier) => (identifier, None),
+			_ => {
+				self.infer_type(expression);
+				return;
+			}
+		};
+
+		if let (Some(declared_type), Some(value_type)) = (&declared_type, &value_type) {
+			if !declared_type.accepts(value_type) {
+				self.report(
+					ErrorCode::MismatchedTypes,
+					format!(
+						"Can not assign {} to variable of type {}",
+						value_type, declared_type
+					),
+					type_specification.as_ref().unwrap(),
+				);
+			}
+		}
+
+		let mutable = mutability.r#type == TokenType::Var;
+		let scope = self.bindings.last_mut().unwrap();
+		Analyzer::insert_binding(scope, identifier, mutable, declared_type.or(value_type));
+	}
+
+	// Functions can not use the variables outside of their body
+	fn check_function(
+		&mut self,
+		r#type: &Option<Token>,
+		identifier: &Token,
+		parameters: &[(Token, Token)],
+		body: &Statement,
+	) {
+		let result = match r#type {
+			Some(r#type) => self.check_type(r#type),
+			None => Some(Type::Void),
+		};
+
+		let bindings = vec![self.parameter_bindings(parameters)];
+		let outer = std::mem::replace(&mut self.bindings, bindings);
+		self.returns
+			.push(ReturnContext::Function(identifier.clone(), result));
+
+		self.check_types(body);
+
+		self.returns.pop();
+		self.bindings = outer;
+	}
+
+	// Parameters can be assigned, but only with values of their type
+	pub(super) fn parameter_bindings(
+		&mut self,
+		parameters: &[(Token, Token)],
+	) -> HashMap<String, Binding> {
+		let mut bindings = HashMap::new();
+
+		for (parameter, parameter_type) in parameters.iter() {
+			let r#type = self.check_type(parameter_type);
+			Analyzer::insert_binding(&mut bindings, parameter, true, r#type);
+		}
+
+		bindings
+	}
+
+	// Functions return their declared type, lambdas return the type of their return statements
+	fn check_return(&mut self, token: &Token, expression: &Option<Expression>) {
+		let r#type = match expression {
+			Some(expression) => self.infer_type(expression),
+			None => Some(Type::Void),
+		};
+
+		let token = match expression {
+			Some(expression) => Analyzer::get_token(expression),
+			None => token,
+		};
+
+		let error = match (self.returns.last_mut(), &r#type) {
+			(Some(ReturnContext::Function(identifier, Some(result))), Some(r#type))
+				if !result.accepts(r#type) =>
+			{
+				Some(format!(
+					"Function {} should return {} but returned {}",
+					Analyzer::get_name(identifier).unwrap_or_default(),
+					result,
+					r#type
+				))
+			}
+			// The first known type a lambda returns is the type all of its returns should have
+			(Some(ReturnContext::Lambda(types)), _) => {
+				let result = types.iter().flatten().next().cloned();
+				types.push(r#type.clone());
+
+				match (result, r#type) {
+					(Some(result), Some(r#type)) if !result.accepts(&r#type) => Some(format!(
+						"Lambda should return {} but returned {}",
+						result, r#type
+					)),
+					_ => None,
+				}
+			}
+			_ => None,
+		};
+
+		if let Some(message) = error {
+			self.report(ErrorCode::Semantic, message, token);
+		}
+	}
+
+	pub(super) fn check_condition(&mut self, condition: &Expression, context: &str) {
+		match self.infer_type(condition) {
+			Some(Type::Bool) | None => {}
+			Some(r#type) => self.report(
+				ErrorCode::Semantic,
+				format!("Unsupported type {} in {}", r#type, context),
+				Analyzer::get_token(condition),
+			),
+		}
+	}
+
+	fn infer_item_type(&mut self, iterable: &Expression) -> Option<Type> {
+		match self.infer_type(iterable)? {
+			Type::String => Some(Type::String),
+			Type::Range => Some(Type::Int),
+			Type::Array(element) => Analyzer::known_type(*element),
+			r#type => {
+				self.report(
+					ErrorCode::UnsupportedType,
+					format!("Unable to iterate over {}", r#type),
+					Analyzer::get_token(iterable),
+				);
+				None
+			}
+		}
+	}
+
+	// Checks the patterns and guards of match arms and returns the types of their bodies, variant patterns bind
+	// the values of the variant in the scope of the arm
+	pub(super) fn check_arms<T>(
+		&mut self,
+		token: &Token,
+		subject: &Expression,
+		arms: &[MatchArm<T>],
+		check_body: fn(&mut Analyzer, &T) -> Option<Type>,
+	) -> Vec<Option<Type>> {
+		let subject = self.infer_type(subject);
+		let mut types = Vec::new();
+
+		for arm in arms.iter() {
+			let mut bindings = HashMap::new();
+
+			for pattern in arm.patterns.iter() {
+				let pattern_type = match self.get_variant_pattern(pattern) {
+					Some((name, variant, identifiers)) => {
+						let value_types = self.get_variant_types(&name, variant);
+
+						for (index, identifier) in identifiers.into_iter().enumerate() {
+							let r#type = value_types
+								.as_ref()
+								.and_then(|types| types.get(index).cloned().flatten());
+							Analyzer::insert_binding(&mut bindings, identifier, false, r#type);
+						}
+
+						Some(Type::Enum(name))
+					}
+					None => self.infer_type(pattern),
+				};
+
+				match (&subject, &pattern_type) {
+					(Some(Type::Int), Some(Type::Range)) => {}
+					(Some(subject), Some(pattern_type))
+						if !subject.accepts(pattern_type) && !pattern_type.accepts(subject) =>
+					{
+						self.report(
+							ErrorCode::UnsupportedType,
+							format!("Unable to match {} against {}", subject, pattern_type),
+							token,
+						)
+					}
+					_ => {}
+				}
+			}
+
+			self.bindings.push(bindings);
+
+			if let Some(guard) = &arm.guard {
+				self.check_condition(guard, "guard");
+			}
+
+			types.push(check_body(self, &arm.body));
+			self.bindings.pop();
+		}
+
+		types
+	}
+
+	// Variant patterns look like Status.NotFound or Status.Failed(message)
+	fn get_variant_pattern<'a>(
+		&self,
+		pattern: &'a Expression,
+	) -> Option<(String, &'a Token, Vec<&'a Token>)> {
+		let (object, variant, arguments) = match pattern {
+			Expression::Get(object, variant) => (object, variant, &[][..]),
+			Expression::Call(function, _, arguments, _) => match &**function {
+				Expression::Get(object, variant) => (object, variant, &arguments[..]),
+				_ => return None,
+			},
+			_ => return None,
+		};
+
+		let name = self.get_enum_name(object)?;
+		let identifiers = arguments
+			.iter()
+			.filter_map(|argument| match argument {
+				Expression::Identifier(identifier) => Some(identifier),
+				_ => None,
+			})
+			.collect();
+
+		Some((name, variant, identifiers))
+	}
+
+	fn get_variant_types(&self, name: &str, variant: &Token) -> Option<Vec<Option<Type>>> {
+		let variant = Analyzer::get_name(variant)?;
+
+		self.variants
+			.get(name)?
+			.iter()
+			.find(|(declared, _)| *declared == variant)
+			.map(|(_, types)| types.clone())
+	}
+}
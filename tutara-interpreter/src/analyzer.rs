@@ -1,15 +1,58 @@
 use crate::Error;
+use crate::ErrorCode;
 use crate::Expression;
+use crate::MatchArm;
 use crate::Result;
 use crate::Statement;
 use crate::Token;
 use crate::TokenType;
+use crate::Type;
 
-pub struct Analyzer {}
+use std::collections::HashMap;
+
+type Variant = (String, Vec<Option<Type>>); // Name, Value types
+
+pub struct Analyzer {
+	enums: HashMap<String, Vec<(String, usize)>>, // Enum, Vec<Variant(Name, Value count)>
+	pub(super) bindings: Vec<HashMap<String, Binding>>, // Variables by scope, the script root is the first scope
+	pub(super) functions: HashMap<String, Signature>,   // Declared functions by name
+	pub(super) records: HashMap<String, Vec<(String, Option<Type>)>>, // Record, Vec<Field(Name, Type)>
+	pub(super) variants: HashMap<String, Vec<Variant>>, // Enum, Vec<Variant>
+	pub(super) returns: Vec<ReturnContext>,             // Functions and lambdas being checked
+	pub(super) errors: Vec<Error>,
+	pub(super) warnings: Vec<Error>,
+}
+
+// Types are None when they can not be known before the script is evaluated
+pub(super) struct Binding {
+	pub(super) mutable: bool,
+	pub(super) identifier: Token,    // Identifier of the declaration
+	pub(super) r#type: Option<Type>, // Declared type or the type of the initial value when it is known
+}
+
+#[derive(Clone)]
+pub(super) struct Signature {
+	pub(super) parameters: Vec<(String, Option<Type>)>, // Vec<Parameter(Name, Type)>
+	pub(super) result: Option<Type>,
+}
+
+pub(super) enum ReturnContext {
+	Function(Token, Option<Type>), // Identifier, Declared result type
+	Lambda(Vec<Option<Type>>),     // Types of the returned values
+}
 
 impl Analyzer {
 	pub fn new() -> Analyzer {
-		Analyzer {}
+		Analyzer {
+			enums: HashMap::new(),
+			bindings: vec![HashMap::new()],
+			functions: HashMap::new(),
+			records: HashMap::new(),
+			variants: HashMap::new(),
+			returns: Vec::new(),
+			errors: Vec::new(),
+			warnings: Vec::new(),
+		}
 	}
 }
 
@@ -22,10 +65,20 @@ impl Default for Analyzer {
 impl Analyzer {
 	pub fn analyze(&mut self, statement: Statement) -> Result<Statement> {
 		use Statement::*;
-		
+
+		self.check_statement(&statement)?;
+
 		match statement {
 			Expression(_) => self.analyze_statement(statement),
-			Loop(_) => self.analyze_statement(statement),
+			Loop(..) => self.analyze_statement(statement),
+			Documentation(..) => self.analyze_statement(statement),
+			Body(..) => self.analyze_statement(statement),
+			Function(..) => self.analyze_statement(statement),
+			While(..) => self.analyze_statement(statement),
+			For(..) => self.analyze_statement(statement),
+			If(..) => self.analyze_statement(statement),
+			Match(..) => self.analyze_statement(statement),
+			Enum(..) => self.analyze_statement(statement),
 			_ => Ok(statement),
 		}
 	}
@@ -39,10 +92,89 @@ impl Analyzer {
 			Expression(expression) => {
 				Ok(Statement::Expression(self.analyze_expression(expression)?))
 			},
-			Loop(statement) => Ok(While(
-				Literal(Token::new(TokenType::Boolean, Some(Boolean(true)), 0, 0, 0)),
+			Loop(token, statement) => Ok(While(
+				token.clone(),
+				Literal(Token::new(
+					TokenType::Boolean,
+					Some(Boolean(true)),
+					token.line,
+					token.column,
+					token.length,
+					token.offset,
+				)),
 				Box::new(self.analyze(*statement)?),
 			)),
+			// Documentation is only used by tooling
+			Documentation(_, statement) => self.analyze(*statement),
+			Body(open_curly_bracket, statements, close_curly_bracket) => Ok(Body(
+				open_curly_bracket,
+				statements
+					.into_iter()
+					.map(|statement| self.analyze(statement))
+					.collect::<Result<Vec<Statement>>>()?,
+				close_curly_bracket,
+			)),
+			Function(token, r#type, identifier, parameters, body) => Ok(Function(
+				token,
+				r#type,
+				identifier,
+				parameters,
+				Box::new(self.analyze(*body)?),
+			)),
+			While(token, condition, body) => {
+				Ok(While(token, condition, Box::new(self.analyze(*body)?)))
+			}
+			For(token, identifier, iterable, body) => Ok(For(
+				token,
+				identifier,
+				iterable,
+				Box::new(self.analyze(*body)?),
+			)),
+			If(token, condition, true_branch, false_branch) => Ok(If(
+				token,
+				condition,
+				Box::new(self.analyze(*true_branch)?),
+				match false_branch {
+					Some(false_branch) => Some(Box::new(self.analyze(*false_branch)?)),
+					None => None,
+				},
+			)),
+			Statement::Match(token, subject, arms, default, close_curly_bracket) => {
+				Ok(Statement::Match(
+					token,
+					subject,
+					arms.into_iter()
+						.map(|arm| {
+							Ok(MatchArm {
+								patterns: arm.patterns,
+								guard: arm.guard,
+								body: self.analyze(arm.body)?,
+							})
+						})
+						.collect::<Result<Vec<MatchArm<Statement>>>>()?,
+					match default {
+						Some(default) => Some(Box::new(self.analyze(*default)?)),
+						None => None,
+					},
+					close_curly_bracket,
+				))
+			}
+			Enum(token, identifier, variants, close_curly_bracket) => {
+				if let Some(String(name)) = &identifier.literal {
+					self.enums.insert(
+						name.clone(),
+						variants
+							.iter()
+							.filter_map(|(variant, types)| match &variant.literal {
+								Some(String(variant)) => Some((variant.clone(), types.len())),
+								_ => None,
+							})
+							.collect(),
+					);
+				}
+
+				Ok(Enum(token, identifier, variants, close_curly_bracket))
+			}
 			_ => Ok(statement),
 		}
 	}
@@ -72,6 +204,17 @@ impl Analyzer {
 					expression.clone(),
 				)),
 			},
+			Set(target, assignment, expression) => match &assignment.r#type {
+				AssignPlus => self.operation_set(*target, assignment, *expression, Plus),
+				AssignMinus => self.operation_set(*target, assignment, *expression, Minus),
+				AssignMultiply => self.operation_set(*target, assignment, *expression, Multiply),
+				AssignDivision => self.operation_set(*target, assignment, *expression, Division),
+				AssignExponentiation => {
+					self.operation_set(*target, assignment, *expression, Exponentiation)
+				}
+				AssignModulo => self.operation_set(*target, assignment, *expression, Modulo),
+				_ => Ok(Set(target, assignment, expression)),
+			},
 			_ => Ok(expression.clone()),
 		}
 	}
@@ -96,6 +239,7 @@ impl Analyzer {
 					identifier.line,
 					identifier.column,
 					identifier.length,
+					identifier.offset,
 				),
 				Token::new(
 					TokenType::Assign,
@@ -103,6 +247,7 @@ impl Analyzer {
 					assignment.line,
 					assignment.column,
 					assignment.length,
+					assignment.offset,
 				),
 				Box::new(Binary(
 					Box::new(Identifier(Token::new(
@@ -111,6 +256,7 @@ impl Analyzer {
 						identifier.line,
 						identifier.column,
 						identifier.length,
+						identifier.offset,
 					))),
 					Token::new(
 						token_type,
@@ -118,13 +264,451 @@ impl Analyzer {
 						assignment.line,
 						assignment.column,
 						assignment.length,
+						assignment.offset,
 					),
 					Box::new(expression),
 				)),
 			)),
-			_ => Err(Error::new_compiler_error(
+			_ => Err(Error::new_semantic_error(
 				"Unsupported identifier".to_string(),
+				identifier.span(),
 			)),
 		}
 	}
+
+	fn operation_set(
+		&mut self,
+		target: Expression,
+		assignment: Token,
+		expression: Expression,
+		token_type: TokenType,
+	) -> Result<Expression> {
+		use Expression::*;
+
+		Ok(Set(
+			Box::new(target.clone()),
+			Token::new(
+				TokenType::Assign,
+				None,
+				assignment.line,
+				assignment.column,
+				assignment.length,
+				assignment.offset,
+			),
+			Box::new(Binary(
+				Box::new(target),
+				Token::new(
+					token_type,
+					None,
+					assignment.line,
+					assignment.column,
+					assignment.length,
+					assignment.offset,
+				),
+				Box::new(expression),
+			)),
+		))
+	}
+}
+
+// Semantic checks, nested statements are checked when they are analyzed
+impl Analyzer {
+	fn check_statement(&self, statement: &Statement) -> Result<()> {
+		use Statement::*;
+
+		match statement {
+			Expression(expression) | Declaration(_, _, expression) | Return(_, Some(expression)) => {
+				self.check_expression(expression)
+			}
+			If(_, condition, ..) | While(_, condition, _) => self.check_expression(condition),
+			For(_, _, iterable, _) => self.check_expression(iterable),
+			Match(token, subject, arms, default, _) => {
+				self.check_expression(subject)?;
+				self.check_match(token, arms, default.is_some())?;
+
+				for arm in arms.iter() {
+					if let Some(guard) = &arm.guard {
+						self.check_expression(guard)?;
+					}
+				}
+
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+
+	fn check_expression(&self, expression: &Expression) -> Result<()> {
+		use Expression::*;
+
+		match expression {
+			Literal(_) | Identifier(_) => Ok(()),
+			Binary(left, _, right)
+			| Range(left, _, right)
+			| Index(left, _, right, _)
+			| Set(left, _, right) => {
+				self.check_expression(left)?;
+				self.check_expression(right)
+			}
+			Unary(_, expression)
+			| Grouping(_, expression, _)
+			| Assignment(_, _, expression)
+			| Get(expression, _) => self.check_expression(expression),
+			Call(function, _, arguments, _) => {
+				self.check_expression(function)?;
+				arguments
+					.iter()
+					.try_for_each(|argument| self.check_expression(argument))
+			}
+			Array(_, elements, _) | Template(_, elements) => elements
+				.iter()
+				.try_for_each(|element| self.check_expression(element)),
+			Map(_, entries, _) => entries.iter().try_for_each(|(key, value)| {
+				self.check_expression(key)?;
+				self.check_expression(value)
+			}),
+			Record(_, fields, _) => fields
+				.iter()
+				.try_for_each(|(_, value)| self.check_expression(value)),
+			Match(token, subject, arms, default, _) => {
+				self.check_expression(subject)?;
+				self.check_match(token, arms, default.is_some())?;
+
+				for arm in arms.iter() {
+					if let Some(guard) = &arm.guard {
+						self.check_expression(guard)?;
+					}
+					self.check_expression(&arm.body)?;
+				}
+
+				match default {
+					Some(default) => self.check_expression(default),
+					None => Ok(()),
+				}
+			}
+			// Lambda bodies are analyzed when the lambda is evaluated
+			Lambda(..) => Ok(()),
+		}
+	}
+
+	// Matches without an else arm have to cover every variant of an enum with unguarded arms
+	fn check_match<T>(&self, token: &Token, arms: &[MatchArm<T>], has_default: bool) -> Result<()> {
+		let mut matched_enum: Option<String> = None;
+		let mut covered: Vec<String> = Vec::new();
+		let mut only_variants = true;
+
+		for arm in arms.iter() {
+			for pattern in arm.patterns.iter() {
+				match self.check_variant_pattern(pattern)? {
+					Some((r#enum, variant, bindings)) => {
+						if !bindings.is_empty() && arm.patterns.len() > 1 {
+							return Err(Error::new_semantic_error(
+								format!(
+									"Pattern {}.{} binds values and can not be combined with other patterns",
+									r#enum, variant
+								),
+								pattern.span(),
+							));
+						}
+
+						match &matched_enum {
+							Some(matched) if *matched != r#enum => only_variants = false,
+							_ => matched_enum = Some(r#enum),
+						}
+
+						if arm.guard.is_none() {
+							covered.push(variant);
+						}
+					}
+					None => only_variants = false,
+				}
+			}
+		}
+
+		if has_default {
+			return Ok(());
+		}
+
+		let variants = match (&matched_enum, only_variants) {
+			(Some(r#enum), true) => &self.enums[r#enum],
+			_ => {
+				return Err(Error::new_semantic_error(
+					"Expected else arm in match".to_string(),
+					token.span(),
+				)
+				.with_code(ErrorCode::NonExhaustiveMatch))
+			}
+		};
+
+		let missing: Vec<String> = variants
+			.iter()
+			.filter(|(variant, _)| !covered.contains(variant))
+			.map(|(variant, _)| format!("{}.{}", matched_enum.as_ref().unwrap(), variant))
+			.collect();
+
+		if missing.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::new_semantic_error(
+				format!("Match is not exhaustive, missing {}", missing.join(", ")),
+				token.span(),
+			)
+			.with_code(ErrorCode::NonExhaustiveMatch))
+		}
+	}
+
+	// Variant patterns look like Status.NotFound or Status.Failed(message) and bind their values
+	fn check_variant_pattern(
+		&self,
+		pattern: &Expression,
+	) -> Result<Option<(String, String, Vec<String>)>> {
+		use Expression::*;
+
+		let (object, variant, arguments) = match pattern {
+			Get(object, variant) => (object, variant, &[][..]),
+			Call(function, _, arguments, _) => match &**function {
+				Get(object, variant) => (object, variant, &arguments[..]),
+				_ => return Ok(None),
+			},
+			_ => return Ok(None),
+		};
+
+		let (r#enum, variants) = match &**object {
+			Identifier(Token {
+				literal: Some(crate::Literal::String(name)),
+				..
+			}) => match self.enums.get(name) {
+				Some(variants) => (name, variants),
+				None => return Ok(None),
+			},
+			_ => return Ok(None),
+		};
+
+		let name = match &variant.literal {
+			Some(crate::Literal::String(name)) => name,
+			_ => return Ok(None),
+		};
+
+		match variants.iter().find(|(declared, _)| declared == name) {
+			Some((_, count)) if *count == arguments.len() => {}
+			Some((_, count)) => {
+				return Err(Error::new_semantic_error(
+					format!(
+						"Variant {}.{} expects {} values but got {}",
+						r#enum,
+						name,
+						count,
+						arguments.len()
+					),
+					variant.span(),
+				)
+				.with_code(ErrorCode::ArgumentCount))
+			}
+			None => {
+				return Err(Error::new_semantic_error(
+					format!("Unknown variant {} of {}", name, r#enum),
+					variant.span(),
+				)
+				.with_code(ErrorCode::UnknownMember))
+			}
+		}
+
+		let mut bindings: Vec<String> = Vec::new();
+
+		for argument in arguments.iter() {
+			match argument {
+				Identifier(Token {
+					literal: Some(crate::Literal::String(binding)),
+					..
+				}) => bindings.push(binding.clone()),
+				_ => {
+					return Err(Error::new_semantic_error(
+						"Expected identifier in variant pattern".to_string(),
+						variant.span(),
+					)
+					.with_code(ErrorCode::ExpectedIdentifier))
+				}
+			}
+		}
+
+		Ok(Some((r#enum.clone(), name.clone(), bindings)))
+	}
+}
+
+// Closure conversion
+impl Analyzer {
+	// Names a lambda refers to besides its parameters, the ones that name a variable are captured
+	pub fn find_captures(parameters: &[(Token, Token)], body: &Statement) -> Vec<String> {
+		let parameters: Vec<&Option<crate::Literal>> = parameters
+			.iter()
+			.map(|(parameter, _)| &parameter.literal)
+			.collect();
+		let mut captures: Vec<String> = Vec::new();
+
+		Analyzer::visit_statement(body, &mut |expression| {
+			if let Expression::Identifier(identifier) | Expression::Assignment(identifier, ..) =
+				expression
+			{
+				if let Some(crate::Literal::String(name)) = &identifier.literal {
+					if !parameters.contains(&&identifier.literal) && !captures.contains(name) {
+						captures.push(name.clone());
+					}
+				}
+			}
+
+			true
+		});
+
+		captures
+	}
+
+	// Names captured by the lambdas in a function body
+	pub fn find_lambda_captures(body: &Statement) -> Vec<String> {
+		let mut captures: Vec<String> = Vec::new();
+
+		Analyzer::visit_statement(body, &mut |expression| {
+			if let Expression::Lambda(_, parameters, _, body) = expression {
+				for name in Analyzer::find_captures(parameters, body) {
+					if !captures.contains(&name) {
+						captures.push(name);
+					}
+				}
+			}
+
+			true
+		});
+
+		captures
+	}
+
+	// Lambdas in the expressions of a statement, without the lambdas nested in their bodies
+	pub fn find_lambdas(statement: &Statement) -> Vec<&Expression> {
+		let mut lambdas: Vec<&Expression> = Vec::new();
+
+		for expression in Analyzer::get_expressions(statement) {
+			Analyzer::visit_expression(expression, &mut |expression| {
+				if let Expression::Lambda(..) = expression {
+					lambdas.push(expression);
+					return false;
+				}
+
+				true
+			});
+		}
+
+		lambdas
+	}
+
+	// Visits the statement and its nested statements, named functions are skipped as they can not capture
+	fn visit_statement<'a>(
+		statement: &'a Statement,
+		visit: &mut dyn FnMut(&'a Expression) -> bool,
+	) {
+		for expression in Analyzer::get_expressions(statement) {
+			Analyzer::visit_expression(expression, visit);
+		}
+
+		for statement in Analyzer::get_bodies(statement) {
+			Analyzer::visit_statement(statement, visit);
+		}
+	}
+
+	// Visits the expression and its nested expressions as long as the visitor returns true
+	fn visit_expression<'a>(
+		expression: &'a Expression,
+		visit: &mut dyn FnMut(&'a Expression) -> bool,
+	) {
+		use Expression::*;
+
+		if !visit(expression) {
+			return;
+		}
+
+		match expression {
+			Literal(_) | Identifier(_) => {}
+			Binary(left, _, right)
+			| Range(left, _, right)
+			| Index(left, _, right, _)
+			| Set(left, _, right) => {
+				Analyzer::visit_expression(left, visit);
+				Analyzer::visit_expression(right, visit);
+			}
+			Unary(_, expression)
+			| Grouping(_, expression, _)
+			| Assignment(_, _, expression)
+			| Get(expression, _) => Analyzer::visit_expression(expression, visit),
+			Call(function, _, arguments, _) => {
+				Analyzer::visit_expression(function, visit);
+				arguments
+					.iter()
+					.for_each(|argument| Analyzer::visit_expression(argument, visit));
+			}
+			Array(_, elements, _) | Template(_, elements) => elements
+				.iter()
+				.for_each(|element| Analyzer::visit_expression(element, visit)),
+			Map(_, entries, _) => entries.iter().for_each(|(key, value)| {
+				Analyzer::visit_expression(key, visit);
+				Analyzer::visit_expression(value, visit);
+			}),
+			Record(_, fields, _) => fields
+				.iter()
+				.for_each(|(_, value)| Analyzer::visit_expression(value, visit)),
+			Match(_, subject, arms, default, _) => {
+				Analyzer::visit_expression(subject, visit);
+
+				for arm in arms.iter() {
+					arm.patterns
+						.iter()
+						.chain(arm.guard.iter())
+						.chain(std::iter::once(&arm.body))
+						.for_each(|expression| Analyzer::visit_expression(expression, visit));
+				}
+
+				if let Some(default) = default {
+					Analyzer::visit_expression(default, visit);
+				}
+			}
+			Lambda(_, _, _, body) => Analyzer::visit_statement(body, visit),
+		}
+	}
+
+	// Expressions that belong to the statement itself
+	fn get_expressions(statement: &Statement) -> Vec<&Expression> {
+		use Statement::*;
+
+		match statement {
+			Expression(expression) | Declaration(_, _, expression) | Return(_, Some(expression)) => {
+				vec![expression]
+			}
+			While(_, condition, _) | If(_, condition, ..) => vec![condition],
+			For(_, identifier, iterable, _) => vec![identifier, iterable],
+			Match(_, subject, arms, _, _) => std::iter::once(subject)
+				.chain(
+					arms.iter()
+						.flat_map(|arm| arm.patterns.iter().chain(arm.guard.iter())),
+				)
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	pub(super) fn get_bodies(statement: &Statement) -> Vec<&Statement> {
+		use Statement::*;
+
+		match statement {
+			Body(_, statements, _) => statements.iter().collect(),
+			Loop(_, body) | While(_, _, body) | For(_, _, _, body) | Documentation(_, body) => {
+				vec![body]
+			}
+			If(_, _, true_branch, false_branch) => std::iter::once(&**true_branch)
+				.chain(false_branch.as_deref())
+				.collect(),
+			Match(_, _, arms, default, _) => arms
+				.iter()
+				.map(|arm| &arm.body)
+				.chain(default.as_deref())
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
 }
@@ -1,4 +1,4 @@
-use crate::Token;
+use crate::{MatchArm, Span, Statement, Token};
 
 use std::fmt::{self, Debug};
 use serde::{Serialize, Deserialize};
@@ -9,10 +9,50 @@ pub enum Expression {
 	Identifier(Token),
 	Binary(Box<Expression>, Token, Box<Expression>),		// Binding two expressions with an operator
 	Unary(Token, Box<Expression>),							// Binding a expression with an operator
-	Grouping(Box<Expression>),								// Group of an expression between ( )
+	Grouping(Token, Box<Expression>, Token),				// (, Expression, )
 	Assignment(Token, Token, Box<Expression>),  			// Identifier, Assignment Operator, Expression
 	Get(Box<Expression>, Token),							// Called on, Called item
 	Call(Box<Expression>, Token, Vec<Expression>, Token),	// Identifier | Get, (, Literal | identifier ,)
+	Range(Box<Expression>, Token, Box<Expression>),			// Start, .. | ..=, End
+	Match(Token, Box<Expression>, Vec<MatchArm<Expression>>, Option<Box<Expression>>, Token), // match, Subject, Arms, Else, }
+	Array(Token, Vec<Expression>, Token),					// [, Elements, ]
+	Map(Token, Vec<(Expression, Expression)>, Token),		// {, Keys and values, }
+	Index(Box<Expression>, Token, Box<Expression>, Token),	// Indexed, [, Index, ]
+	Record(Token, Vec<(Token, Expression)>, Token),			// Identifier, Vec<Field(Identifier, Expression)>, }
+	Set(Box<Expression>, Token, Box<Expression>),			// Index | Get, Assignment Operator, Expression
+	Lambda(Token, Vec<(Token, Token)>, Token, Box<Statement>), // (, Vec<Parameter(Identifier, Type)>, ->, Body
+	Template(Token, Vec<Expression>),						// Template, Vec<Part(Literal | Expression)>
+}
+
+impl Expression {
+	// Expressions span from their first up to their last token
+	pub fn span(&self) -> Span {
+		use Expression::*;
+
+		match self {
+			Literal(token) | Identifier(token) | Template(token, _) => token.span(),
+			Binary(left, _, right) | Range(left, _, right) | Set(left, _, right) => {
+				left.span().to(right.span())
+			}
+			Unary(operator, expression) => operator.span().to(expression.span()),
+			Grouping(open_parenthesis, _, close_parenthesis) => {
+				open_parenthesis.span().to(close_parenthesis.span())
+			}
+			Assignment(identifier, _, value) => identifier.span().to(value.span()),
+			Get(object, property) => object.span().to(property.span()),
+			Call(function, _, _, close_parenthesis) => function.span().to(close_parenthesis.span()),
+			Match(token, .., close_curly_bracket) => token.span().to(close_curly_bracket.span()),
+			Index(indexed, _, _, close_square_bracket) => indexed.span().to(close_square_bracket.span()),
+			Array(open_square_bracket, _, close_square_bracket) => {
+				open_square_bracket.span().to(close_square_bracket.span())
+			}
+			Map(open_curly_bracket, _, close_curly_bracket) => {
+				open_curly_bracket.span().to(close_curly_bracket.span())
+			}
+			Record(identifier, _, close_curly_bracket) => identifier.span().to(close_curly_bracket.span()),
+			Lambda(open_parenthesis, _, _, body) => open_parenthesis.span().to(body.span()),
+		}
+	}
 }
 
 impl fmt::Display for Expression {
@@ -4,11 +4,21 @@ use std::fmt::{self, Debug};
 use super::Literal::*;
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Eq, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Literal {
-	Number(u32),
+	Integer(i64),
+	Float(f64),
 	String(String),
-	Boolean(bool)
+	Boolean(bool),
+	Template(Vec<TemplatePart>)
+}
+
+// Interpolated strings like 'Hello $name, ${a + b}' are split into their text and the source of the
+// embedded expressions, the position of the expression source is kept for error reporting.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub enum TemplatePart {
+	Text(String),
+	Expression(String, u32, u32, u32), // Source, line, column, offset
 }
 
 impl fmt::Display for Literal {
@@ -20,8 +30,13 @@ impl fmt::Display for Literal {
 impl PartialEq for Literal {
 	fn eq(&self, other: &Literal) -> bool {
 		match *self {
-			Number(ref a) => match *other {
-				Number(ref b) => a.eq(b),
+			Integer(ref a) => match *other {
+				Integer(ref b) => a.eq(b),
+				_ => false,
+			},
+			// Floats are compared by their bits, so NaN equals itself as Eq requires
+			Float(ref a) => match *other {
+				Float(ref b) => a.to_bits() == b.to_bits(),
 				_ => false,
 			},
 			String(ref a) => match *other {
@@ -32,6 +47,12 @@ impl PartialEq for Literal {
 				Boolean(ref b) => a.eq(b),
 				_ => false,
 			},
+			Template(ref a) => match *other {
+				Template(ref b) => a.eq(b),
+				_ => false,
+			},
 		}
 	}
 }
+
+impl Eq for Literal {}
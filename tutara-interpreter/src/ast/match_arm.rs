@@ -0,0 +1,10 @@
+use crate::Expression;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct MatchArm<T> {
+	pub patterns: Vec<Expression>, // Literal | Range | Variant
+	pub guard: Option<Expression>, // Condition
+	pub body: T,                   // Expression | Statement
+}
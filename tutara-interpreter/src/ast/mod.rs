@@ -1,14 +1,20 @@
 mod token;
 pub use token::Token;
 
+mod span;
+pub use span::Span;
+
 mod token_type;
 pub use token_type::TokenType;
 
 mod literal;
-pub use literal::Literal;
+pub use literal::{Literal, TemplatePart};
 
 mod expression;
 pub use expression::Expression;
 
 mod statement;
 pub use statement::Statement;
+
+mod match_arm;
+pub use match_arm::MatchArm;
@@ -0,0 +1,32 @@
+use serde::{Serialize, Deserialize};
+
+// Part of the source covered by a token, expression or statement, the end is exclusive
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub struct Span {
+	pub offset: u32, // Byte offset of the start in the source
+	pub line: u32,
+	pub column: u32,
+	pub end_line: u32,
+	pub end_column: u32,
+}
+
+impl Span {
+	pub fn new(offset: u32, line: u32, column: u32, end_line: u32, end_column: u32) -> Span {
+		Span {
+			offset,
+			line,
+			column,
+			end_line,
+			end_column,
+		}
+	}
+
+	// Span from the start of this span up to the end of the other span
+	pub fn to(self, other: Span) -> Span {
+		Span {
+			end_line: other.end_line,
+			end_column: other.end_column,
+			..self
+		}
+	}
+}
@@ -1,4 +1,4 @@
-use crate::{Expression, Token};
+use crate::{Expression, MatchArm, Span, Token};
 
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug};
@@ -8,24 +8,64 @@ pub enum Statement {
 	Expression(Expression),
 	Declaration(Token, Option<Token>, Expression), // var | val , Type , Assignment | Identifier
 	Comment(Token),
-	Body(Vec<Statement>),       // Statements
+	Documentation(Vec<Token>, Box<Statement>), // Doc comments , Declaration
+	Body(Token, Vec<Statement>, Token), // { , Statements , }
 	Function(
+		Token,                  // fun
 		Option<Token>,          // Type
 		Token,                  // Identifier
 		Vec<(Token, Token)>, // Vec<Parameter(Identifier, Type)>
 		Box<Statement>,         // Body
 	),
-	Loop(Box<Statement>),              // Body
-	While(Expression, Box<Statement>), // Condition , Body
+	Loop(Token, Box<Statement>),       // loop , Body
+	While(Token, Expression, Box<Statement>), // while , Condition , Body
 	For(
+		Token,          // for
 		Expression,     // Identifier
 		Expression,     // Iterable
 		Box<Statement>, // Body
 	),
-	Break,
-	Continue,
-	Return(Option<Expression>), // Option<Expression>
-	If(Expression, Box<Statement>, Option<Box<Statement>>), // Expression , Body A , Body B
+	Break(Token),    // break
+	Continue(Token), // continue
+	Return(Token, Option<Expression>), // return , Option<Expression>
+	If(Token, Expression, Box<Statement>, Option<Box<Statement>>), // if , Expression , Body A , Body B
+	Match(Token, Expression, Vec<MatchArm<Statement>>, Option<Box<Statement>>, Token), // match , Subject , Arms , Else , }
+	Record(Token, Token, Vec<(Token, Token)>, Token), // type , Identifier , Vec<Field(Identifier, Type)> , }
+	Enum(Token, Token, Vec<(Token, Vec<Token>)>, Token), // enum , Identifier , Vec<Variant(Identifier, Types)> , }
+}
+
+impl Statement {
+	// Statements span from their first up to their last token
+	pub fn span(&self) -> Span {
+		use Statement::*;
+
+		match self {
+			Expression(expression) => expression.span(),
+			Declaration(mutability, _, expression) => mutability.span().to(expression.span()),
+			Comment(token) | Break(token) | Continue(token) => token.span(),
+			Documentation(comments, declaration) => match comments.first() {
+				Some(comment) => comment.span().to(declaration.span()),
+				None => declaration.span(),
+			},
+			Body(open_curly_bracket, _, close_curly_bracket) => {
+				open_curly_bracket.span().to(close_curly_bracket.span())
+			}
+			Function(token, .., body) => token.span().to(body.span()),
+			Loop(token, body) => token.span().to(body.span()),
+			While(token, _, body) | For(token, _, _, body) => token.span().to(body.span()),
+			Return(token, expression) => match expression {
+				Some(expression) => token.span().to(expression.span()),
+				None => token.span(),
+			},
+			If(token, _, true_branch, false_branch) => match false_branch {
+				Some(false_branch) => token.span().to(false_branch.span()),
+				None => token.span().to(true_branch.span()),
+			},
+			Match(token, .., close_curly_bracket)
+			| Record(token, .., close_curly_bracket)
+			| Enum(token, .., close_curly_bracket) => token.span().to(close_curly_bracket.span()),
+		}
+	}
 }
 
 impl fmt::Display for Statement {
@@ -54,54 +94,110 @@ impl PartialEq for Statement {
 				Comment(ref b_comment) => a_comment.eq(b_comment),
 				_ => false,
 			},
-			Body(ref a_body) => match *other {
-				Body(ref b_body) => a_body.eq(b_body),
-				_ => false,
-			},
-			Function(ref a_type_specification, ref a_identifier, ref a_parameters, ref a_body) => {
-				match *other {
-					Function(
-						ref b_type_specification,
-						ref b_identifier,
-						ref b_parameters,
-						ref b_body,
-					) => {
-						a_type_specification.eq(b_type_specification)
-							&& a_identifier.eq(b_identifier)
-							&& a_parameters.eq(b_parameters)
-							&& a_body.eq(b_body)
-					}
-					_ => false,
+			Documentation(ref a_comments, ref a_statement) => match *other {
+				Documentation(ref b_comments, ref b_statement) => {
+					a_comments.eq(b_comments) && a_statement.eq(b_statement)
 				}
-			}
-			Loop(ref a_body) => match *other {
-				Loop(ref b_body) => a_body.eq(b_body),
 				_ => false,
 			},
-			While(ref a_condition, ref a_body) => match *other {
-				While(ref b_condition, ref b_body) => {
-					a_condition.eq(b_condition) && a_body.eq(b_body)
+			Body(ref a_token, ref a_body, ref a_close) => match *other {
+				Body(ref b_token, ref b_body, ref b_close) => {
+					a_token.eq(b_token) && a_body.eq(b_body) && a_close.eq(b_close)
 				}
 				_ => false,
 			},
-			For(ref a_identifier, ref a_iterable, ref a_body) => match *other {
-				For(ref b_identifier, ref b_iterable, ref b_body) => {
-					a_identifier.eq(b_identifier) && a_iterable.eq(b_iterable) && a_body.eq(b_body)
+			Function(
+				ref a_token,
+				ref a_type_specification,
+				ref a_identifier,
+				ref a_parameters,
+				ref a_body,
+			) => match *other {
+				Function(
+					ref b_token,
+					ref b_type_specification,
+					ref b_identifier,
+					ref b_parameters,
+					ref b_body,
+				) => {
+					a_token.eq(b_token)
+						&& a_type_specification.eq(b_type_specification)
+						&& a_identifier.eq(b_identifier)
+						&& a_parameters.eq(b_parameters)
+						&& a_body.eq(b_body)
 				}
 				_ => false,
 			},
-			Break => matches!(*other, Break),
-			Continue => matches!(*other, Continue),
-			Return(ref a_expression) => match *other {
-				Return(ref b_expression) => a_expression.eq(b_expression),
+			Loop(ref a_token, ref a_body) => match *other {
+				Loop(ref b_token, ref b_body) => a_token.eq(b_token) && a_body.eq(b_body),
 				_ => false,
 			},
-			If(ref a_expression, ref a_body, ref a_else) => match *other {
-				If(ref b_expression, ref b_body, ref b_else) => {
-					a_expression.eq(b_expression) && a_body.eq(b_body) && a_else.eq(b_else)
+			While(ref a_token, ref a_condition, ref a_body) => match *other {
+				While(ref b_token, ref b_condition, ref b_body) => {
+					a_token.eq(b_token) && a_condition.eq(b_condition) && a_body.eq(b_body)
 				}
 				_ => false,
-			}
+			},
+			For(ref a_token, ref a_identifier, ref a_iterable, ref a_body) => match *other {
+				For(ref b_token, ref b_identifier, ref b_iterable, ref b_body) => {
+					a_token.eq(b_token)
+						&& a_identifier.eq(b_identifier)
+						&& a_iterable.eq(b_iterable)
+						&& a_body.eq(b_body)
+				}
+				_ => false,
+			},
+			Break(ref a_token) => match *other {
+				Break(ref b_token) => a_token.eq(b_token),
+				_ => false,
+			},
+			Continue(ref a_token) => match *other {
+				Continue(ref b_token) => a_token.eq(b_token),
+				_ => false,
+			},
+			Return(ref a_token, ref a_expression) => match *other {
+				Return(ref b_token, ref b_expression) => {
+					a_token.eq(b_token) && a_expression.eq(b_expression)
+				}
+				_ => false,
+			},
+			If(ref a_token, ref a_expression, ref a_body, ref a_else) => match *other {
+				If(ref b_token, ref b_expression, ref b_body, ref b_else) => {
+					a_token.eq(b_token)
+						&& a_expression.eq(b_expression)
+						&& a_body.eq(b_body)
+						&& a_else.eq(b_else)
+				}
+				_ => false,
+			},
+			Match(ref a_token, ref a_subject, ref a_arms, ref a_else, ref a_close) => match *other {
+				Match(ref b_token, ref b_subject, ref b_arms, ref b_else, ref b_close) => {
+					a_token.eq(b_token)
+						&& a_subject.eq(b_subject)
+						&& a_arms.eq(b_arms)
+						&& a_else.eq(b_else)
+						&& a_close.eq(b_close)
+				}
+				_ => false,
+			},
+			Record(ref a_token, ref a_identifier, ref a_fields, ref a_close) => match *other {
+				Record(ref b_token, ref b_identifier, ref b_fields, ref b_close) => {
+					a_token.eq(b_token)
+						&& a_identifier.eq(b_identifier)
+						&& a_fields.eq(b_fields)
+						&& a_close.eq(b_close)
+				}
+				_ => false,
+			},
+			Enum(ref a_token, ref a_identifier, ref a_variants, ref a_close) => match *other {
+				Enum(ref b_token, ref b_identifier, ref b_variants, ref b_close) => {
+					a_token.eq(b_token)
+						&& a_identifier.eq(b_identifier)
+						&& a_variants.eq(b_variants)
+						&& a_close.eq(b_close)
+				}
+				_ => false,
+			},
 		}
 	}
 }
@@ -1,4 +1,5 @@
 use super::literal::Literal;
+use super::span::Span;
 use super::token_type::TokenType;
 
 use std::fmt::{self, Debug};
@@ -11,6 +12,9 @@ pub struct Token {
 	pub line: u32,
 	pub column: u32,
 	pub length: u32,
+	pub offset: u32, // Byte offset in the source
+	pub end_line: u32,
+	pub end_column: u32, // Column after the last character, on the line the token ends at
 }
 
 impl Token {
@@ -20,6 +24,7 @@ impl Token {
 		line: u32,
 		column: u32,
 		length: u32,
+		offset: u32,
 	) -> Token {
 		Token {
 			r#type,
@@ -27,8 +32,28 @@ impl Token {
 			line,
 			column,
 			length,
+			offset,
+			end_line: line,
+			end_column: column + length,
 		}
 	}
+
+	// Strings and comments spanning multiple lines end on another line than they start
+	pub fn with_end(mut self, end_line: u32, end_column: u32) -> Token {
+		self.end_line = end_line;
+		self.end_column = end_column;
+		self
+	}
+
+	pub fn span(&self) -> Span {
+		Span::new(
+			self.offset,
+			self.line,
+			self.column,
+			self.end_line,
+			self.end_column,
+		)
+	}
 }
 
 impl fmt::Display for Token {
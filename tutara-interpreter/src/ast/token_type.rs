@@ -5,7 +5,9 @@ use std::fmt::{self, Debug};
 pub enum TokenType {
 	// Primitives
 	Integer, // 12
+	Float,   // 1.5
 	String,  // "foo"
+	Template, // 'foo $bar'
 	Boolean, // true / false
 	// Variables
 	Val, // Immutable
@@ -37,6 +39,9 @@ pub enum TokenType {
 	AssignDivision, 		// /=
 	AssignExponentiation,   // **=
 	AssignModulo,   		// %=
+	// Records
+	Type, // type
+	Enum, // enum
 	// Function
 	Function,  // fun
 	Return,    // return
@@ -57,12 +62,17 @@ pub enum TokenType {
 	CloseParenthesis,  	// )
 	OpenCurlyBracket,  	// {
 	CloseCurlyBracket, 	// }
+	OpenSquareBracket, 	// [
+	CloseSquareBracket, // ]
 	// Uncategorized
 	Specifier, 			// :
 	Dot,
 	Arrow,				// ->
+	Range,				// ..
+	RangeInclusive,		// ..=
 	// System
 	Comment,
+	DocComment,		// ///
 }
 
 impl TokenType {
@@ -96,6 +106,9 @@ impl TokenType {
 			"**" => Some(Exponentiation),
 			"%" => Some(Modulo),
 
+			"type" => Some(Type),
+			"enum" => Some(Enum),
+
 			"fun" => Some(Function),
 			"return" => Some(Return),
 			"," => Some(Separator),
@@ -115,6 +128,8 @@ impl TokenType {
 			")" => Some(CloseParenthesis),
 			"{" => Some(OpenCurlyBracket),
 			"}" => Some(CloseCurlyBracket),
+			"[" => Some(OpenSquareBracket),
+			"]" => Some(CloseSquareBracket),
 
 			"=" => Some(Assign),
 			":" => Some(Specifier),
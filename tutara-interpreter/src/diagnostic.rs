@@ -0,0 +1,236 @@
+use crate::{Error, ErrorCode, ErrorType, Severity, Span};
+use std::fmt;
+
+// Kinds of text in a rendered diagnostic, outputs that support colors use them to style the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+	Plain,
+	Error,     // Severity of errors and the underline of their location
+	Warning,   // Severity of warnings and the underline of their location
+	Message,   // Message of the error
+	Gutter,    // Line numbers and the margins next to the source
+	Secondary, // Underlines and messages of labels
+	Note,
+	Help,
+}
+
+// Error together with the source it was reported for, rendered as the source lines it points to
+pub struct Diagnostic<'a> {
+	error: &'a Error,
+	source: &'a str,
+}
+
+// Underlined part of a single line, multi-line spans are underlined up to the end of their first line
+struct Mark<'a> {
+	line: u32,
+	column: u32,
+	length: u32,
+	message: Option<&'a str>, // Labels have a message, the location of the error does not
+	primary: bool,
+}
+
+impl<'a> Diagnostic<'a> {
+	pub fn new(error: &'a Error, source: &'a str) -> Diagnostic<'a> {
+		Diagnostic { error, source }
+	}
+
+	pub fn code(&self) -> Option<&'static str> {
+		self.error.code.map(ErrorCode::as_str)
+	}
+
+	pub fn help(&self) -> Option<&'static str> {
+		use ErrorCode::*;
+
+		match self.error.code? {
+			IntegerOutOfRange => Some("Int literals have to fit in a signed 64-bit integer"),
+			InvalidEscape => Some(
+				"Supported escape sequences are \\n, \\r, \\t, \\b, \\f, \\v, \\0, \\\\, \\', \\$, \\xHH and \\u{HHHH}",
+			),
+			UnexpectedEndOfFile => Some("Check for an unclosed bracket or an incomplete expression"),
+			ExpectedEndOfBody => Some("Add a } to close the body"),
+			ValAssignment => Some("Declare the variable with var to be able to assign to it"),
+			NonExhaustiveMatch => Some("Add arms for the missing values or an else arm"),
+			DuplicateDeclaration => Some("Rename one of the declarations"),
+			OutsideOfLoop => Some("break and continue can only be used in loop, while and for bodies"),
+			MissingReturn => Some("Add a return statement at the end of the function"),
+			NoMatchArm => Some("Add an else arm to handle the other values"),
+			MissingScriptReturn => Some("Scripts end with a return statement of their result"),
+			_ => None,
+		}
+	}
+
+	// Parts of the rendered diagnostic with their style, together they form the plain text diagnostic
+	pub fn render(&self) -> Vec<(Style, String)> {
+		let mut parts = Vec::new();
+		let severity = match self.error.severity {
+			Severity::Error => Style::Error,
+			Severity::Warning => Style::Warning,
+		};
+
+		match self.code() {
+			Some(code) => parts.push((severity, format!("{}[{}]", self.error.severity, code))),
+			None => parts.push((severity, self.error.severity.to_string())),
+		}
+
+		parts.push((Style::Message, format!(": {}", self.error.message)));
+		parts.push((Style::Plain, "\n".to_string()));
+
+		let marks = self.marks();
+		let width = marks
+			.iter()
+			.map(|mark| mark.line.to_string().len())
+			.max()
+			.unwrap_or(0);
+		let margin = " ".repeat(width);
+
+		if let Some(location) = marks.iter().find(|mark| mark.primary) {
+			parts.push((Style::Gutter, format!("{}--> ", margin)));
+			parts.push((
+				Style::Plain,
+				format!("line {} on column {}\n", location.line, location.column),
+			));
+			parts.push((Style::Gutter, format!("{} |\n", margin)));
+		}
+
+		let mut previous: Option<u32> = None;
+
+		for mark in marks.iter() {
+			let line = self.line(mark.line);
+
+			if previous != Some(mark.line) {
+				if previous.is_some_and(|previous| mark.line > previous + 1) {
+					parts.push((Style::Gutter, "...\n".to_string()));
+				}
+
+				parts.push((Style::Gutter, format!("{:>1$} | ", mark.line, width)));
+				parts.push((Style::Plain, format!("{}\n", line)));
+				previous = Some(mark.line);
+			}
+
+			// Tabs are kept so the underline lines up with the source
+			let indent: String = line
+				.chars()
+				.take(mark.column as usize)
+				.map(|character| if character == '\t' { '\t' } else { ' ' })
+				.collect();
+			let available = line.chars().count().saturating_sub(mark.column as usize);
+			let length = (mark.length as usize).min(available).max(1);
+
+			let (style, underline) = if mark.primary {
+				(severity, "^".repeat(length))
+			} else {
+				(Style::Secondary, "-".repeat(length))
+			};
+
+			parts.push((Style::Gutter, format!("{} | ", margin)));
+			parts.push((Style::Plain, indent));
+
+			match mark.message {
+				Some(message) => parts.push((style, format!("{} {}\n", underline, message))),
+				None => parts.push((style, format!("{}\n", underline))),
+			}
+		}
+
+		let notes = self
+			.error
+			.labels
+			.iter()
+			.filter(|label| label.span.is_none())
+			.map(|label| ("note", Style::Note, label.message.as_str()));
+		let help = self.help().map(|help| ("help", Style::Help, help));
+		let mut notes = notes.chain(help).peekable();
+
+		if !marks.is_empty() && notes.peek().is_some() {
+			parts.push((Style::Gutter, format!("{} |\n", margin)));
+		}
+
+		for (kind, style, text) in notes {
+			parts.push((Style::Gutter, format!("{} = ", margin)));
+			parts.push((style, kind.to_string()));
+			parts.push((Style::Plain, format!(": {}\n", text)));
+		}
+
+		parts
+	}
+
+	// Location of the error followed by its labels, ordered by their position in the source
+	fn marks(&self) -> Vec<Mark<'_>> {
+		use ErrorType::*;
+
+		let location = match &self.error.r#type {
+			Lexical(line, column, length) => Some((*line, *column, *length)),
+			Parser(token) | Runtime(Some(token)) => Some((token.line, token.column, token.length)),
+			Semantic(span) | Compiler(Some(span)) => Some(self.locate(span)),
+			Eof => Some(self.end()),
+			Compiler(None) | Runtime(None) => None,
+		};
+
+		let mut marks: Vec<Mark> = location
+			.into_iter()
+			.map(|(line, column, length)| Mark {
+				line,
+				column,
+				length,
+				message: None,
+				primary: true,
+			})
+			.chain(self.error.labels.iter().filter_map(|label| {
+				let (line, column, length) = self.locate(label.span.as_ref()?);
+
+				Some(Mark {
+					line,
+					column,
+					length,
+					message: Some(label.message.as_str()),
+					primary: false,
+				})
+			}))
+			.collect();
+
+		marks.sort_by_key(|mark| (mark.line, !mark.primary, mark.column));
+		marks
+	}
+
+	fn locate(&self, span: &Span) -> (u32, u32, u32) {
+		let length = if span.end_line == span.line {
+			span.end_column.saturating_sub(span.column)
+		} else {
+			let line = self.line(span.line).chars().count() as u32;
+			line.saturating_sub(span.column)
+		};
+
+		(span.line, span.column, length)
+	}
+
+	// The end of the file is just after the last character of the source, trailing new lines are skipped
+	fn end(&self) -> (u32, u32, u32) {
+		let source = self.source.trim_end();
+		let line = source.split('\n').count() as u32;
+		let column = source
+			.rsplit('\n')
+			.next()
+			.unwrap_or_default()
+			.chars()
+			.count() as u32;
+
+		(line, column, 1)
+	}
+
+	fn line(&self, line: u32) -> &str {
+		self.source
+			.split('\n')
+			.nth(line.saturating_sub(1) as usize)
+			.unwrap_or_default()
+			.trim_end_matches('\r')
+	}
+}
+
+impl fmt::Display for Diagnostic<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (_, text) in self.render() {
+			write!(f, "{}", text)?;
+		}
+
+		Ok(())
+	}
+}
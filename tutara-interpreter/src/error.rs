@@ -5,40 +5,196 @@ use std::fmt::{self, Debug};
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ErrorType {
 	Lexical(u32, u32, u32), // Line, column, length
-	Parser(Token),
-	Compiler,
+	Parser(Box<Token>), // Boxed to keep results small
+	Semantic(Span),
+	Compiler(Option<Span>), // Errors of the module as a whole have no span
+	Runtime(Option<Box<Token>>),
 	Eof,
 }
 
+// Errors stop a script from being evaluated, warnings are only reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+	Error,
+	Warning,
+}
+
+// Stable codes of errors, the first code of each stage is used for its errors without a specific code.
+// Codes are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorCode {
+	Lexical,                    // E0100
+	UnexpectedCharacter,        // E0101
+	UnterminatedString,         // E0102
+	UnexpectedNewLine,          // E0103
+	UnterminatedComment,        // E0104
+	IntegerOutOfRange,          // E0105
+	FloatOutOfRange,            // E0106
+	InvalidDigits,              // E0107
+	InvalidEscape,              // E0108
+	InvalidInterpolation,       // E0109
+	InvalidAssignmentOperation, // E0110
+	Syntax,                     // E0200
+	UnexpectedEndOfFile,        // E0201
+	UnexpectedToken,            // E0202
+	ExpectedEndOfBody,          // E0203
+	ExpectedType,               // E0204
+	ExpectedIdentifier,         // E0205
+	Semantic,                   // E0300
+	UnknownVariable,            // E0301
+	UnknownFunction,            // E0302
+	UnknownType,                // E0303
+	UnknownMember,              // E0304, fields, properties and variants
+	ValAssignment,              // E0305
+	MismatchedTypes,            // E0306
+	ArgumentCount,              // E0307
+	UnsupportedOperation,       // E0308
+	UnsupportedType,            // E0309
+	MissingReturn,              // E0310
+	OutsideOfLoop,              // E0311
+	NonExhaustiveMatch,         // E0312
+	DuplicateDeclaration,       // E0313, types, functions, fields and variants
+	Compiler,                   // E0400
+	Runtime,                    // E0500
+	DivisionByZero,             // E0501
+	IntegerOverflow,            // E0502
+	OutOfBounds,                // E0503
+	MissingKey,                 // E0504
+	NoMatchArm,                 // E0505
+	MissingScriptReturn,        // E0506
+	EmptyArray,                 // E0507
+	NegativeExponent,           // E0508
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Error {
 	pub r#type: ErrorType,
+	pub severity: Severity,
+	#[serde(default)]
+	pub code: Option<ErrorCode>, // Warnings and lints have no code
+	pub message: String,
+	#[serde(default)]
+	pub labels: Vec<Label>, // Secondary locations and notes that explain the error
+}
+
+// Labels without a span are notes about the error as a whole
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Label {
+	pub span: Option<Span>,
 	pub message: String,
 }
 
 impl Error {
 	pub fn new(r#type: ErrorType, message: String) -> Error {
-		Error { r#type, message }
+		let code = match r#type {
+			ErrorType::Lexical(..) => ErrorCode::Lexical,
+			ErrorType::Parser(_) | ErrorType::Eof => ErrorCode::Syntax,
+			ErrorType::Semantic(_) => ErrorCode::Semantic,
+			ErrorType::Compiler(_) => ErrorCode::Compiler,
+			ErrorType::Runtime(_) => ErrorCode::Runtime,
+		};
+
+		Error {
+			r#type,
+			severity: Severity::Error,
+			code: Some(code),
+			message,
+			labels: Vec::new(),
+		}
 	}
 
 	pub fn new_lexical_error(message: String, line: u32, column: u32, length: u32) -> Error {
 		Error {
 			r#type: ErrorType::Lexical(line, column, length),
+			severity: Severity::Error,
+			code: Some(ErrorCode::Lexical),
 			message,
+			labels: Vec::new(),
 		}
 	}
 
 	pub fn new_parser_error(message: String, token: Token) -> Error {
 		Error {
-			r#type: ErrorType::Parser(token),
+			r#type: ErrorType::Parser(Box::new(token)),
+			severity: Severity::Error,
+			code: Some(ErrorCode::Syntax),
+			message,
+			labels: Vec::new(),
+		}
+	}
+
+	pub fn new_semantic_error(message: String, span: Span) -> Error {
+		Error {
+			r#type: ErrorType::Semantic(span),
+			severity: Severity::Error,
+			code: Some(ErrorCode::Semantic),
+			message,
+			labels: Vec::new(),
+		}
+	}
+
+	pub fn new_semantic_warning(message: String, span: Span) -> Error {
+		Error {
+			r#type: ErrorType::Semantic(span),
+			severity: Severity::Warning,
+			code: None,
 			message,
+			labels: Vec::new(),
 		}
 	}
 
 	pub fn new_compiler_error(message: String) -> Error {
 		Error {
-			r#type: ErrorType::Compiler,
+			r#type: ErrorType::Compiler(None),
+			severity: Severity::Error,
+			code: Some(ErrorCode::Compiler),
+			message,
+			labels: Vec::new(),
+		}
+	}
+
+	// Compiler errors are created without a span, the span of the innermost expression or statement
+	// that was being compiled is added when the error is returned from it
+	pub fn with_span(self, span: Span) -> Error {
+		match self.r#type {
+			ErrorType::Compiler(None) => Error {
+				r#type: ErrorType::Compiler(Some(span)),
+				..self
+			},
+			_ => self,
+		}
+	}
+
+	pub fn with_code(self, code: ErrorCode) -> Error {
+		Error {
+			code: Some(code),
+			..self
+		}
+	}
+
+	pub fn with_label(mut self, span: Span, message: String) -> Error {
+		self.labels.push(Label {
+			span: Some(span),
+			message,
+		});
+		self
+	}
+
+	pub fn with_note(mut self, message: String) -> Error {
+		self.labels.push(Label {
+			span: None,
 			message,
+		});
+		self
+	}
+
+	pub fn new_runtime_error(message: String, token: Option<Token>) -> Error {
+		Error {
+			r#type: ErrorType::Runtime(token.map(Box::new)),
+			severity: Severity::Error,
+			code: Some(ErrorCode::Runtime),
+			message,
+			labels: Vec::new(),
 		}
 	}
 }
@@ -57,8 +213,88 @@ impl fmt::Display for Error {
 				"Syntax error on {}: at line: {} on column: {}, message: {}",
 				token.r#type, token.line, token.column, self.message
 			),
-			Compiler => write!(f, "Compiler error: {}", self.message),
+			Semantic(span) => write!(
+				f,
+				"Semantic {} at line: {} on column: {}, message: {}",
+				self.severity, span.line, span.column, self.message
+			),
+			Compiler(Some(span)) => write!(
+				f,
+				"Compiler error at line: {} on column: {}, message: {}",
+				span.line, span.column, self.message
+			),
+			Compiler(None) => write!(f, "Compiler error: {}", self.message),
+			Runtime(Some(token)) => write!(
+				f,
+				"Runtime error on {}: at line: {} on column: {}, message: {}",
+				token.r#type, token.line, token.column, self.message
+			),
+			Runtime(None) => write!(f, "Runtime error: {}", self.message),
 			Eof => write!(f, "{}", self.message),
 		}
 	}
 }
+
+impl ErrorCode {
+	pub fn as_str(self) -> &'static str {
+		use ErrorCode::*;
+		match self {
+			Lexical => "E0100",
+			UnexpectedCharacter => "E0101",
+			UnterminatedString => "E0102",
+			UnexpectedNewLine => "E0103",
+			UnterminatedComment => "E0104",
+			IntegerOutOfRange => "E0105",
+			FloatOutOfRange => "E0106",
+			InvalidDigits => "E0107",
+			InvalidEscape => "E0108",
+			InvalidInterpolation => "E0109",
+			InvalidAssignmentOperation => "E0110",
+			Syntax => "E0200",
+			UnexpectedEndOfFile => "E0201",
+			UnexpectedToken => "E0202",
+			ExpectedEndOfBody => "E0203",
+			ExpectedType => "E0204",
+			ExpectedIdentifier => "E0205",
+			Semantic => "E0300",
+			UnknownVariable => "E0301",
+			UnknownFunction => "E0302",
+			UnknownType => "E0303",
+			UnknownMember => "E0304",
+			ValAssignment => "E0305",
+			MismatchedTypes => "E0306",
+			ArgumentCount => "E0307",
+			UnsupportedOperation => "E0308",
+			UnsupportedType => "E0309",
+			MissingReturn => "E0310",
+			OutsideOfLoop => "E0311",
+			NonExhaustiveMatch => "E0312",
+			DuplicateDeclaration => "E0313",
+			Compiler => "E0400",
+			Runtime => "E0500",
+			DivisionByZero => "E0501",
+			IntegerOverflow => "E0502",
+			OutOfBounds => "E0503",
+			MissingKey => "E0504",
+			NoMatchArm => "E0505",
+			MissingScriptReturn => "E0506",
+			EmptyArray => "E0507",
+			NegativeExponent => "E0508",
+		}
+	}
+}
+
+impl fmt::Display for ErrorCode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Severity::Error => write!(f, "error"),
+			Severity::Warning => write!(f, "warning"),
+		}
+	}
+}
@@ -0,0 +1,227 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, ErrorCode, Expression, Result, Token, TokenType, Type, Value};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+impl Interpreter {
+	pub fn evaluate_array(
+		&mut self,
+		open_square_bracket: &Token,
+		elements: &[Expression],
+	) -> Result<Value> {
+		let mut values: Vec<Value> = Vec::new();
+
+		for element in elements {
+			let value = self.evaluate_expression(element)?;
+
+			if let Some(first) = values.first() {
+				if first.r#type() != value.r#type() {
+					return Err(Error::new_runtime_error(
+						format!(
+							"Array elements should be of type {} but got {}",
+							first.r#type(),
+							value.r#type()
+						),
+						Some(open_square_bracket.clone()),
+					));
+				}
+			} else if let Value::Void = value {
+				return Err(Error::new_runtime_error(
+					"Array elements can not be Void".to_string(),
+					Some(open_square_bracket.clone()),
+				));
+			}
+
+			values.push(value);
+		}
+
+		Ok(Value::Array(Rc::new(RefCell::new(values))))
+	}
+
+	pub fn evaluate_index(
+		&mut self,
+		indexed: &Expression,
+		open_square_bracket: &Token,
+		index: &Expression,
+	) -> Result<Value> {
+		let indexed = self.evaluate_expression(indexed)?;
+		let index = self.evaluate_expression(index)?;
+
+		match indexed {
+			Value::Array(array) => {
+				let array = array.borrow();
+				let index = self.get_array_index(&array, index, open_square_bracket)?;
+
+				Ok(array[index].clone())
+			}
+			Value::Map(map) => self.get_map_value(&map.borrow(), &index, open_square_bracket),
+			_ => Err(Error::new_runtime_error(
+				format!("Unable to index {}", indexed.r#type()),
+				Some(open_square_bracket.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_set(
+		&mut self,
+		target: &Expression,
+		operator: &Token,
+		expression: &Expression,
+	) -> Result<Value> {
+		if operator.r#type != TokenType::Assign {
+			return Err(Error::new_runtime_error(
+				"Unsupported assignment operator".to_string(),
+				Some(operator.clone()),
+			));
+		}
+
+		match target {
+			Expression::Index(indexed, open_square_bracket, index, _) => {
+				let indexed = self.evaluate_expression(indexed)?;
+				let index = self.evaluate_expression(index)?;
+				let value = self.evaluate_expression(expression)?;
+
+				match indexed {
+					Value::Array(array) => {
+						let mut array = array.borrow_mut();
+						let index = self.get_array_index(&array, index, open_square_bracket)?;
+
+						if array[index].r#type() != value.r#type() {
+							return Err(Error::new_runtime_error(
+								format!(
+									"Can not assign {} to element of type {}",
+									value.r#type(),
+									array[index].r#type()
+								),
+								Some(operator.clone()),
+							));
+						}
+
+						array[index] = value;
+						Ok(Value::Void)
+					}
+					Value::Map(map) => {
+						self.insert_map_value(&map, index, value, operator)?;
+						Ok(Value::Void)
+					}
+					_ => Err(Error::new_runtime_error(
+						format!("Unable to index {}", indexed.r#type()),
+						Some(open_square_bracket.clone()),
+					)),
+				}
+			}
+			Expression::Get(object, property) => {
+				let value = self.evaluate_expression(expression)?;
+
+				self.set_record_field(object, property, operator, value)?;
+				Ok(Value::Void)
+			}
+			_ => Err(Error::new_runtime_error(
+				"Unsupported assignment target".to_string(),
+				Some(operator.clone()),
+			)),
+		}
+	}
+
+	// Elements of arrays and maps are shared, so they are updated in place
+	pub(crate) fn with_element_mut<T>(
+		&self,
+		indexed: Value,
+		index: Value,
+		open_square_bracket: &Token,
+		update: impl FnOnce(&mut Value) -> Result<T>,
+	) -> Result<T> {
+		match indexed {
+			Value::Array(array) => {
+				let mut array = array.borrow_mut();
+				let index = self.get_array_index(&array, index, open_square_bracket)?;
+
+				update(&mut array[index])
+			}
+			Value::Map(map) => match map.borrow_mut().iter_mut().find(|(key, _)| *key == index) {
+				Some((_, value)) => update(value),
+				None => Err(Error::new_runtime_error(
+					format!("Key {} not found in map", index),
+					Some(open_square_bracket.clone()),
+				)
+				.with_code(ErrorCode::MissingKey)),
+			},
+			_ => Err(Error::new_runtime_error(
+				format!("Unable to index {}", indexed.r#type()),
+				Some(open_square_bracket.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_array_push(
+		&mut self,
+		array: Rc<RefCell<Vec<Value>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let value = self.evaluate_arguments(method, arguments, 1)?.remove(0);
+		let mut array = array.borrow_mut();
+		let element_type = match array.first() {
+			Some(element) => element.r#type(),
+			None => value.r#type(),
+		};
+
+		if element_type != value.r#type() || element_type == Type::Void {
+			return Err(Error::new_runtime_error(
+				format!("Can not push {} to Array<{}>", value.r#type(), element_type),
+				Some(method.clone()),
+			));
+		}
+
+		array.push(value);
+		Ok(Value::Void)
+	}
+
+	pub fn evaluate_array_pop(
+		&mut self,
+		array: Rc<RefCell<Vec<Value>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		self.evaluate_arguments(method, arguments, 0)?;
+
+		match array.borrow_mut().pop() {
+			Some(value) => Ok(value),
+			None => Err(Error::new_runtime_error(
+				"Unable to pop from an empty array".to_string(),
+				Some(method.clone()),
+			)
+			.with_code(ErrorCode::EmptyArray)),
+		}
+	}
+
+	fn get_array_index(
+		&self,
+		array: &[Value],
+		index: Value,
+		open_square_bracket: &Token,
+	) -> Result<usize> {
+		match index {
+			Value::Integer(index) if index >= 0 && (index as usize) < array.len() => {
+				Ok(index as usize)
+			}
+			Value::Integer(index) => Err(Error::new_runtime_error(
+				format!(
+					"Index {} out of bounds for array of length {}",
+					index,
+					array.len()
+				),
+				Some(open_square_bracket.clone()),
+			)
+			.with_code(ErrorCode::OutOfBounds)),
+			_ => Err(Error::new_runtime_error(
+				format!(
+					"Array index should be of type Int but got {}",
+					index.r#type()
+				),
+				Some(open_square_bracket.clone()),
+			)),
+		}
+	}
+}
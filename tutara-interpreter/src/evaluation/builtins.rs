@@ -0,0 +1,96 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Result, Token, Value};
+
+impl Interpreter {
+	pub fn evaluate_builtin(
+		&mut self,
+		name: &str,
+		open_parenthesis: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		match name {
+			"print" => {
+				let mut values: Vec<String> = Vec::new();
+
+				for argument in arguments {
+					values.push(self.evaluate_expression(argument)?.to_string());
+				}
+
+				self.output.push(values.join(" "));
+				Ok(Value::Void)
+			}
+			_ => Err(Error::new_runtime_error(
+				format!("Unknown function {}", name),
+				Some(open_parenthesis.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_method(
+		&mut self,
+		object: Value,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let name = self.get_name(method)?;
+
+		match (object, name.as_str()) {
+			(Value::Integer(value), "toFloat") => self.evaluate_to_float(value, method, arguments),
+			(Value::Float(value), "toInt") => self.evaluate_to_int(value, method, arguments),
+			(Value::Range(range), "step") => self.evaluate_range_step(range, method, arguments),
+			(Value::Array(array), "push") => self.evaluate_array_push(array, method, arguments),
+			(Value::Array(array), "pop") => self.evaluate_array_pop(array, method, arguments),
+			(Value::Map(map), "remove") => self.evaluate_map_remove(map, method, arguments),
+			(Value::Map(map), "contains") => self.evaluate_map_contains(map, method, arguments),
+			(Value::Map(map), "keys") => self.evaluate_map_keys(map, method, arguments),
+			(Value::Map(map), "values") => self.evaluate_map_values(map, method, arguments),
+			(object, _) => Err(Error::new_runtime_error(
+				format!("Unknown function {} on {}", name, object.r#type()),
+				Some(method.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_get(&mut self, object: &Expression, property: &Token) -> Result<Value> {
+		if let Some(name) = self.get_enum_name(object) {
+			return self.evaluate_variant(name, property, &[]);
+		}
+
+		let object = self.evaluate_expression(object)?;
+		let name = self.get_name(property)?;
+
+		match (object, name.as_str()) {
+			(Value::Array(array), "length") => Ok(Value::Integer(array.borrow().len() as i64)),
+			(Value::Map(map), "length") => Ok(Value::Integer(map.borrow().len() as i64)),
+			(Value::Record(record), _) => self.get_record_field(&record, property),
+			(object, _) => Err(Error::new_runtime_error(
+				format!("Unknown property {} on {}", name, object.r#type()),
+				Some(property.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_arguments(
+		&mut self,
+		method: &Token,
+		arguments: &[Expression],
+		count: usize,
+	) -> Result<Vec<Value>> {
+		if arguments.len() != count {
+			return Err(Error::new_runtime_error(
+				format!(
+					"Function {} expects {} arguments but got {}",
+					self.get_name(method)?,
+					count,
+					arguments.len()
+				),
+				Some(method.clone()),
+			));
+		}
+
+		arguments
+			.iter()
+			.map(|argument| self.evaluate_expression(argument))
+			.collect()
+	}
+}
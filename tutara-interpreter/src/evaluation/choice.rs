@@ -0,0 +1,226 @@
+use crate::interpreter::Interpreter;
+use crate::{
+	Error, ErrorCode, Expression, MatchArm, Operation, Result, Scope, ScopeContext, Statement,
+	Token, Value,
+};
+
+impl Interpreter {
+	pub fn evaluate_if(
+		&mut self,
+		condition: &Expression,
+		true_branch: &Statement,
+		false_branch: &Option<Box<Statement>>,
+	) -> Result<Operation> {
+		match self.evaluate_expression(condition)? {
+			Value::Boolean(true) => self.evaluate_scoped(ScopeContext::If, true_branch),
+			Value::Boolean(false) => match false_branch {
+				Some(false_branch) => self.evaluate_scoped(ScopeContext::If, false_branch),
+				None => Ok(Operation::NoOp),
+			},
+			_ => Err(Error::new_runtime_error(
+				"Unsupported type in condition".to_string(),
+				None,
+			)),
+		}
+	}
+
+	pub fn evaluate_match(
+		&mut self,
+		token: &Token,
+		subject: &Expression,
+		arms: &[MatchArm<Statement>],
+		default: &Option<Box<Statement>>,
+	) -> Result<Operation> {
+		let subject = self.evaluate_expression(subject)?;
+
+		match self.find_match_arm(token, &subject, arms)? {
+			Some((body, bindings)) => {
+				self.scope.push(bindings);
+				let operation = self.evaluate_statement(body);
+				self.scope.pop();
+
+				operation
+			}
+			None => match default {
+				Some(default) => self.evaluate_scoped(ScopeContext::If, default),
+				None => Err(self.create_unmatched_error(token, &subject)),
+			},
+		}
+	}
+
+	pub fn evaluate_match_expression(
+		&mut self,
+		token: &Token,
+		subject: &Expression,
+		arms: &[MatchArm<Expression>],
+		default: &Option<Box<Expression>>,
+	) -> Result<Value> {
+		let subject = self.evaluate_expression(subject)?;
+
+		match self.find_match_arm(token, &subject, arms)? {
+			Some((body, bindings)) => {
+				self.scope.push(bindings);
+				let value = self.evaluate_expression(body);
+				self.scope.pop();
+
+				value
+			}
+			None => match default {
+				Some(default) => self.evaluate_expression(default),
+				None => Err(self.create_unmatched_error(token, &subject)),
+			},
+		}
+	}
+
+	// The scope of a matching arm holds the values bound by its variant pattern
+	fn find_match_arm<'a, T>(
+		&mut self,
+		token: &Token,
+		subject: &Value,
+		arms: &'a [MatchArm<T>],
+	) -> Result<Option<(&'a T, Scope)>> {
+		for arm in arms {
+			for pattern in arm.patterns.iter() {
+				let bindings = match self.matches_pattern(token, subject, pattern)? {
+					Some(bindings) => bindings,
+					None => continue,
+				};
+
+				let guard = match &arm.guard {
+					Some(guard) => {
+						self.scope.push(bindings);
+						let guard = self.evaluate_expression(guard);
+						let bindings = self.scope.pop().unwrap();
+
+						(guard?, bindings)
+					}
+					None => (Value::Boolean(true), bindings),
+				};
+
+				match guard {
+					(Value::Boolean(true), bindings) => return Ok(Some((&arm.body, bindings))),
+					(Value::Boolean(false), _) => break,
+					_ => {
+						return Err(Error::new_runtime_error(
+							"Unsupported type in guard".to_string(),
+							Some(token.clone()),
+						))
+					}
+				}
+			}
+		}
+
+		Ok(None)
+	}
+
+	fn matches_pattern(
+		&mut self,
+		token: &Token,
+		subject: &Value,
+		pattern: &Expression,
+	) -> Result<Option<Scope>> {
+		if let Some((name, variant, bindings)) = self.get_variant_pattern(pattern) {
+			return self.matches_variant(token, subject, name, variant, bindings);
+		}
+
+		let matches = match (subject, self.evaluate_expression(pattern)?) {
+			(Value::Integer(value), Value::Range(range)) => range.contains(*value),
+			(subject, pattern) if subject.r#type() == pattern.r#type() => subject == &pattern,
+			(subject, pattern) => {
+				return Err(Error::new_runtime_error(
+					format!(
+						"Unable to match {} against {}",
+						subject.r#type(),
+						pattern.r#type()
+					),
+					Some(token.clone()),
+				))
+			}
+		};
+
+		Ok(match matches {
+			true => Some(Scope::new(ScopeContext::If)),
+			false => None,
+		})
+	}
+
+	// Variant patterns look like Status.NotFound or Status.Failed(message)
+	fn get_variant_pattern<'a>(
+		&self,
+		pattern: &'a Expression,
+	) -> Option<(String, &'a Token, &'a [Expression])> {
+		match pattern {
+			Expression::Get(object, variant) => Some((self.get_enum_name(object)?, variant, &[])),
+			Expression::Call(function, _, bindings, _) => match &**function {
+				Expression::Get(object, variant) => {
+					Some((self.get_enum_name(object)?, variant, bindings))
+				}
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	fn matches_variant(
+		&self,
+		token: &Token,
+		subject: &Value,
+		name: String,
+		variant: &Token,
+		bindings: &[Expression],
+	) -> Result<Option<Scope>> {
+		let subject = match subject {
+			Value::Enum(subject) if subject.r#enum == name => subject,
+			subject => {
+				return Err(Error::new_runtime_error(
+					format!("Unable to match {} against {}", subject.r#type(), name),
+					Some(token.clone()),
+				))
+			}
+		};
+
+		// Checks the variant exists even when the subject is another variant
+		self.get_variant_types(&name, variant)?;
+
+		if subject.name != self.get_name(variant)? {
+			return Ok(None);
+		}
+
+		let mut scope = Scope::new(ScopeContext::If);
+
+		for (binding, value) in bindings.iter().zip(subject.values.iter()) {
+			match binding {
+				Expression::Identifier(identifier) => {
+					scope
+						.variables
+						.insert(self.get_name(identifier)?, value.clone());
+				}
+				_ => {
+					return Err(Error::new_runtime_error(
+						"Expected identifier in variant pattern".to_string(),
+						Some(variant.clone()),
+					))
+				}
+			}
+		}
+
+		Ok(Some(scope))
+	}
+
+	fn create_unmatched_error(&self, token: &Token, subject: &Value) -> Error {
+		Error::new_runtime_error(format!("No match arm for {}", subject), Some(token.clone()))
+			.with_code(ErrorCode::NoMatchArm)
+	}
+
+	pub(crate) fn evaluate_scoped(
+		&mut self,
+		scope_context: ScopeContext,
+		statement: &Statement,
+	) -> Result<Operation> {
+		self.scope.push(Scope::new(scope_context));
+		let operation = self.evaluate_statement(statement);
+		self.scope.pop();
+
+		operation
+	}
+}
@@ -0,0 +1,25 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Literal, Operation, Result, Statement, Token};
+
+impl Interpreter {
+	pub fn evaluate_body(&mut self, statements: &[Statement]) -> Result<Operation> {
+		for statement in statements {
+			match self.evaluate_statement(statement)? {
+				Operation::NoOp => {}
+				operation => return Ok(operation),
+			}
+		}
+
+		Ok(Operation::NoOp)
+	}
+
+	pub(crate) fn get_name(&self, identifier: &Token) -> Result<String> {
+		match &identifier.literal {
+			Some(Literal::String(name)) => Ok(name.clone()),
+			_ => Err(Error::new_runtime_error(
+				"Unsupported identifier".to_string(),
+				Some(identifier.clone()),
+			)),
+		}
+	}
+}
@@ -0,0 +1,135 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Literal, Operation, Result, Token, Type, Value, Variant};
+
+use std::rc::Rc;
+
+pub struct EnumType {
+	pub(crate) variants: Vec<(String, Vec<Type>)>,
+}
+
+impl Interpreter {
+	pub fn evaluate_enum(
+		&mut self,
+		identifier: &Token,
+		variants: &[(Token, Vec<Token>)],
+	) -> Result<Operation> {
+		let name = self.get_name(identifier)?;
+
+		if self.is_type_declared(identifier)? {
+			return Err(Error::new_runtime_error(
+				format!("Type {} is already declared", name),
+				Some(identifier.clone()),
+			));
+		}
+
+		let mut enum_variants: Vec<(String, Vec<Type>)> = Vec::new();
+
+		for (variant, types) in variants.iter() {
+			let variant_name = self.get_name(variant)?;
+			let types = types
+				.iter()
+				.map(|r#type| self.resolve_type(r#type))
+				.collect::<Result<Vec<Type>>>()?;
+
+			if enum_variants
+				.iter()
+				.any(|(existing, _)| *existing == variant_name)
+			{
+				return Err(Error::new_runtime_error(
+					format!("Variant {} is already declared", variant_name),
+					Some(variant.clone()),
+				));
+			}
+
+			enum_variants.push((variant_name, types));
+		}
+
+		self.enums.insert(
+			name,
+			Rc::new(EnumType {
+				variants: enum_variants,
+			}),
+		);
+
+		Ok(Operation::NoOp)
+	}
+
+	// Status.Ok(1) and Status.NotFound construct variants unless Status is a variable
+	pub fn get_enum_name(&self, object: &Expression) -> Option<String> {
+		match object {
+			Expression::Identifier(Token {
+				literal: Some(Literal::String(name)),
+				..
+			}) if self.enums.contains_key(name) && self.find_variable_scope(name).is_none() => {
+				Some(name.clone())
+			}
+			_ => None,
+		}
+	}
+
+	pub fn evaluate_variant(
+		&mut self,
+		name: String,
+		variant: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let variant_name = self.get_name(variant)?;
+		let types = self.get_variant_types(&name, variant)?;
+
+		if types.len() != arguments.len() {
+			return Err(Error::new_runtime_error(
+				format!(
+					"Variant {}.{} expects {} values but got {}",
+					name,
+					variant_name,
+					types.len(),
+					arguments.len()
+				),
+				Some(variant.clone()),
+			));
+		}
+
+		let mut values: Vec<Value> = Vec::new();
+
+		for (r#type, argument) in types.iter().zip(arguments) {
+			let value = self.evaluate_expression(argument)?;
+
+			if !r#type.accepts(&value.r#type()) {
+				return Err(Error::new_runtime_error(
+					format!(
+						"Expected {} for variant {}.{} but got {}",
+						r#type,
+						name,
+						variant_name,
+						value.r#type()
+					),
+					Some(variant.clone()),
+				));
+			}
+
+			values.push(value);
+		}
+
+		Ok(Value::Enum(Variant {
+			r#enum: name,
+			name: variant_name,
+			values,
+		}))
+	}
+
+	pub(crate) fn get_variant_types(&self, name: &str, variant: &Token) -> Result<Vec<Type>> {
+		let variant_name = self.get_name(variant)?;
+
+		match self.enums[name]
+			.variants
+			.iter()
+			.find(|(declared, _)| *declared == variant_name)
+		{
+			Some((_, types)) => Ok(types.clone()),
+			None => Err(Error::new_runtime_error(
+				format!("Unknown variant {} of {}", variant_name, name),
+				Some(variant.clone()),
+			)),
+		}
+	}
+}
@@ -0,0 +1,72 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, ErrorCode, Expression, Literal, Result, TokenType, Value};
+
+impl Interpreter {
+	pub fn evaluate_expression(&mut self, expression: &Expression) -> Result<Value> {
+		use Expression::*;
+
+		match expression {
+			Literal(token) => match &token.literal {
+				Some(self::Literal::Integer(integer)) => Ok(Value::Integer(*integer)),
+				Some(self::Literal::Float(float)) => Ok(Value::Float(*float)),
+				Some(self::Literal::Boolean(bool)) => Ok(Value::Boolean(*bool)),
+				Some(self::Literal::String(string)) => Ok(Value::String(string.clone())),
+				Some(self::Literal::Template(_)) | None => Err(Error::new_runtime_error(
+					"Unsupported literal".to_string(),
+					Some(token.clone()),
+				)),
+			},
+			Identifier(identifier) => self.get_variable(identifier),
+			Assignment(identifier, operator, expression) => {
+				self.set_variable(identifier, operator, expression)?;
+				Ok(Value::Void)
+			}
+			Unary(operator, expression) => {
+				let value = self.evaluate_expression(expression)?;
+				match (&operator.r#type, value) {
+					(TokenType::Not, Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+					(TokenType::Minus, Value::Integer(value)) => match value.checked_neg() {
+						Some(value) => Ok(Value::Integer(value)),
+						None => Err(Error::new_runtime_error(
+							"Integer overflow".to_string(),
+							Some(operator.clone()),
+						)
+						.with_code(ErrorCode::IntegerOverflow)),
+					},
+					(TokenType::Minus, Value::Float(value)) => Ok(Value::Float(-value)),
+					(TokenType::Plus, Value::Integer(value)) => Ok(Value::Integer(value)),
+					(TokenType::Plus, Value::Float(value)) => Ok(Value::Float(value)),
+					(_, value) => Err(Error::new_runtime_error(
+						format!(
+							"Unsupported operation {} on {}",
+							operator.r#type,
+							value.r#type()
+						),
+						Some(operator.clone()),
+					)),
+				}
+			}
+			Binary(left, operator, right) => self.evaluate_operator(left, right, operator),
+			Grouping(_, expression, _) => self.evaluate_expression(expression),
+			Call(function, open_parenthesis, arguments, _) => {
+				self.evaluate_call(function, open_parenthesis, arguments)
+			}
+			Range(start, operator, end) => self.evaluate_range(start, operator, end),
+			Match(token, subject, arms, default, _) => {
+				self.evaluate_match_expression(token, subject, arms, default)
+			}
+			Get(object, property) => self.evaluate_get(object, property),
+			Array(open_square_bracket, elements, _) => {
+				self.evaluate_array(open_square_bracket, elements)
+			}
+			Map(open_curly_bracket, entries, _) => self.evaluate_map(open_curly_bracket, entries),
+			Index(indexed, open_square_bracket, index, _) => {
+				self.evaluate_index(indexed, open_square_bracket, index)
+			}
+			Record(identifier, fields, _) => self.evaluate_record_construction(identifier, fields),
+			Set(target, operator, expression) => self.evaluate_set(target, operator, expression),
+			Lambda(_, parameters, _, body) => self.evaluate_lambda(parameters, body),
+			Template(token, parts) => self.evaluate_template(token, parts),
+		}
+	}
+}
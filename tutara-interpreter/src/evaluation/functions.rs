@@ -0,0 +1,282 @@
+use crate::interpreter::Interpreter;
+use crate::{
+	Analyzer, Error, Expression, Operation, Result, Scope, ScopeContext, Statement, Token, Type,
+	Value,
+};
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Calls are evaluated recursively and every call takes kilobytes of stack, deeper calls are an error
+// instead of overflowing the small stacks of WebAssembly and test threads. Interpreters running on a
+// larger stack can allow more calls with Interpreter::with_max_call_depth.
+pub const MAX_CALL_DEPTH: usize = 64;
+
+pub struct Function {
+	pub(crate) r#type: Type,
+	pub(crate) parameters: Vec<(String, Type)>,
+	pub(crate) body: Statement,
+}
+
+// Lambdas copy the val variables they use and share the var variables with the scope they are created in
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Closure {
+	pub(crate) parameters: Vec<(String, Type)>,
+	pub(crate) body: Statement,
+	pub(crate) values: Vec<(String, Value)>,
+	pub(crate) references: Vec<(String, Rc<RefCell<Value>>)>,
+}
+
+impl Interpreter {
+	pub fn evaluate_function(
+		&mut self,
+		identifier: &Token,
+		r#type: &Option<Token>,
+		parameters: &[(Token, Token)],
+		body: &Statement,
+	) -> Result<Operation> {
+		// Get parameter types
+		let mut params: Vec<(String, Type)> = Vec::new();
+
+		for (parameter, parameter_type) in parameters.iter() {
+			params.push((
+				self.get_name(parameter)?,
+				self.resolve_type(parameter_type)?,
+			));
+		}
+
+		// Get function return type
+		let fun_type = match r#type {
+			Some(token) => self.resolve_type(token)?,
+			None => Type::Void,
+		};
+
+		let fun_name = self.get_name(identifier)?;
+
+		self.functions.insert(
+			fun_name,
+			Rc::new(Function {
+				r#type: fun_type,
+				parameters: params,
+				body: body.clone(),
+			}),
+		);
+
+		Ok(Operation::NoOp)
+	}
+
+	pub fn evaluate_call(
+		&mut self,
+		function: &Expression,
+		open_parenthesis: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let name = match function {
+			Expression::Identifier(identifier) => {
+				let name = self.get_name(identifier)?;
+
+				// Variables holding a lambda shadow functions with the same name
+				if self.find_variable_scope(&name).is_some() {
+					let closure = self.get_variable(identifier)?;
+					return self.evaluate_closure_call(closure, open_parenthesis, arguments);
+				}
+
+				name
+			}
+			Expression::Get(object, method) => {
+				if let Some(name) = self.get_enum_name(object) {
+					return self.evaluate_variant(name, method, arguments);
+				}
+
+				let object = self.evaluate_expression(object)?;
+				return self.evaluate_method(object, method, arguments);
+			}
+			_ => {
+				let closure = self.evaluate_expression(function)?;
+				return self.evaluate_closure_call(closure, open_parenthesis, arguments);
+			}
+		};
+
+		let fun = match self.functions.get(&name) {
+			Some(fun) => Rc::clone(fun),
+			None => return self.evaluate_builtin(&name, open_parenthesis, arguments),
+		};
+
+		let scope = self.create_call_scope(
+			&format!("Function {}", name),
+			&fun.parameters,
+			open_parenthesis,
+			arguments,
+		)?;
+
+		let value = match self.evaluate_call_body(scope, &fun.body, open_parenthesis)? {
+			Operation::Return(value) => value,
+			_ => Value::Void,
+		};
+
+		if !fun.r#type.accepts(&value.r#type()) {
+			return Err(Error::new_runtime_error(
+				format!(
+					"Function {} should return {} but returned {}",
+					name,
+					fun.r#type,
+					value.r#type()
+				),
+				Some(open_parenthesis.clone()),
+			));
+		}
+
+		Ok(value)
+	}
+
+	pub fn evaluate_lambda(
+		&mut self,
+		parameters: &[(Token, Token)],
+		body: &Statement,
+	) -> Result<Value> {
+		let mut params: Vec<(String, Type)> = Vec::new();
+
+		for (parameter, parameter_type) in parameters.iter() {
+			params.push((
+				self.get_name(parameter)?,
+				self.resolve_type(parameter_type)?,
+			));
+		}
+
+		let body = self.analyzer.analyze(body.clone())?;
+		let mut values: Vec<(String, Value)> = Vec::new();
+		let mut references: Vec<(String, Rc<RefCell<Value>>)> = Vec::new();
+
+		for name in Analyzer::find_captures(parameters, &body) {
+			let index = match self.find_variable_scope(&name) {
+				Some(index) => index,
+				None => continue,
+			};
+
+			if self.scope[index].mutable.contains(&name) {
+				let reference = self.capture_reference(index, &name);
+				references.push((name, reference));
+			} else {
+				let value = match self.scope[index].variables.get(&name) {
+					Some(value) => value.clone(),
+					None => self.scope[index].references[&name].borrow().clone(),
+				};
+				values.push((name, value));
+			}
+		}
+
+		Ok(Value::Function(Rc::new(Closure {
+			parameters: params,
+			body,
+			values,
+			references,
+		})))
+	}
+
+	fn evaluate_closure_call(
+		&mut self,
+		closure: Value,
+		open_parenthesis: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let closure = match closure {
+			Value::Function(closure) => closure,
+			other => {
+				return Err(Error::new_runtime_error(
+					format!("Unable to call {}", other.r#type()),
+					Some(open_parenthesis.clone()),
+				))
+			}
+		};
+
+		let mut scope =
+			self.create_call_scope("Lambda", &closure.parameters, open_parenthesis, arguments)?;
+
+		for (name, value) in closure.values.iter() {
+			scope.variables.insert(name.clone(), value.clone());
+		}
+
+		for (name, reference) in closure.references.iter() {
+			scope.references.insert(name.clone(), Rc::clone(reference));
+			scope.mutable.insert(name.clone());
+		}
+
+		match self.evaluate_call_body(scope, &closure.body, open_parenthesis)? {
+			Operation::Return(value) => Ok(value),
+			_ => Ok(Value::Void),
+		}
+	}
+
+	fn evaluate_call_body(
+		&mut self,
+		scope: Scope,
+		body: &Statement,
+		open_parenthesis: &Token,
+	) -> Result<Operation> {
+		if self.calls >= self.max_calls {
+			return Err(Error::new_runtime_error(
+				format!("Maximum call depth of {} exceeded", self.max_calls),
+				Some(open_parenthesis.clone()),
+			));
+		}
+
+		self.calls += 1;
+		self.scope.push(scope);
+		let operation = self.evaluate_statement(body);
+		self.scope.pop();
+		self.calls -= 1;
+
+		operation
+	}
+
+	// Evaluates the arguments in the scope of the caller and sets them as parameters in a new scope
+	fn create_call_scope(
+		&mut self,
+		callee: &str,
+		parameters: &[(String, Type)],
+		open_parenthesis: &Token,
+		arguments: &[Expression],
+	) -> Result<Scope> {
+		if parameters.len() != arguments.len() {
+			return Err(Error::new_runtime_error(
+				format!(
+					"{} expects {} arguments but got {}",
+					callee,
+					parameters.len(),
+					arguments.len()
+				),
+				Some(open_parenthesis.clone()),
+			));
+		}
+
+		let mut scope = Scope::new(ScopeContext::Fun);
+
+		for ((parameter_name, parameter_type), argument) in parameters.iter().zip(arguments) {
+			let value = self.evaluate_expression(argument)?;
+
+			if !parameter_type.accepts(&value.r#type()) {
+				return Err(Error::new_runtime_error(
+					format!(
+						"Expected {} for parameter {} but got {}",
+						parameter_type,
+						parameter_name,
+						value.r#type()
+					),
+					Some(open_parenthesis.clone()),
+				));
+			}
+
+			scope.variables.insert(parameter_name.clone(), value);
+		}
+
+		Ok(scope)
+	}
+
+	pub fn evaluate_return(&mut self, right: &Option<Expression>) -> Result<Operation> {
+		match right {
+			Some(expression) => Ok(Operation::Return(self.evaluate_expression(expression)?)),
+			None => Ok(Operation::Return(Value::Void)),
+		}
+	}
+}
@@ -0,0 +1,100 @@
+use crate::Value;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::fmt;
+use std::rc::Rc;
+
+/// A value passed into a script from the outside. Unlike `Value` it can't
+/// describe ranges, records, enums or functions, so inputs can't fake them.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Input {
+	Integer(i64),
+	Float(f64),
+	Boolean(bool),
+	String(String),
+	Array(Vec<Input>),
+	Map(Vec<(String, Input)>),
+}
+
+impl From<Input> for Value {
+	fn from(input: Input) -> Value {
+		match input {
+			Input::Integer(value) => Value::Integer(value),
+			Input::Float(value) => Value::Float(value),
+			Input::Boolean(value) => Value::Boolean(value),
+			Input::String(value) => Value::String(value),
+			Input::Array(elements) => Value::Array(Rc::new(RefCell::new(
+				elements.into_iter().map(Value::from).collect(),
+			))),
+			Input::Map(entries) => Value::Map(Rc::new(RefCell::new(
+				entries
+					.into_iter()
+					.map(|(key, value)| (Value::String(key), Value::from(value)))
+					.collect(),
+			))),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Input {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Input, D::Error> {
+		deserializer.deserialize_any(InputVisitor)
+	}
+}
+
+struct InputVisitor;
+
+impl<'de> Visitor<'de> for InputVisitor {
+	type Value = Input;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "an Int, Float, Bool, String, array or object")
+	}
+
+	fn visit_bool<E: de::Error>(self, value: bool) -> Result<Input, E> {
+		Ok(Input::Boolean(value))
+	}
+
+	fn visit_i64<E: de::Error>(self, value: i64) -> Result<Input, E> {
+		Ok(Input::Integer(value))
+	}
+
+	fn visit_u64<E: de::Error>(self, value: u64) -> Result<Input, E> {
+		i64::try_from(value)
+			.map(Input::Integer)
+			.map_err(|_| E::custom(format!("Int {} is out of range", value)))
+	}
+
+	fn visit_f64<E: de::Error>(self, value: f64) -> Result<Input, E> {
+		Ok(Input::Float(value))
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<Input, E> {
+		Ok(Input::String(value.to_string()))
+	}
+
+	fn visit_string<E: de::Error>(self, value: String) -> Result<Input, E> {
+		Ok(Input::String(value))
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Input, A::Error> {
+		let mut elements = Vec::new();
+		while let Some(element) = seq.next_element()? {
+			elements.push(element);
+		}
+
+		Ok(Input::Array(elements))
+	}
+
+	// Objects keep the order of their keys, like map literals in a script
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Input, A::Error> {
+		let mut entries = Vec::new();
+		while let Some(entry) = map.next_entry()? {
+			entries.push(entry);
+		}
+
+		Ok(Input::Map(entries))
+	}
+}
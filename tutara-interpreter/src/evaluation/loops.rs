@@ -0,0 +1,127 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Operation, Result, Scope, ScopeContext, Statement, Value};
+
+use std::iter::successors;
+
+impl Interpreter {
+	pub fn evaluate_while(
+		&mut self,
+		condition: &Expression,
+		body: &Statement,
+	) -> Result<Operation> {
+		loop {
+			match self.evaluate_expression(condition)? {
+				Value::Boolean(true) => {}
+				Value::Boolean(false) => break,
+				_ => {
+					return Err(Error::new_runtime_error(
+						"Unsupported type in condition".to_string(),
+						None,
+					))
+				}
+			}
+
+			match self.evaluate_scoped(ScopeContext::While, body)? {
+				Operation::Break => break,
+				Operation::Return(value) => return Ok(Operation::Return(value)),
+				Operation::Continue | Operation::NoOp => {}
+			}
+		}
+
+		Ok(Operation::NoOp)
+	}
+
+	pub fn evaluate_for(
+		&mut self,
+		identifier: &Expression,
+		iterable: &Expression,
+		body: &Statement,
+	) -> Result<Operation> {
+		let name = match identifier {
+			Expression::Identifier(identifier) => self.get_name(identifier)?,
+			_ => {
+				return Err(Error::new_runtime_error(
+					"Unsupported identifier".to_string(),
+					None,
+				))
+			}
+		};
+
+		let iterable = self.evaluate_expression(iterable)?;
+
+		for item in self.iterate(iterable)? {
+			let mut scope = Scope::new(ScopeContext::While);
+			scope.variables.insert(name.clone(), item);
+
+			self.scope.push(scope);
+			let operation = self.evaluate_statement(body);
+			self.scope.pop();
+
+			match operation? {
+				Operation::Break => break,
+				Operation::Return(value) => return Ok(Operation::Return(value)),
+				Operation::Continue | Operation::NoOp => {}
+			}
+		}
+
+		Ok(Operation::NoOp)
+	}
+
+	pub fn evaluate_continue(&mut self) -> Result<Operation> {
+		if self.in_loop() {
+			Ok(Operation::Continue)
+		} else {
+			Err(Error::new_runtime_error(
+				"Unable to continue in current scope".to_string(),
+				None,
+			))
+		}
+	}
+
+	pub fn evaluate_break(&mut self) -> Result<Operation> {
+		if self.in_loop() {
+			Ok(Operation::Break)
+		} else {
+			Err(Error::new_runtime_error(
+				"Unable to break from current scope".to_string(),
+				None,
+			))
+		}
+	}
+
+	fn iterate(&self, iterable: Value) -> Result<Box<dyn Iterator<Item = Value>>> {
+		match iterable {
+			Value::String(string) => Ok(Box::new(
+				string
+					.chars()
+					.map(|character| Value::String(character.to_string()))
+					.collect::<Vec<Value>>()
+					.into_iter(),
+			)),
+			Value::Range(range) => Ok(Box::new(
+				successors(Some(range.start), move |value| {
+					value.checked_add(range.step)
+				})
+				.take_while(move |value| range.contains(*value))
+				.map(Value::Integer),
+			)),
+			Value::Array(array) => Ok(Box::new(array.borrow().clone().into_iter())),
+			_ => Err(Error::new_runtime_error(
+				format!("Unable to iterate over {}", iterable.r#type()),
+				None,
+			)),
+		}
+	}
+
+	fn in_loop(&self) -> bool {
+		for scope in self.scope.iter().rev() {
+			match scope.scope_context {
+				ScopeContext::While => return true,
+				ScopeContext::Fun => return false,
+				_ => {}
+			}
+		}
+
+		false
+	}
+}
@@ -0,0 +1,154 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, ErrorCode, Expression, Result, Token, Value};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+impl Interpreter {
+	pub fn evaluate_map(
+		&mut self,
+		open_curly_bracket: &Token,
+		entries: &[(Expression, Expression)],
+	) -> Result<Value> {
+		let map = Rc::new(RefCell::new(Vec::new()));
+
+		for (key, value) in entries {
+			let key = self.evaluate_expression(key)?;
+			let value = self.evaluate_expression(value)?;
+
+			self.insert_map_value(&map, key, value, open_curly_bracket)?;
+		}
+
+		Ok(Value::Map(map))
+	}
+
+	pub fn get_map_value(
+		&self,
+		map: &[(Value, Value)],
+		key: &Value,
+		open_square_bracket: &Token,
+	) -> Result<Value> {
+		match map.iter().find(|(entry, _)| entry == key) {
+			Some((_, value)) => Ok(value.clone()),
+			None => Err(Error::new_runtime_error(
+				format!("Key {} not found in map", key),
+				Some(open_square_bracket.clone()),
+			)
+			.with_code(ErrorCode::MissingKey)),
+		}
+	}
+
+	// Inserts the value or replaces the value of an existing key
+	pub fn insert_map_value(
+		&self,
+		map: &RefCell<Vec<(Value, Value)>>,
+		key: Value,
+		value: Value,
+		token: &Token,
+	) -> Result<()> {
+		let mut map = map.borrow_mut();
+
+		match (map.first(), &key, &value) {
+			(_, Value::Void, _)
+			| (_, Value::Float(_), _)
+			| (_, Value::Range(_), _)
+			| (_, Value::Array(_), _)
+			| (_, Value::Map(_), _)
+			| (_, Value::Record(_), _)
+			| (_, Value::Enum(_), _)
+			| (_, Value::Function(_), _) => {
+				return Err(Error::new_runtime_error(
+					format!("Unable to use {} as map key", key.r#type()),
+					Some(token.clone()),
+				))
+			}
+			(None, _, Value::Void) => {
+				return Err(Error::new_runtime_error(
+					"Map values can not be Void".to_string(),
+					Some(token.clone()),
+				))
+			}
+			(Some((first_key, first_value)), _, _)
+				if first_key.r#type() != key.r#type() || first_value.r#type() != value.r#type() =>
+			{
+				return Err(Error::new_runtime_error(
+					format!(
+						"Can not insert {} and {} into Map<{}, {}>",
+						key.r#type(),
+						value.r#type(),
+						first_key.r#type(),
+						first_value.r#type()
+					),
+					Some(token.clone()),
+				))
+			}
+			_ => {}
+		}
+
+		match map.iter_mut().find(|(entry, _)| *entry == key) {
+			Some(entry) => entry.1 = value,
+			None => map.push((key, value)),
+		}
+
+		Ok(())
+	}
+
+	pub fn evaluate_map_remove(
+		&mut self,
+		map: Rc<RefCell<Vec<(Value, Value)>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let key = self.evaluate_arguments(method, arguments, 1)?.remove(0);
+		let mut map = map.borrow_mut();
+
+		match map.iter().position(|(entry, _)| *entry == key) {
+			Some(index) => Ok(map.remove(index).1),
+			None => Err(Error::new_runtime_error(
+				format!("Key {} not found in map", key),
+				Some(method.clone()),
+			)
+			.with_code(ErrorCode::MissingKey)),
+		}
+	}
+
+	pub fn evaluate_map_contains(
+		&mut self,
+		map: Rc<RefCell<Vec<(Value, Value)>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		let key = self.evaluate_arguments(method, arguments, 1)?.remove(0);
+		let contains = map.borrow().iter().any(|(entry, _)| *entry == key);
+
+		Ok(Value::Boolean(contains))
+	}
+
+	pub fn evaluate_map_keys(
+		&mut self,
+		map: Rc<RefCell<Vec<(Value, Value)>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		self.evaluate_arguments(method, arguments, 0)?;
+		let keys = map.borrow().iter().map(|(key, _)| key.clone()).collect();
+
+		Ok(Value::Array(Rc::new(RefCell::new(keys))))
+	}
+
+	pub fn evaluate_map_values(
+		&mut self,
+		map: Rc<RefCell<Vec<(Value, Value)>>>,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		self.evaluate_arguments(method, arguments, 0)?;
+		let values = map
+			.borrow()
+			.iter()
+			.map(|(_, value)| value.clone())
+			.collect();
+
+		Ok(Value::Array(Rc::new(RefCell::new(values))))
+	}
+}
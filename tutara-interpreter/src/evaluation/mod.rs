@@ -0,0 +1,47 @@
+mod input;
+pub use input::*;
+
+mod operation;
+pub use operation::*;
+
+mod scope;
+pub use scope::*;
+
+mod types;
+pub use types::*;
+
+mod value;
+pub use value::*;
+
+mod arrays;
+
+mod builtins;
+
+mod choice;
+
+mod common;
+
+mod enums;
+pub use enums::*;
+
+mod expressions;
+
+mod functions;
+pub use functions::*;
+
+mod loops;
+
+mod maps;
+
+mod numbers;
+
+mod operations;
+
+mod ranges;
+
+mod records;
+pub use records::*;
+
+mod strings;
+
+mod variables;
@@ -0,0 +1,34 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Result, Token, Value};
+
+impl Interpreter {
+	pub fn evaluate_to_float(
+		&mut self,
+		value: i64,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		self.evaluate_arguments(method, arguments, 0)?;
+
+		Ok(Value::Float(value as f64))
+	}
+
+	// Floats are truncated towards zero, NaN and values outside of the Int range can not be converted
+	pub fn evaluate_to_int(
+		&mut self,
+		value: f64,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		self.evaluate_arguments(method, arguments, 0)?;
+
+		if value >= i64::MIN as f64 && value < -(i64::MIN as f64) {
+			Ok(Value::Integer(value as i64))
+		} else {
+			Err(Error::new_runtime_error(
+				format!("Unable to convert {:?} to Int", value),
+				Some(method.clone()),
+			))
+		}
+	}
+}
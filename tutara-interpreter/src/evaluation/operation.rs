@@ -0,0 +1,8 @@
+use crate::Value;
+
+pub enum Operation {
+	Return(Value),
+	Break,
+	Continue,
+	NoOp,
+}
@@ -0,0 +1,100 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, ErrorCode, Expression, Result, Token, TokenType, Value};
+use std::convert::TryFrom;
+
+impl Interpreter {
+	pub fn evaluate_operator(
+		&mut self,
+		left: &Expression,
+		right: &Expression,
+		operator: &Token,
+	) -> Result<Value> {
+		use Value::*;
+
+		let operations = (
+			self.evaluate_expression(left)?,
+			self.evaluate_expression(right)?,
+		);
+
+		match (&operations, &operator.r#type) {
+			((Integer(lhs), Integer(rhs)), TokenType::Plus) => {
+				self.check_overflow(lhs.checked_add(*rhs), operator)
+			}
+			((Integer(lhs), Integer(rhs)), TokenType::Minus) => {
+				self.check_overflow(lhs.checked_sub(*rhs), operator)
+			}
+			((Integer(lhs), Integer(rhs)), TokenType::Multiply) => {
+				self.check_overflow(lhs.checked_mul(*rhs), operator)
+			}
+			((Integer(_), Integer(0)), TokenType::Division)
+			| ((Integer(_), Integer(0)), TokenType::Modulo) => Err(Error::new_runtime_error(
+				"Division by zero".to_string(),
+				Some(operator.clone()),
+			)
+			.with_code(ErrorCode::DivisionByZero)),
+			((Integer(lhs), Integer(rhs)), TokenType::Division) => {
+				self.check_overflow(lhs.checked_div(*rhs), operator)
+			}
+			((Integer(lhs), Integer(rhs)), TokenType::Modulo) => {
+				self.check_overflow(lhs.checked_rem(*rhs), operator)
+			}
+			((Integer(_), Integer(rhs)), TokenType::Exponentiation) if *rhs < 0 => {
+				Err(Error::new_runtime_error(
+					"Negative exponent on Int".to_string(),
+					Some(operator.clone()),
+				)
+				.with_code(ErrorCode::NegativeExponent))
+			}
+			((Integer(lhs), Integer(rhs)), TokenType::Exponentiation) => self.check_overflow(
+				u32::try_from(*rhs)
+					.ok()
+					.and_then(|rhs| lhs.checked_pow(rhs)),
+				operator,
+			),
+			((Integer(lhs), Integer(rhs)), TokenType::GreaterOrEqual) => Ok(Boolean(lhs >= rhs)),
+			((Integer(lhs), Integer(rhs)), TokenType::LesserOrEqual) => Ok(Boolean(lhs <= rhs)),
+			((Integer(lhs), Integer(rhs)), TokenType::Greater) => Ok(Boolean(lhs > rhs)),
+			((Integer(lhs), Integer(rhs)), TokenType::Lesser) => Ok(Boolean(lhs < rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Plus) => Ok(Float(lhs + rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Minus) => Ok(Float(lhs - rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Multiply) => Ok(Float(lhs * rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Division) => Ok(Float(lhs / rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Exponentiation) => Ok(Float(lhs.powf(*rhs))),
+			((Float(lhs), Float(rhs)), TokenType::Modulo) => Ok(Float(lhs % rhs)),
+			((Float(lhs), Float(rhs)), TokenType::GreaterOrEqual) => Ok(Boolean(lhs >= rhs)),
+			((Float(lhs), Float(rhs)), TokenType::LesserOrEqual) => Ok(Boolean(lhs <= rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Greater) => Ok(Boolean(lhs > rhs)),
+			((Float(lhs), Float(rhs)), TokenType::Lesser) => Ok(Boolean(lhs < rhs)),
+			((Boolean(lhs), Boolean(rhs)), TokenType::And) => Ok(Boolean(*lhs && *rhs)),
+			((Boolean(lhs), Boolean(rhs)), TokenType::Or) => Ok(Boolean(*lhs || *rhs)),
+			((String(lhs), String(rhs)), TokenType::Plus) => Ok(String(format!("{}{}", lhs, rhs))),
+			((lhs, rhs), TokenType::Equal) if lhs.r#type() == rhs.r#type() => {
+				Ok(Boolean(lhs == rhs))
+			}
+			((lhs, rhs), TokenType::NotEqual) if lhs.r#type() == rhs.r#type() => {
+				Ok(Boolean(lhs != rhs))
+			}
+			((lhs, rhs), _) => Err(Error::new_runtime_error(
+				format!(
+					"Unsupported operation {} on {} and {}",
+					operator.r#type,
+					lhs.r#type(),
+					rhs.r#type()
+				),
+				Some(operator.clone()),
+			)),
+		}
+	}
+
+	// Int operations are checked, an overflow stops the program instead of wrapping around
+	fn check_overflow(&self, result: Option<i64>, operator: &Token) -> Result<Value> {
+		match result {
+			Some(result) => Ok(Value::Integer(result)),
+			None => Err(Error::new_runtime_error(
+				"Integer overflow".to_string(),
+				Some(operator.clone()),
+			)
+			.with_code(ErrorCode::IntegerOverflow)),
+		}
+	}
+}
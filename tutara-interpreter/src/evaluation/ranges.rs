@@ -0,0 +1,60 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Range, Result, Token, TokenType, Value};
+
+impl Interpreter {
+	pub fn evaluate_range(
+		&mut self,
+		start: &Expression,
+		operator: &Token,
+		end: &Expression,
+	) -> Result<Value> {
+		match (
+			self.evaluate_expression(start)?,
+			self.evaluate_expression(end)?,
+		) {
+			(Value::Integer(start), Value::Integer(end)) => Ok(Value::Range(Range {
+				start,
+				end,
+				step: 1,
+				inclusive: operator.r#type == TokenType::RangeInclusive,
+			})),
+			(start, end) => Err(Error::new_runtime_error(
+				format!(
+					"Unsupported range from {} to {}",
+					start.r#type(),
+					end.r#type()
+				),
+				Some(operator.clone()),
+			)),
+		}
+	}
+
+	pub fn evaluate_range_step(
+		&mut self,
+		range: Range,
+		method: &Token,
+		arguments: &[Expression],
+	) -> Result<Value> {
+		if arguments.len() != 1 {
+			return Err(Error::new_runtime_error(
+				format!(
+					"Function step expects 1 arguments but got {}",
+					arguments.len()
+				),
+				Some(method.clone()),
+			));
+		}
+
+		match self.evaluate_expression(&arguments[0])? {
+			Value::Integer(step) if step > 0 => Ok(Value::Range(Range { step, ..range })),
+			Value::Integer(_) => Err(Error::new_runtime_error(
+				"Range step must be positive".to_string(),
+				Some(method.clone()),
+			)),
+			value => Err(Error::new_runtime_error(
+				format!("Expected Int for parameter step but got {}", value.r#type()),
+				Some(method.clone()),
+			)),
+		}
+	}
+}
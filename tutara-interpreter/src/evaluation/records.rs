@@ -0,0 +1,293 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Operation, Record, Result, Token, Type, Value};
+
+use std::rc::Rc;
+
+pub struct RecordType {
+	pub(crate) fields: Vec<(String, Type)>,
+}
+
+impl Interpreter {
+	pub fn evaluate_record(
+		&mut self,
+		identifier: &Token,
+		fields: &[(Token, Token)],
+	) -> Result<Operation> {
+		let name = self.get_name(identifier)?;
+
+		if self.is_type_declared(identifier)? {
+			return Err(Error::new_runtime_error(
+				format!("Type {} is already declared", name),
+				Some(identifier.clone()),
+			));
+		}
+
+		let mut record_fields: Vec<(String, Type)> = Vec::new();
+
+		for (field, field_type) in fields.iter() {
+			let field_name = self.get_name(field)?;
+			let field_type = self.resolve_type(field_type)?;
+
+			if record_fields
+				.iter()
+				.any(|(existing, _)| *existing == field_name)
+			{
+				return Err(Error::new_runtime_error(
+					format!("Field {} is already declared", field_name),
+					Some(field.clone()),
+				));
+			}
+
+			record_fields.push((field_name, field_type));
+		}
+
+		self.records.insert(
+			name,
+			Rc::new(RecordType {
+				fields: record_fields,
+			}),
+		);
+
+		Ok(Operation::NoOp)
+	}
+
+	pub fn evaluate_record_construction(
+		&mut self,
+		identifier: &Token,
+		fields: &[(Token, Expression)],
+	) -> Result<Value> {
+		let name = self.get_name(identifier)?;
+		let record = match self.records.get(&name) {
+			Some(record) => Rc::clone(record),
+			None => {
+				return Err(Error::new_runtime_error(
+					format!("Unknown type {}", name),
+					Some(identifier.clone()),
+				))
+			}
+		};
+
+		let mut values: Vec<(String, Value)> = Vec::new();
+
+		for (field, expression) in fields.iter() {
+			let field_name = self.get_name(field)?;
+			let field_type = match record
+				.fields
+				.iter()
+				.find(|(declared, _)| *declared == field_name)
+			{
+				Some((_, field_type)) => field_type,
+				None => {
+					return Err(Error::new_runtime_error(
+						format!("Unknown field {} for {}", field_name, name),
+						Some(field.clone()),
+					))
+				}
+			};
+
+			if values.iter().any(|(existing, _)| *existing == field_name) {
+				return Err(Error::new_runtime_error(
+					format!("Field {} is already assigned", field_name),
+					Some(field.clone()),
+				));
+			}
+
+			let value = self.evaluate_expression(expression)?;
+
+			if !field_type.accepts(&value.r#type()) {
+				return Err(Error::new_runtime_error(
+					format!(
+						"Can not assign {} to field {} of type {}",
+						value.r#type(),
+						field_name,
+						field_type
+					),
+					Some(field.clone()),
+				));
+			}
+
+			values.push((field_name, value));
+		}
+
+		// Fields are kept in declaration order so equal records compare equal
+		let mut record_fields: Vec<(String, Value)> = Vec::new();
+
+		for (field_name, _) in record.fields.iter() {
+			match values
+				.iter()
+				.position(|(assigned, _)| assigned == field_name)
+			{
+				Some(index) => record_fields.push(values.remove(index)),
+				None => {
+					return Err(Error::new_runtime_error(
+						format!("Missing field {} for {}", field_name, name),
+						Some(identifier.clone()),
+					))
+				}
+			}
+		}
+
+		Ok(Value::Record(Record {
+			name,
+			fields: record_fields,
+		}))
+	}
+
+	pub fn get_record_field(&self, record: &Record, property: &Token) -> Result<Value> {
+		let name = self.get_name(property)?;
+
+		match record.fields.iter().find(|(field, _)| *field == name) {
+			Some((_, value)) => Ok(value.clone()),
+			None => Err(Error::new_runtime_error(
+				format!("Unknown field {} for {}", name, record.name),
+				Some(property.clone()),
+			)),
+		}
+	}
+
+	// Records are values, a field is assigned by updating the variable, array element or map value that
+	// holds the record
+	pub fn set_record_field(
+		&mut self,
+		object: &Expression,
+		property: &Token,
+		operator: &Token,
+		value: Value,
+	) -> Result<()> {
+		let mut path = vec![self.get_name(property)?];
+		let mut current = object;
+
+		let identifier = loop {
+			match current {
+				Expression::Get(object, property) => {
+					path.push(self.get_name(property)?);
+					current = object;
+				}
+				Expression::Identifier(identifier) => break identifier,
+				Expression::Index(indexed, open_square_bracket, index, _) => {
+					let indexed = self.evaluate_expression(indexed)?;
+					let index = self.evaluate_expression(index)?;
+
+					return self.with_element_mut(indexed, index, open_square_bracket, |element| {
+						Interpreter::set_field(element, &path, property, operator, value)
+					});
+				}
+				_ => {
+					return Err(Error::new_runtime_error(
+						"Unable to assign to a field of a temporary value".to_string(),
+						Some(operator.clone()),
+					))
+				}
+			}
+		};
+
+		self.with_variable_mut(identifier, |record| {
+			Interpreter::set_field(record, &path, property, operator, value)
+		})
+	}
+
+	// Follows the path of field names, from the last to the first, and assigns the value to the field
+	fn set_field(
+		mut field: &mut Value,
+		path: &[String],
+		property: &Token,
+		operator: &Token,
+		value: Value,
+	) -> Result<()> {
+		for name in path.iter().rev() {
+			field = match field {
+				Value::Record(record) => {
+					let record_name = &record.name;
+
+					match record.fields.iter_mut().find(|(field, _)| field == name) {
+						Some((_, value)) => value,
+						None => {
+							return Err(Error::new_runtime_error(
+								format!("Unknown field {} for {}", name, record_name),
+								Some(property.clone()),
+							))
+						}
+					}
+				}
+				other => {
+					return Err(Error::new_runtime_error(
+						format!("Unable to get field {} of {}", name, other.r#type()),
+						Some(property.clone()),
+					))
+				}
+			}
+		}
+
+		if !field.r#type().accepts(&value.r#type()) {
+			return Err(Error::new_runtime_error(
+				format!(
+					"Can not assign {} to field of type {}",
+					value.r#type(),
+					field.r#type()
+				),
+				Some(operator.clone()),
+			));
+		}
+
+		*field = value;
+		Ok(())
+	}
+
+	// Builtin types are always declared, records and enums once their declaration is evaluated
+	pub(crate) fn is_type_declared(&self, identifier: &Token) -> Result<bool> {
+		match Type::from_token(identifier)? {
+			Type::Record(name) => {
+				Ok(self.records.contains_key(&name) || self.enums.contains_key(&name))
+			}
+			_ => Ok(true),
+		}
+	}
+
+	// Type of a type specification, record and enum types have to be declared before they are used
+	pub fn resolve_type(&self, token: &Token) -> Result<Type> {
+		let r#type = self.resolve_enums(Type::from_token(token)?);
+
+		match self.find_unknown_record(&r#type) {
+			Some(unknown) => Err(Error::new_runtime_error(
+				format!("Unknown type {}", unknown),
+				Some(token.clone()),
+			)),
+			None => Ok(r#type),
+		}
+	}
+
+	// Type names can not tell records and enums apart, declared enums are resolved by name
+	fn resolve_enums(&self, r#type: Type) -> Type {
+		match r#type {
+			Type::Record(name) if self.enums.contains_key(&name) => Type::Enum(name),
+			Type::Array(element) => Type::Array(Box::new(self.resolve_enums(*element))),
+			Type::Map(key, value) => Type::Map(
+				Box::new(self.resolve_enums(*key)),
+				Box::new(self.resolve_enums(*value)),
+			),
+			Type::Function(parameters, result) => Type::Function(
+				parameters
+					.into_iter()
+					.map(|parameter| self.resolve_enums(parameter))
+					.collect(),
+				Box::new(self.resolve_enums(*result)),
+			),
+			r#type => r#type,
+		}
+	}
+
+	fn find_unknown_record(&self, r#type: &Type) -> Option<String> {
+		match r#type {
+			Type::Record(name) if !self.records.contains_key(name) => Some(name.clone()),
+			Type::Array(element) => self.find_unknown_record(element),
+			Type::Map(key, value) => self
+				.find_unknown_record(key)
+				.or_else(|| self.find_unknown_record(value)),
+			Type::Function(parameters, result) => parameters
+				.iter()
+				.chain(std::iter::once(&**result))
+				.find_map(|r#type| self.find_unknown_record(r#type)),
+			_ => None,
+		}
+	}
+}
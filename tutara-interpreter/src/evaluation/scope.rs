@@ -0,0 +1,29 @@
+use crate::Value;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+pub struct Scope {
+	pub(crate) scope_context: ScopeContext,
+	pub(crate) variables: HashMap<String, Value>,
+	pub(crate) references: HashMap<String, Rc<RefCell<Value>>>, // Variables shared with closures
+	pub(crate) mutable: HashSet<String>,                        // Variables declared with var
+}
+
+impl Scope {
+	pub fn new(scope_context: ScopeContext) -> Scope {
+		Scope {
+			scope_context,
+			variables: HashMap::new(),
+			references: HashMap::new(),
+			mutable: HashSet::new(),
+		}
+	}
+}
+
+pub enum ScopeContext {
+	While,
+	If,
+	Fun,
+	Main,
+}
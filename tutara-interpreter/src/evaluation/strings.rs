@@ -0,0 +1,26 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Result, Token, Value};
+
+impl Interpreter {
+	// Ints, floats, bools and strings are converted to text, other values can not be interpolated
+	pub fn evaluate_template(&mut self, token: &Token, parts: &[Expression]) -> Result<Value> {
+		let mut text = String::new();
+
+		for part in parts {
+			match self.evaluate_expression(part)? {
+				value @ Value::Integer(_)
+				| value @ Value::Float(_)
+				| value @ Value::Boolean(_)
+				| value @ Value::String(_) => text.push_str(&value.to_string()),
+				value => {
+					return Err(Error::new_runtime_error(
+						format!("Unable to interpolate {}", value.r#type()),
+						Some(token.clone()),
+					))
+				}
+			}
+		}
+
+		Ok(Value::String(text))
+	}
+}
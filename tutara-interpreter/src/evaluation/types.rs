@@ -0,0 +1,152 @@
+use crate::{Error, ErrorCode, Literal, Result, Token, TokenType};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug};
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub enum Type {
+	Int,
+	Float,
+	Bool,
+	String,
+	Range,
+	Array(Box<Type>),
+	Map(Box<Type>, Box<Type>),
+	Record(String),
+	Enum(String),
+	Function(Vec<Type>, Box<Type>),
+	Void,
+}
+
+impl Type {
+	pub fn from_token(token: &Token) -> Result<Type> {
+		match (&token.r#type, &token.literal) {
+			(TokenType::Identifier, Some(Literal::String(name))) => match Type::from_name(name) {
+				Some(r#type) => Ok(r#type),
+				None => Err(Error::new_runtime_error(
+					format!("Unknown type {}", name),
+					Some(token.clone()),
+				)
+				.with_code(ErrorCode::UnknownType)),
+			},
+			_ => Err(Error::new_runtime_error(
+				"Invalid token/literal".to_string(),
+				Some(token.clone()),
+			)),
+		}
+	}
+
+	fn from_name(name: &str) -> Option<Type> {
+		match name {
+			"Int" => Some(Type::Int),
+			"Float" => Some(Type::Float),
+			"Bool" => Some(Type::Bool),
+			"String" => Some(Type::String),
+			"Range" => Some(Type::Range),
+			_ if Type::is_record_name(name) => Some(Type::Record(name.to_string())),
+			_ => {
+				let (name, arguments) = name.strip_suffix('>')?.split_once('<')?;
+				let arguments = Type::split_arguments(arguments)
+					.into_iter()
+					.map(|argument| match argument {
+						"Void" => Some(Type::Void),
+						argument => Type::from_name(argument),
+					})
+					.collect::<Option<Vec<Type>>>()?;
+
+				// Void is only a type argument as the result of a function type, like Fun<Int, Void>
+				match (name, arguments.as_slice()) {
+					("Array", [element]) if *element != Type::Void => {
+						Some(Type::Array(Box::new(element.clone())))
+					}
+					("Map", [key, value]) if !arguments.contains(&Type::Void) => {
+						Some(Type::Map(Box::new(key.clone()), Box::new(value.clone())))
+					}
+					("Fun", [parameters @ .., result]) if !parameters.contains(&Type::Void) => {
+						Some(Type::Function(
+							parameters.to_vec(),
+							Box::new(result.clone()),
+						))
+					}
+					_ => None,
+				}
+			}
+		}
+	}
+
+	// Record names start with an uppercase letter, like Point, generic type names are reserved
+	fn is_record_name(name: &str) -> bool {
+		!matches!(name, "Array" | "Map" | "Fun")
+			&& name.starts_with(char::is_uppercase)
+			&& name
+				.chars()
+				.all(|character| character.is_alphanumeric() || character == '_')
+	}
+
+	// Splits type arguments on the commas that are not part of a nested type
+	fn split_arguments(arguments: &str) -> Vec<&str> {
+		let mut depth = 0;
+		let mut start = 0;
+		let mut split = Vec::new();
+
+		for (index, character) in arguments.char_indices() {
+			match character {
+				'<' => depth += 1,
+				'>' => depth -= 1,
+				',' if depth == 0 => {
+					split.push(arguments[start..index].trim());
+					start = index + 1;
+				}
+				_ => {}
+			}
+		}
+
+		split.push(arguments[start..].trim());
+		split
+	}
+
+	// Empty array and map literals have no element type and are accepted by every array or map type,
+	// lambdas have no declared result type and are accepted by every function type with the same parameters
+	pub fn accepts(&self, other: &Type) -> bool {
+		match (self, other) {
+			(Type::Array(element), Type::Array(other_element)) => {
+				**element == Type::Void
+					|| **other_element == Type::Void
+					|| element.accepts(other_element)
+			}
+			(Type::Map(key, value), Type::Map(other_key, other_value)) => {
+				**key == Type::Void
+					|| **other_key == Type::Void
+					|| key.accepts(other_key) && value.accepts(other_value)
+			}
+			(
+				Type::Function(parameters, result),
+				Type::Function(other_parameters, other_result),
+			) => {
+				parameters == other_parameters
+					&& (**other_result == Type::Void || result.accepts(other_result))
+			}
+			_ => self == other,
+		}
+	}
+}
+
+impl fmt::Display for Type {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Type::Array(element) => write!(f, "Array<{}>", element),
+			Type::Map(key, value) => write!(f, "Map<{}, {}>", key, value),
+			Type::Record(name) | Type::Enum(name) => write!(f, "{}", name),
+			Type::Function(parameters, result) => {
+				let types: Vec<String> = parameters
+					.iter()
+					.chain(std::iter::once(&**result))
+					.map(|r#type| r#type.to_string())
+					.collect();
+
+				write!(f, "Fun<{}>", types.join(", "))
+			}
+			_ => write!(f, "{:?}", self),
+		}
+	}
+}
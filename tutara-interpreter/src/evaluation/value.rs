@@ -0,0 +1,154 @@
+use crate::{Closure, Type};
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Value {
+	Integer(i64),
+	Float(f64),
+	Boolean(bool),
+	String(String),
+	Range(Range),
+	Array(Rc<RefCell<Vec<Value>>>),
+	Map(Rc<RefCell<Vec<(Value, Value)>>>),
+	Record(Record),
+	Enum(Variant),
+	Function(Rc<Closure>),
+	Void,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct Range {
+	pub start: i64,
+	pub end: i64,
+	pub step: i64,
+	pub inclusive: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Record {
+	pub name: String,
+	pub fields: Vec<(String, Value)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Variant {
+	pub r#enum: String,
+	pub name: String,
+	pub values: Vec<Value>,
+}
+
+impl Value {
+	pub fn r#type(&self) -> Type {
+		match self {
+			Value::Integer(_) => Type::Int,
+			Value::Float(_) => Type::Float,
+			Value::Boolean(_) => Type::Bool,
+			Value::String(_) => Type::String,
+			Value::Range(_) => Type::Range,
+			Value::Array(array) => Type::Array(Box::new(match array.borrow().first() {
+				Some(element) => element.r#type(),
+				None => Type::Void,
+			})),
+			Value::Map(map) => match map.borrow().first() {
+				Some((key, value)) => Type::Map(Box::new(key.r#type()), Box::new(value.r#type())),
+				None => Type::Map(Box::new(Type::Void), Box::new(Type::Void)),
+			},
+			Value::Record(record) => Type::Record(record.name.clone()),
+			Value::Enum(variant) => Type::Enum(variant.r#enum.clone()),
+			Value::Function(closure) => Type::Function(
+				closure
+					.parameters
+					.iter()
+					.map(|(_, r#type)| r#type.clone())
+					.collect(),
+				Box::new(Type::Void),
+			),
+			Value::Void => Type::Void,
+		}
+	}
+}
+
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Value::Integer(value) => write!(f, "{}", value),
+			Value::Float(value) => write!(f, "{:?}", value),
+			Value::Boolean(value) => write!(f, "{}", value),
+			Value::String(value) => write!(f, "{}", value),
+			Value::Range(value) => write!(f, "{}", value),
+			Value::Array(array) => {
+				let elements: Vec<String> = array
+					.borrow()
+					.iter()
+					.map(|element| element.to_string())
+					.collect();
+
+				write!(f, "[{}]", elements.join(", "))
+			}
+			Value::Map(map) => {
+				let entries: Vec<String> = map
+					.borrow()
+					.iter()
+					.map(|(key, value)| format!("{}: {}", key, value))
+					.collect();
+
+				write!(f, "{{{}}}", entries.join(", "))
+			}
+			Value::Record(record) => write!(f, "{}", record),
+			Value::Enum(variant) => write!(f, "{}", variant),
+			Value::Function(_) => write!(f, "{}", self.r#type()),
+			Value::Void => Ok(()),
+		}
+	}
+}
+
+impl Range {
+	pub fn contains(&self, value: i64) -> bool {
+		value >= self.start && (value < self.end || self.inclusive && value == self.end)
+	}
+}
+
+impl fmt::Display for Record {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let fields: Vec<String> = self
+			.fields
+			.iter()
+			.map(|(name, value)| format!("{}: {}", name, value))
+			.collect();
+
+		write!(f, "{} {{ {} }}", self.name, fields.join(", "))
+	}
+}
+
+impl fmt::Display for Variant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.values.is_empty() {
+			return write!(f, "{}.{}", self.r#enum, self.name);
+		}
+
+		let values: Vec<String> = self.values.iter().map(|value| value.to_string()).collect();
+
+		write!(f, "{}.{}({})", self.r#enum, self.name, values.join(", "))
+	}
+}
+
+impl fmt::Display for Range {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let operator = if self.inclusive { "..=" } else { ".." };
+
+		if self.step == 1 {
+			write!(f, "{}{}{}", self.start, operator, self.end)
+		} else {
+			write!(
+				f,
+				"({}{}{}).step({})",
+				self.start, operator, self.end, self.step
+			)
+		}
+	}
+}
@@ -0,0 +1,162 @@
+use crate::interpreter::Interpreter;
+use crate::{Error, Expression, Result, ScopeContext, Token, TokenType, Value};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+impl Interpreter {
+	pub fn get_variable(&self, identifier: &Token) -> Result<Value> {
+		let name = self.get_name(identifier)?;
+
+		match self.find_variable_scope(&name) {
+			Some(index) => match self.scope[index].variables.get(&name) {
+				Some(value) => Ok(value.clone()),
+				None => Ok(self.scope[index].references[&name].borrow().clone()),
+			},
+			None => Err(Error::new_runtime_error(
+				"Variable not found in this scope".to_string(),
+				Some(identifier.clone()),
+			)),
+		}
+	}
+
+	pub fn set_variable(
+		&mut self,
+		identifier: &Token,
+		operator: &Token,
+		expression: &Expression,
+	) -> Result<()> {
+		let value = if operator.r#type == TokenType::Assign {
+			self.evaluate_expression(expression)?
+		} else {
+			return Err(Error::new_runtime_error(
+				"Unsupported assignment operator".to_string(),
+				Some(operator.clone()),
+			));
+		};
+
+		self.with_variable_mut(identifier, |variable| {
+			if !variable.r#type().accepts(&value.r#type()) {
+				return Err(Error::new_runtime_error(
+					format!(
+						"Can not assign {} to variable of type {}",
+						value.r#type(),
+						variable.r#type()
+					),
+					Some(operator.clone()),
+				));
+			}
+
+			*variable = value;
+			Ok(())
+		})
+	}
+
+	// Variables captured by reference live outside of the scope, they are only borrowed during the update
+	pub fn with_variable_mut<T>(
+		&mut self,
+		identifier: &Token,
+		update: impl FnOnce(&mut Value) -> Result<T>,
+	) -> Result<T> {
+		let name = self.get_name(identifier)?;
+
+		match self.find_variable_scope(&name) {
+			Some(index) => {
+				let scope = &mut self.scope[index];
+
+				match scope.variables.get_mut(&name) {
+					Some(variable) => update(variable),
+					None => update(&mut scope.references[&name].borrow_mut()),
+				}
+			}
+			None => Err(Error::new_runtime_error(
+				"Variable not found in this scope".to_string(),
+				Some(identifier.clone()),
+			)),
+		}
+	}
+
+	pub(crate) fn find_variable_scope(&self, name: &str) -> Option<usize> {
+		for index in (0..self.scope.len()).rev() {
+			if self.scope[index].variables.contains_key(name)
+				|| self.scope[index].references.contains_key(name)
+			{
+				return Some(index);
+			}
+
+			if let ScopeContext::Fun = self.scope[index].scope_context {
+				break;
+			}
+		}
+
+		None
+	}
+
+	// Moves a variable out of its scope so closures can share it
+	pub(crate) fn capture_reference(&mut self, index: usize, name: &str) -> Rc<RefCell<Value>> {
+		let scope = &mut self.scope[index];
+
+		if let Some(value) = scope.variables.remove(name) {
+			scope
+				.references
+				.insert(name.to_string(), Rc::new(RefCell::new(value)));
+		}
+
+		Rc::clone(&scope.references[name])
+	}
+}
+
+impl Interpreter {
+	pub fn evaluate_declaration(
+		&mut self,
+		mutability: &Token,
+		type_specification: &Option<Token>,
+		expression: &Expression,
+	) -> Result<()> {
+		match expression {
+			Expression::Assignment(identifier, _operator, inner_expression) => {
+				let name = self.get_name(identifier)?;
+				let value = self.evaluate_expression(inner_expression)?;
+
+				if let Value::Void = value {
+					return Err(Error::new_runtime_error(
+						"Unsupported assignment operation".to_string(),
+						Some(identifier.clone()),
+					));
+				}
+
+				if let Some(type_specification) = type_specification {
+					let r#type = self.resolve_type(type_specification)?;
+
+					if !r#type.accepts(&value.r#type()) {
+						return Err(Error::new_runtime_error(
+							format!(
+								"Can not assign {} to variable of type {}",
+								value.r#type(),
+								r#type
+							),
+							Some(type_specification.clone()),
+						));
+					}
+				}
+
+				let scope_index = self.scope.len() - 1;
+				let scope = &mut self.scope[scope_index];
+
+				if mutability.r#type == TokenType::Var {
+					scope.mutable.insert(name.clone());
+				} else {
+					scope.mutable.remove(&name);
+				}
+
+				scope.references.remove(&name);
+				scope.variables.insert(name, value);
+				Ok(())
+			}
+			_ => Err(Error::new_runtime_error(
+				"Unsupported expression".to_string(),
+				None,
+			)),
+		}
+	}
+}
@@ -0,0 +1,135 @@
+use crate::parser::Parser;
+use crate::{
+	Analyzer, EnumType, Error, ErrorCode, Function, Operation, RecordType, Result, Scope,
+	ScopeContext, Statement, Value, MAX_CALL_DEPTH,
+};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub struct Interpreter {
+	pub(super) analyzer: Analyzer,
+	pub(super) scope: Vec<Scope>,
+	pub(super) functions: HashMap<String, Rc<Function>>,
+	pub(super) records: HashMap<String, Rc<RecordType>>,
+	pub(super) enums: HashMap<String, Rc<EnumType>>,
+	pub(super) output: Vec<String>,
+	pub(super) calls: usize, // Depth of the function and lambda calls being evaluated
+	pub(super) max_calls: usize,
+}
+
+impl Interpreter {
+	pub fn new() -> Interpreter {
+		Interpreter {
+			analyzer: Analyzer::new(),
+			scope: vec![Scope::new(ScopeContext::Main)],
+			functions: HashMap::new(),
+			records: HashMap::new(),
+			enums: HashMap::new(),
+			output: Vec::new(),
+			calls: 0,
+			max_calls: MAX_CALL_DEPTH,
+		}
+	}
+
+	pub fn with_inputs(inputs: HashMap<String, Value>) -> Interpreter {
+		let mut interpreter = Interpreter::new();
+
+		for (name, value) in inputs.iter() {
+			interpreter.analyzer.declare_variable(name, value.r#type());
+		}
+
+		interpreter.scope[0].variables.extend(inputs);
+
+		interpreter
+	}
+
+	// The stack of the thread running the interpreter has to fit the calls
+	pub fn with_max_call_depth(mut self, depth: usize) -> Interpreter {
+		self.max_calls = depth;
+		self
+	}
+
+	pub fn output(&self) -> &[String] {
+		&self.output
+	}
+}
+
+impl Default for Interpreter {
+	fn default() -> Interpreter {
+		Interpreter::new()
+	}
+}
+
+impl Interpreter {
+	pub fn evaluate(&mut self, parser: Parser<'_>) -> Result<Value> {
+		let statements = parser.collect::<Result<Vec<Statement>>>()?;
+
+		// Every type error is found before the script runs, the first one is returned
+		if let Some(error) = self.analyzer.check(&statements).into_iter().next() {
+			return Err(error);
+		}
+
+		let declarations = Analyzer::hoist(statements);
+		let statements = declarations
+			.types
+			.into_iter()
+			.chain(declarations.functions)
+			.chain(declarations.statements);
+
+		for statement in statements {
+			let statement = self.analyzer.analyze(statement)?;
+
+			if let Operation::Return(value) = self.evaluate_statement(&statement)? {
+				return Ok(value);
+			}
+		}
+
+		Err(
+			Error::new_runtime_error("No return statement found in script".to_string(), None)
+				.with_code(ErrorCode::MissingScriptReturn),
+		)
+	}
+
+	pub fn evaluate_statement(&mut self, statement: &Statement) -> Result<Operation> {
+		use Statement::*;
+
+		match statement {
+			Break(_) => self.evaluate_break(),
+			Continue(_) => self.evaluate_continue(),
+			While(_, condition, body) => self.evaluate_while(condition, body),
+			If(_, condition, true_branch, false_branch) => {
+				self.evaluate_if(condition, true_branch, false_branch)
+			}
+			Expression(expression) => {
+				self.evaluate_expression(expression)?;
+				Ok(Operation::NoOp)
+			}
+			Declaration(mutability, type_specification, expression) => {
+				self.evaluate_declaration(mutability, type_specification, expression)?;
+				Ok(Operation::NoOp)
+			}
+			Body(_, statements, _) => self.evaluate_body(statements),
+			Return(_, expression) => self.evaluate_return(expression),
+			Comment(_) => Ok(Operation::NoOp),
+			Match(token, subject, arms, default, _) => {
+				self.evaluate_match(token, subject, arms, default)
+			}
+			Function(_, r#type, identifier, parameters, body) => {
+				self.evaluate_function(identifier, r#type, parameters, body)
+			}
+			For(_, identifier, iterable, body) => self.evaluate_for(identifier, iterable, body),
+			Record(_, identifier, fields, _) => self.evaluate_record(identifier, fields),
+			Enum(_, identifier, variants, _) => self.evaluate_enum(identifier, variants),
+			// Statements converted by analyzer
+			Documentation(..) => Err(Error::new_runtime_error(
+				"Unexpected statement: documentation".to_string(),
+				None,
+			)),
+			Loop(..) => Err(Error::new_runtime_error(
+				"Unexpected statement: loop".to_string(),
+				None,
+			)),
+		}
+	}
+}
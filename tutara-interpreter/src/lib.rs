@@ -10,6 +10,19 @@ pub use parsing::*;
 pub mod analyzer;
 pub use analyzer::*;
 
+pub mod analysis;
+
+pub mod linter;
+pub use linter::*;
+
+pub mod linting;
+
+pub mod interpreter;
+pub use interpreter::*;
+
+pub mod evaluation;
+pub use evaluation::*;
+
 pub mod result {
 	use std::result;
 	use crate::Error;
@@ -21,5 +34,8 @@ pub mod error;
 pub use error::*;
 pub use ErrorType::*;
 
+pub mod diagnostic;
+pub use diagnostic::*;
+
 pub mod ast;
 pub use ast::*;
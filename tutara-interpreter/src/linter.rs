@@ -0,0 +1,120 @@
+use crate::{Error, ErrorType, Severity, Statement, Token};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+	UnusedVariable,
+	UnusedParameter,
+	ShadowedBinding,
+	UnnecessaryVar,    // var that is never reassigned
+	ConstantCondition, // if and while
+	SelfAssignment,    // a = a
+	BooleanComparison, // a == true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Level {
+	Off,
+	Warning,
+	Error,
+}
+
+// Levels of the rules, rules that are not configured report warnings
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LintConfig {
+	#[serde(default)]
+	pub rules: HashMap<Rule, Level>,
+}
+
+pub struct Linter {
+	config: LintConfig,
+	pub(super) scopes: Vec<Vec<LintScope>>, // Scopes by function, lambdas can use the scopes of their function
+	pub(super) enums: Vec<String>,          // Declared enums, their variants are matched by name
+	diagnostics: Vec<Error>,
+}
+
+pub(super) type LintScope = HashMap<String, LintBinding>;
+
+pub(super) struct LintBinding {
+	pub(super) identifier: Token, // Identifier of the declaration
+	pub(super) parameter: bool,
+	pub(super) mutable: bool,
+	pub(super) used: bool,
+	pub(super) reassigned: bool,
+}
+
+impl LintConfig {
+	pub fn level(&self, rule: Rule) -> Level {
+		self.rules.get(&rule).copied().unwrap_or(Level::Warning)
+	}
+}
+
+impl Linter {
+	pub fn new(config: LintConfig) -> Linter {
+		Linter {
+			config,
+			scopes: vec![vec![HashMap::new()]],
+			enums: Vec::new(),
+			diagnostics: Vec::new(),
+		}
+	}
+}
+
+impl Default for Linter {
+	fn default() -> Linter {
+		Linter::new(LintConfig::default())
+	}
+}
+
+impl Linter {
+	// Lints the statements of a script and returns the warnings and errors of the enabled rules
+	pub fn lint(&mut self, statements: &[Statement]) -> Vec<Error> {
+		self.declare_enums(statements);
+
+		for statement in statements.iter() {
+			self.lint_statement(statement);
+		}
+
+		self.pop_scope();
+		self.scopes = vec![vec![HashMap::new()]];
+
+		std::mem::take(&mut self.diagnostics)
+	}
+
+	pub(super) fn report(&mut self, rule: Rule, message: String, token: &Token) {
+		let severity = match self.config.level(rule) {
+			Level::Off => return,
+			Level::Warning => Severity::Warning,
+			Level::Error => Severity::Error,
+		};
+
+		self.diagnostics.push(Error {
+			r#type: ErrorType::Semantic(token.span()),
+			severity,
+			code: None,
+			message: format!("{} ({})", message, rule),
+			labels: Vec::new(),
+		});
+	}
+}
+
+impl fmt::Display for Rule {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use Rule::*;
+
+		match self {
+			UnusedVariable => write!(f, "unused-variable"),
+			UnusedParameter => write!(f, "unused-parameter"),
+			ShadowedBinding => write!(f, "shadowed-binding"),
+			UnnecessaryVar => write!(f, "unnecessary-var"),
+			ConstantCondition => write!(f, "constant-condition"),
+			SelfAssignment => write!(f, "self-assignment"),
+			BooleanComparison => write!(f, "boolean-comparison"),
+		}
+	}
+}
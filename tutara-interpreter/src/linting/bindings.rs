@@ -0,0 +1,134 @@
+use crate::analyzer::Analyzer;
+use crate::linter::{LintBinding, Linter, Rule};
+use crate::{Literal, Statement, Token};
+
+use std::collections::HashMap;
+
+// Bindings are tracked by scope, they are reported when their scope ends
+impl Linter {
+	pub(crate) fn declare_enums(&mut self, statements: &[Statement]) {
+		for statement in statements.iter() {
+			match statement {
+				Statement::Enum(
+					_,
+					Token {
+						literal: Some(Literal::String(name)),
+						..
+					},
+					..,
+				) => self.enums.push(name.clone()),
+				Statement::Function(_, _, _, _, body) => {
+					self.declare_enums(std::slice::from_ref(body))
+				}
+				statement => {
+					for body in Analyzer::get_bodies(statement) {
+						self.declare_enums(std::slice::from_ref(body));
+					}
+				}
+			}
+		}
+	}
+
+	pub(super) fn push_scope(&mut self) {
+		self.scopes.last_mut().unwrap().push(HashMap::new());
+	}
+
+	pub(crate) fn pop_scope(&mut self) {
+		let scope = self.scopes.last_mut().unwrap().pop().unwrap_or_default();
+
+		let mut bindings: Vec<(String, LintBinding)> = scope.into_iter().collect();
+		bindings.sort_by_key(|(_, binding)| (binding.identifier.line, binding.identifier.column));
+
+		for (name, binding) in bindings {
+			if !binding.used && binding.parameter {
+				self.report(
+					Rule::UnusedParameter,
+					format!("Parameter {} is never used", name),
+					&binding.identifier,
+				);
+			} else if !binding.used {
+				self.report(
+					Rule::UnusedVariable,
+					format!("Variable {} is never used", name),
+					&binding.identifier,
+				);
+			} else if binding.mutable && !binding.reassigned && !binding.parameter {
+				self.report(
+					Rule::UnnecessaryVar,
+					format!("Variable {} is never reassigned, use val", name),
+					&binding.identifier,
+				);
+			}
+		}
+	}
+
+	// Functions can not use the bindings outside of their body
+	pub(super) fn push_function(&mut self) {
+		self.scopes.push(vec![HashMap::new()]);
+	}
+
+	pub(super) fn pop_function(&mut self) {
+		self.pop_scope();
+		self.scopes.pop();
+	}
+
+	pub(super) fn declare_parameters(&mut self, parameters: &[(Token, Token)]) {
+		for (parameter, _) in parameters.iter() {
+			self.declare(parameter, true, true);
+		}
+	}
+
+	pub(super) fn declare(&mut self, identifier: &Token, parameter: bool, mutable: bool) {
+		let name = match &identifier.literal {
+			Some(Literal::String(name)) => name.clone(),
+			_ => return,
+		};
+
+		if let Some(shadowed) = self.find_binding(&name) {
+			let message = format!(
+				"{} shadows the declaration at line {} on column {}",
+				name, shadowed.identifier.line, shadowed.identifier.column
+			);
+			self.report(Rule::ShadowedBinding, message, identifier);
+		}
+
+		let scope = self.scopes.last_mut().unwrap().last_mut().unwrap();
+		scope.insert(
+			name,
+			LintBinding {
+				identifier: identifier.clone(),
+				parameter,
+				mutable,
+				used: false,
+				reassigned: false,
+			},
+		);
+	}
+
+	pub(super) fn use_binding(&mut self, identifier: &Token) {
+		if let Some(binding) = self.find_identifier(identifier) {
+			binding.used = true;
+		}
+	}
+
+	pub(super) fn reassign_binding(&mut self, identifier: &Token) {
+		if let Some(binding) = self.find_identifier(identifier) {
+			binding.reassigned = true;
+		}
+	}
+
+	fn find_identifier(&mut self, identifier: &Token) -> Option<&mut LintBinding> {
+		match &identifier.literal {
+			Some(Literal::String(name)) => self.find_binding(&name.clone()),
+			_ => None,
+		}
+	}
+
+	fn find_binding(&mut self, name: &str) -> Option<&mut LintBinding> {
+		self.scopes
+			.last_mut()?
+			.iter_mut()
+			.rev()
+			.find_map(|scope| scope.get_mut(name))
+	}
+}
@@ -0,0 +1,156 @@
+use crate::linter::{Linter, Rule};
+use crate::{Expression, Literal, Token, TokenType};
+
+impl Linter {
+	pub(super) fn lint_expression(&mut self, expression: &Expression) {
+		use Expression::*;
+
+		match expression {
+			Literal(_) => {}
+			Identifier(identifier) => self.use_binding(identifier),
+			Binary(left, operator, right) => {
+				self.lint_comparison(left, operator, right);
+				self.lint_expression(left);
+				self.lint_expression(right);
+			}
+			Unary(_, expression) | Grouping(_, expression, _) | Get(expression, _) => {
+				self.lint_expression(expression)
+			}
+			Assignment(identifier, operator, value) => {
+				if operator.r#type == TokenType::Assign {
+					self.lint_self_assignment(&Identifier(identifier.clone()), value, identifier);
+				} else {
+					self.use_binding(identifier);
+				}
+
+				self.lint_expression(value);
+				self.reassign_binding(identifier);
+			}
+			Set(target, operator, value) => {
+				if operator.r#type == TokenType::Assign {
+					self.lint_self_assignment(target, value, operator);
+				}
+
+				self.lint_expression(target);
+				self.lint_expression(value);
+
+				// Fields and elements can only be changed through var bindings
+				if let Some(identifier) = Linter::get_root(target) {
+					self.reassign_binding(identifier);
+				}
+			}
+			Call(function, _, arguments, _) => {
+				self.lint_expression(function);
+
+				for argument in arguments.iter() {
+					self.lint_expression(argument);
+				}
+
+				if let Get(object, method) = &**function {
+					if Linter::is_mutating(method) {
+						if let Some(identifier) = Linter::get_root(object) {
+							self.reassign_binding(identifier);
+						}
+					}
+				}
+			}
+			Range(left, _, right) | Index(left, _, right, _) => {
+				self.lint_expression(left);
+				self.lint_expression(right);
+			}
+			Match(_, subject, arms, default, _) => {
+				self.lint_expression(subject);
+				self.lint_arms(arms, Linter::lint_expression);
+
+				if let Some(default) = default {
+					self.lint_expression(default);
+				}
+			}
+			Array(_, expressions, _) | Template(_, expressions) => {
+				for expression in expressions.iter() {
+					self.lint_expression(expression);
+				}
+			}
+			Map(_, entries, _) => {
+				for (key, value) in entries.iter() {
+					self.lint_expression(key);
+					self.lint_expression(value);
+				}
+			}
+			Record(_, fields, _) => {
+				for (_, value) in fields.iter() {
+					self.lint_expression(value);
+				}
+			}
+			// Lambdas can use the bindings of the scope they are declared in
+			Lambda(_, parameters, _, body) => {
+				self.push_scope();
+				self.declare_parameters(parameters);
+				self.lint_statement(body);
+				self.pop_scope();
+			}
+		}
+	}
+
+	// a == true is the same as a, a != true is the same as !a
+	fn lint_comparison(&mut self, left: &Expression, operator: &Token, right: &Expression) {
+		if operator.r#type != TokenType::Equal && operator.r#type != TokenType::NotEqual {
+			return;
+		}
+
+		let value = match (left, right) {
+			(Expression::Literal(token), _) | (_, Expression::Literal(token)) => {
+				match token.literal {
+					Some(Literal::Boolean(value)) => value,
+					_ => return,
+				}
+			}
+			_ => return,
+		};
+
+		self.report(
+			Rule::BooleanComparison,
+			format!("Comparison to {} can be simplified", value),
+			operator,
+		);
+	}
+
+	fn lint_self_assignment(&mut self, target: &Expression, value: &Expression, token: &Token) {
+		if Linter::is_same_target(target, value) {
+			self.report(
+				Rule::SelfAssignment,
+				"Value is assigned to itself".to_string(),
+				token,
+			);
+		}
+	}
+
+	fn is_same_target(target: &Expression, value: &Expression) -> bool {
+		match (target, value) {
+			(Expression::Identifier(target), Expression::Identifier(value)) => {
+				target.literal == value.literal
+			}
+			(Expression::Get(target, a), Expression::Get(value, b)) => {
+				a.literal == b.literal && Linter::is_same_target(target, value)
+			}
+			(target, Expression::Grouping(_, value, _)) => Linter::is_same_target(target, value),
+			_ => false,
+		}
+	}
+
+	// Identifier of the binding that is changed by a field or element assignment
+	fn get_root(expression: &Expression) -> Option<&Token> {
+		match expression {
+			Expression::Identifier(identifier) => Some(identifier),
+			Expression::Get(object, _) | Expression::Index(object, ..) => Linter::get_root(object),
+			_ => None,
+		}
+	}
+
+	fn is_mutating(method: &Token) -> bool {
+		match &method.literal {
+			Some(Literal::String(name)) => matches!(name.as_str(), "push" | "pop" | "remove"),
+			_ => false,
+		}
+	}
+}
@@ -0,0 +1,5 @@
+mod bindings;
+
+mod expressions;
+
+mod statements;
@@ -0,0 +1,164 @@
+use crate::analyzer::Analyzer;
+use crate::linter::{Linter, Rule};
+use crate::{Expression, Literal, MatchArm, Statement, TokenType};
+
+impl Linter {
+	pub(crate) fn lint_statement(&mut self, statement: &Statement) {
+		use Statement::*;
+
+		match statement {
+			Expression(expression) => self.lint_expression(expression),
+			Declaration(mutability, _, expression) => {
+				let mutable = mutability.r#type == TokenType::Var;
+
+				match expression {
+					self::Expression::Assignment(identifier, _, value) => {
+						self.lint_expression(value);
+						self.declare(identifier, false, mutable);
+					}
+					self::Expression::Identifier(identifier) => {
+						self.declare(identifier, false, mutable)
+					}
+					expression => self.lint_expression(expression),
+				}
+			}
+			Body(_, statements, _) => {
+				self.push_scope();
+
+				for statement in statements.iter() {
+					self.lint_statement(statement);
+				}
+
+				self.pop_scope();
+			}
+			Function(_, _, _, parameters, body) => {
+				self.push_function();
+				self.declare_parameters(parameters);
+				self.lint_statement(body);
+				self.pop_function();
+			}
+			If(_, condition, true_branch, false_branch) => {
+				self.lint_condition(condition, false);
+				self.lint_statement(true_branch);
+
+				if let Some(false_branch) = false_branch {
+					self.lint_statement(false_branch);
+				}
+			}
+			While(_, condition, body) => {
+				self.lint_condition(condition, true);
+				self.lint_statement(body);
+			}
+			For(_, identifier, iterable, body) => {
+				self.lint_expression(iterable);
+				self.push_scope();
+
+				if let self::Expression::Identifier(identifier) = identifier {
+					self.declare(identifier, false, false);
+				}
+
+				self.lint_statement(body);
+				self.pop_scope();
+			}
+			Match(_, subject, arms, default, _) => {
+				self.lint_expression(subject);
+				self.lint_arms(arms, Linter::lint_statement);
+
+				if let Some(default) = default {
+					self.lint_statement(default);
+				}
+			}
+			Return(_, Some(expression)) => self.lint_expression(expression),
+			Loop(_, body) | Documentation(_, body) => self.lint_statement(body),
+			Return(_, None) | Comment(_) | Break(_) | Continue(_) | Record(..) | Enum(..) => {}
+		}
+	}
+
+	// Variant patterns bind the values of the variant in the scope of the arm
+	pub(super) fn lint_arms<T>(&mut self, arms: &[MatchArm<T>], lint_body: fn(&mut Linter, &T)) {
+		for arm in arms.iter() {
+			self.push_scope();
+
+			for pattern in arm.patterns.iter() {
+				match pattern {
+					Expression::Call(function, _, arguments, _) if self.is_variant(function) => {
+						for argument in arguments.iter() {
+							if let Expression::Identifier(identifier) = argument {
+								self.declare(identifier, false, false);
+							}
+						}
+					}
+					pattern => self.lint_expression(pattern),
+				}
+			}
+
+			if let Some(guard) = &arm.guard {
+				self.lint_expression(guard);
+			}
+
+			lint_body(self, &arm.body);
+			self.pop_scope();
+		}
+	}
+
+	fn is_variant(&self, function: &Expression) -> bool {
+		match function {
+			Expression::Get(object, _) => match &**object {
+				Expression::Identifier(identifier) => match &identifier.literal {
+					Some(Literal::String(name)) => self.enums.contains(name),
+					_ => false,
+				},
+				_ => false,
+			},
+			_ => false,
+		}
+	}
+
+	// Conditions that only use literals always take the same branch
+	fn lint_condition(&mut self, condition: &Expression, is_loop: bool) {
+		self.lint_expression(condition);
+
+		if !Linter::is_constant(condition) {
+			return;
+		}
+
+		let message = match (Linter::get_boolean(condition), is_loop) {
+			(Some(true), true) => "Condition is always true, use loop instead".to_string(),
+			(Some(value), _) => format!("Condition is always {}", value),
+			(None, _) => "Condition is constant".to_string(),
+		};
+
+		self.report(
+			Rule::ConstantCondition,
+			message,
+			Analyzer::get_token(condition),
+		);
+	}
+
+	fn is_constant(expression: &Expression) -> bool {
+		match expression {
+			Expression::Literal(_) => true,
+			Expression::Grouping(_, expression, _) | Expression::Unary(_, expression) => {
+				Linter::is_constant(expression)
+			}
+			Expression::Binary(left, _, right) => {
+				Linter::is_constant(left) && Linter::is_constant(right)
+			}
+			_ => false,
+		}
+	}
+
+	pub(super) fn get_boolean(expression: &Expression) -> Option<bool> {
+		match expression {
+			Expression::Literal(token) => match token.literal {
+				Some(Literal::Boolean(value)) => Some(value),
+				_ => None,
+			},
+			Expression::Grouping(_, expression, _) => Linter::get_boolean(expression),
+			Expression::Unary(operator, expression) if operator.r#type == TokenType::Not => {
+				Linter::get_boolean(expression).map(|value| !value)
+			}
+			_ => None,
+		}
+	}
+}
@@ -1,17 +1,65 @@
 use crate::ast::*;
 use crate::Error;
+use crate::ErrorCode;
 use crate::Result;
 use crate::Tokenizer;
 
 use core::iter::Peekable;
 
+// Error recovery continues at the keywords that start a statement
+const STATEMENT_KEYWORDS: &[TokenType] = &[
+	TokenType::Var,
+	TokenType::Val,
+	TokenType::Comment,
+	TokenType::DocComment,
+	TokenType::Function,
+	TokenType::Return,
+	TokenType::Loop,
+	TokenType::While,
+	TokenType::For,
+	TokenType::Break,
+	TokenType::Continue,
+	TokenType::If,
+	TokenType::Match,
+	TokenType::Type,
+	TokenType::Enum,
+];
+
 pub struct Parser<'a> {
 	pub(super) tokenizer: Peekable<Tokenizer<'a>>,
+	recovering: bool, // Syntax errors in bodies are collected instead of returned
+	errors: Vec<Error>,
 }
 
 impl<'a> Parser<'_> {
 	pub fn new(tokenizer: Peekable<Tokenizer<'a>>) -> Parser<'a> {
-		Parser { tokenizer }
+		Parser {
+			tokenizer,
+			recovering: false,
+			errors: Vec::new(),
+		}
+	}
+
+	// Parses the whole script, statements with syntax errors are left out of the partial tree
+	pub fn parse_all(mut self) -> (Vec<Statement>, Vec<Error>) {
+		self.recovering = true;
+
+		let mut statements = Vec::new();
+
+		while let Some(statement) = self.next() {
+			match statement {
+				Ok(statement) => statements.push(statement),
+				Err(error) => {
+					self.errors.push(error);
+					self.synchronize();
+
+					// Outside of a body a } can not be recovered from, it is skipped
+					self.next_if_token_type(TokenType::CloseCurlyBracket);
+				}
+			}
+		}
+
+		(statements, self.errors)
 	}
 }
 
@@ -33,27 +81,49 @@ impl Parser<'_> {
 		use TokenType::*;
 	
 		if let Some(token) = self.next_if_in_token_types(&[
-			Var, Val, Comment, Function, Return, Loop, While, For, Break, Continue, If,
+			Var,
+			Val,
+			Comment,
+			DocComment,
+			Function,
+			Return,
+			Loop,
+			While,
+			For,
+			Break,
+			Continue,
+			If,
+			Match,
+			Type,
+			Enum,
+			OpenCurlyBracket,
 		]) {
 			if let Ok(token) = token {
 				match token.r#type {
 					Val | Var => self.declaration(token),
 					Comment => Ok(Statement::Comment(token)),
+					DocComment => self.documentation(token),
 					Function => self.function(token),
-					Return => Ok(Statement::Return(self.expression_root().ok())),
+					Return => self.r#return(token),
 					Loop => self.r#loop(token),
 					While => self.r#while(token),
 					For => self.r#for(token),
-					Break => Ok(Statement::Break),
-					Continue => Ok(Statement::Continue),
+					Break => Ok(Statement::Break(token)),
+					Continue => Ok(Statement::Continue(token)),
 					If => self.r#if(token),
+					Match => self.r#match(token),
+					Type => self.record(token),
+					Enum => self.r#enum(token),
+					OpenCurlyBracket => self.block(token),
 					_ => self.create_statement_syntax_error(
+						ErrorCode::Syntax,
 						"statement not implemented please report issue".to_string(),
 						token,
 					),
 				}
 			} else {
 				self.create_statement_syntax_error(
+					ErrorCode::Syntax,
 					"Invalid token found".to_string(),
 					token.unwrap(),
 				)
@@ -64,9 +134,45 @@ impl Parser<'_> {
 	}
 }
 
+impl Parser<'_> {
+	// The value of a return is left out when the next token can not start an expression, like a } or the
+	// keyword of the next statement
+	fn r#return(&mut self, token: Token) -> Result<Statement> {
+		use TokenType::*;
+
+		if self.peek_in_token_types(&[
+			CloseCurlyBracket,
+			Separator,
+			Var,
+			Val,
+			DocComment,
+			Function,
+			Return,
+			Loop,
+			While,
+			For,
+			Break,
+			Continue,
+			If,
+			Else,
+			Type,
+			Enum,
+		]) || self.tokenizer.peek().is_none()
+		{
+			return Ok(Statement::Return(token, None));
+		}
+
+		Ok(Statement::Return(token, Some(self.expression_root()?)))
+	}
+}
+
 // Helper functions for iterating trough tokens
 impl Parser<'_> {
 	pub(super) fn peek_in_token_types(&mut self, types: &[TokenType]) -> bool {
+		if !types.contains(&TokenType::Comment) {
+			self.skip_comments();
+		}
+
 		match self.tokenizer.peek() {
 			Some(&Ok(ref token)) => types.contains(&token.r#type),
 			_ => false,
@@ -82,6 +188,10 @@ impl Parser<'_> {
 	}
 
 	pub(super) fn peek_token_type(&mut self, token_type: TokenType) -> bool {
+		if token_type != TokenType::Comment {
+			self.skip_comments();
+		}
+
 		match self.tokenizer.peek() {
 			Some(&Ok(ref token)) => token.r#type == token_type,
 			_ => false,
@@ -96,17 +206,153 @@ impl Parser<'_> {
 		}
 	}
 
+	// Comments are only statements in the place of a statement, comments inside of a statement like
+	// 1 /* one */ + 2 are skipped
+	fn skip_comments(&mut self) {
+		while let Some(Ok(Token {
+			r#type: TokenType::Comment,
+			..
+		})) = self.tokenizer.peek()
+		{
+			self.tokenizer.next();
+		}
+	}
+
 	pub(super) fn next_if_specifier(&mut self) -> Option<Result<Token>> {
 		if let Some(Ok(specifier)) = self.next_if_token_type(TokenType::Specifier) {
-			if let Some(Ok(r#type)) = self.next_if_token_type(TokenType::Identifier) {
-				return Some(Ok(r#type));
+			return Some(self.type_specification(specifier));
+		}
+		None
+	}
+
+	// Generic types like Array<Int> are merged into a single identifier token
+	pub(super) fn type_specification(&mut self, token: Token) -> Result<Token> {
+		let r#type = match self.next_if_token_type(TokenType::Identifier) {
+			Some(Ok(r#type)) => r#type,
+			_ => {
+				return self.create_token_syntax_error(
+					ErrorCode::ExpectedType,
+					"Expected type".to_string(),
+					token,
+				)
+			}
+		};
+
+		if let Some(Ok(lesser)) = self.next_if_token_type(TokenType::Lesser) {
+			let mut arguments: Vec<String> = Vec::new();
+
+			loop {
+				match self.type_specification(lesser.clone())?.literal {
+					Some(Literal::String(argument)) => arguments.push(argument),
+					_ => {
+						return self.create_token_syntax_error(
+							ErrorCode::ExpectedType,
+							"Expected type".to_string(),
+							lesser,
+						)
+					}
+				}
+
+				if self.next_if_token_type(TokenType::Separator).is_none() {
+					break;
+				}
+			}
+
+			let greater = match self.next_if_token_type(TokenType::Greater) {
+				Some(Ok(greater)) => greater,
+				_ => {
+					return self.create_token_syntax_error(
+						ErrorCode::Syntax,
+						"Expected >".to_string(),
+						lesser,
+					)
+				}
+			};
+
+			let name = match &r#type.literal {
+				Some(Literal::String(name)) => format!("{}<{}>", name, arguments.join(", ")),
+				_ => {
+					return self.create_token_syntax_error(
+						ErrorCode::ExpectedType,
+						"Expected type".to_string(),
+						r#type,
+					)
+				}
+			};
+			let length = if greater.line == r#type.line {
+				greater.column + greater.length - r#type.column
 			} else {
-				return Some(
-					self.create_token_syntax_error("Expected type".to_string(), specifier),
-				);
+				r#type.length
+			};
+
+			return Ok(Token::new(
+				TokenType::Identifier,
+				Some(Literal::String(name)),
+				r#type.line,
+				r#type.column,
+				length,
+				r#type.offset,
+			));
+		}
+
+		Ok(r#type)
+	}
+}
+
+// Error recovery
+impl Parser<'_> {
+	pub(super) fn recover(&mut self, error: Error) -> Result<()> {
+		if !self.recovering {
+			return Err(error);
+		}
+
+		self.errors.push(error);
+		self.synchronize();
+
+		Ok(())
+	}
+
+	// Unexpected tokens are consumed, unless recovery can continue at them because they start a statement
+	// or close a body
+	pub(super) fn next_unexpected(&mut self) -> Option<Result<Token>> {
+		match self.tokenizer.peek() {
+			Some(Ok(token))
+				if token.r#type == TokenType::CloseCurlyBracket
+					|| STATEMENT_KEYWORDS.contains(&token.r#type) =>
+			{
+				Some(Ok(token.clone()))
 			}
+			_ => self.tokenizer.next(),
+		}
+	}
+
+	// Skips tokens up to the next statement keyword or the } that closes the current body
+	fn synchronize(&mut self) {
+		use TokenType::*;
+
+		let mut depth = 0;
+
+		loop {
+			match self.tokenizer.peek() {
+				Some(Ok(token)) => match token.r#type {
+					OpenCurlyBracket => depth += 1,
+					CloseCurlyBracket if depth == 0 => return,
+					CloseCurlyBracket => depth -= 1,
+					ref r#type if depth == 0 && STATEMENT_KEYWORDS.contains(r#type) => return,
+					_ => {}
+				},
+				Some(Err(_)) => {
+					if let Some(Err(error)) = self.tokenizer.next() {
+						self.errors.push(error);
+					}
+
+					continue;
+				}
+				None => return,
+			}
+
+			self.tokenizer.next();
 		}
-		None
 	}
 }
 
@@ -114,25 +360,28 @@ impl Parser<'_> {
 impl Parser<'_> {
 	pub(super) fn create_token_syntax_error(
 		&mut self,
+		code: ErrorCode,
 		message: String,
 		token: Token,
 	) -> Result<Token> {
-		Err(Error::new_parser_error(message, token))
+		Err(Error::new_parser_error(message, token).with_code(code))
 	}
 
 	pub(super) fn create_expression_syntax_error(
 		&mut self,
+		code: ErrorCode,
 		message: String,
 		token: Token,
 	) -> Result<Expression> {
-		Err(Error::new_parser_error(message, token))
+		Err(Error::new_parser_error(message, token).with_code(code))
 	}
 
 	pub(super) fn create_statement_syntax_error(
 		&mut self,
+		code: ErrorCode,
 		message: String,
 		token: Token,
 	) -> Result<Statement> {
-		Err(Error::new_parser_error(message, token))
+		Err(Error::new_parser_error(message, token).with_code(code))
 	}
 }
@@ -1,6 +1,9 @@
 use crate::ast::*;
 use crate::parser::Parser;
-use crate::Result;
+use crate::{Error, ErrorCode, ErrorType, Result};
+
+// Subject, arms, else arm and the closing curly bracket of a match
+type MatchParts<T> = (Expression, Vec<MatchArm<T>>, Option<T>, Token);
 
 impl Parser<'_> {
 	pub(crate) fn r#if(&mut self, token: Token) -> Result<Statement> {
@@ -16,6 +19,7 @@ impl Parser<'_> {
 					if let Some(Ok(next_else)) = self.next_if_token_type(TokenType::Else) {
 						match self.r#else(next_else) {
 							Ok(statement) => Ok(Statement::If(
+								token,
 								expression,
 								Box::new(body?),
 								Some(Box::new(statement)),
@@ -23,16 +27,28 @@ impl Parser<'_> {
 							Err(error) => Err(error),
 						}
 					} else {
-						Ok(Statement::If(expression, Box::new(body?), None))
+						Ok(Statement::If(token, expression, Box::new(body?), None))
 					}
 				} else {
-					self.create_statement_syntax_error("Expected body".to_string(), token)
+					self.create_statement_syntax_error(
+						ErrorCode::Syntax,
+						"Expected body".to_string(),
+						token,
+					)
 				}
 			} else {
-				self.create_statement_syntax_error("Expected close parenthesis".to_string(), token)
+				self.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected close parenthesis".to_string(),
+					token,
+				)
 			}
 		} else {
-			self.create_statement_syntax_error("Expected open parenthesis".to_string(), token)
+			self.create_statement_syntax_error(
+				ErrorCode::Syntax,
+				"Expected open parenthesis".to_string(),
+				token,
+			)
 		}
 	}
 
@@ -40,7 +56,141 @@ impl Parser<'_> {
 		if let Some(Ok(open_curly_bracket)) = self.next_if_token_type(TokenType::OpenCurlyBracket) {
 			self.body(open_curly_bracket)
 		} else {
-			self.create_statement_syntax_error("Expected body".to_string(), token)
+			self.create_statement_syntax_error(
+				ErrorCode::Syntax,
+				"Expected body".to_string(),
+				token,
+			)
+		}
+	}
+
+	pub(crate) fn r#match(&mut self, token: Token) -> Result<Statement> {
+		let (subject, arms, default, close_curly_bracket) =
+			self.match_arms(&token, Parser::match_body)?;
+
+		Ok(Statement::Match(
+			token,
+			subject,
+			arms,
+			default.map(Box::new),
+			close_curly_bracket,
+		))
+	}
+
+	pub(crate) fn match_expression(&mut self, token: Token) -> Result<Expression> {
+		let (subject, arms, default, close_curly_bracket) =
+			self.match_arms(&token, Parser::expression_root)?;
+
+		Ok(Expression::Match(
+			token,
+			Box::new(subject),
+			arms,
+			default.map(Box::new),
+			close_curly_bracket,
+		))
+	}
+
+	fn match_body(&mut self) -> Result<Statement> {
+		if let Some(Ok(open_curly_bracket)) = self.next_if_token_type(TokenType::OpenCurlyBracket) {
+			return self.body(open_curly_bracket);
+		}
+
+		match self.next() {
+			Some(result) => result,
+			None => Err(
+				Error::new(ErrorType::Eof, "Unexpected end of file".to_string())
+					.with_code(ErrorCode::UnexpectedEndOfFile),
+			),
+		}
+	}
+
+	fn match_arms<T>(
+		&mut self,
+		token: &Token,
+		body: fn(&mut Self) -> Result<T>,
+	) -> Result<MatchParts<T>> {
+		self.expect_match_token(
+			TokenType::OpenParenthesis,
+			"Expected open parenthesis",
+			token,
+		)?;
+
+		let subject = self.expression_root()?;
+
+		self.expect_match_token(
+			TokenType::CloseParenthesis,
+			"Expected close parenthesis",
+			token,
+		)?;
+
+		self.expect_match_token(TokenType::OpenCurlyBracket, "Expected match body", token)?;
+
+		let mut arms: Vec<MatchArm<T>> = Vec::new();
+
+		loop {
+			if self.next_if_token_type(TokenType::Else).is_some() {
+				self.expect_match_token(TokenType::Arrow, "Expected arrow", token)?;
+				let default = body(self)?;
+				self.next_if_token_type(TokenType::Separator);
+				let close_curly_bracket = self.expect_match_token(
+					TokenType::CloseCurlyBracket,
+					"Expected end of match after else arm",
+					token,
+				)?;
+
+				return Ok((subject, arms, Some(default), close_curly_bracket));
+			}
+
+			// Matches without an else arm are checked for exhaustiveness by the analyzer
+			if let Some(close_curly_bracket) = self.next_if_token_type(TokenType::CloseCurlyBracket)
+			{
+				let close_curly_bracket = close_curly_bracket?;
+
+				if arms.is_empty() {
+					return Err(Error::new_parser_error(
+						"Expected match arm".to_string(),
+						token.clone(),
+					));
+				}
+
+				return Ok((subject, arms, None, close_curly_bracket));
+			}
+
+			// Patterns
+			let mut patterns: Vec<Expression> = vec![self.range()?];
+
+			while self.next_if_token_type(TokenType::Separator).is_some() {
+				patterns.push(self.range()?);
+			}
+
+			// Guard
+			let guard = match self.next_if_token_type(TokenType::If) {
+				Some(_) => Some(self.expression_root()?),
+				None => None,
+			};
+
+			self.expect_match_token(TokenType::Arrow, "Expected arrow", token)?;
+			let body = body(self)?;
+			self.next_if_token_type(TokenType::Separator);
+
+			arms.push(MatchArm {
+				patterns,
+				guard,
+				body,
+			});
+		}
+	}
+
+	fn expect_match_token(
+		&mut self,
+		token_type: TokenType,
+		message: &str,
+		token: &Token,
+	) -> Result<Token> {
+		match self.next_if_token_type(token_type) {
+			Some(Ok(next)) => Ok(next),
+			Some(Err(error)) => Err(error),
+			None => Err(Error::new_parser_error(message.to_string(), token.clone())),
 		}
 	}
 }
@@ -1,21 +1,72 @@
 use crate::ast::*;
+use crate::ErrorCode;
 use crate::Result;
 use crate::parser::Parser;
 
 impl Parser<'_>{
 	pub(crate) fn body(&mut self, open_curly_bracket: Token) -> Result<Statement> {
-		let mut statements: Vec<Statement> = Vec::new();
+		self.body_from(open_curly_bracket, Vec::new())
+	}
+
+	// Statements starting with { are bodies unless the first expression is followed by a :
+	pub(crate) fn block(&mut self, open_curly_bracket: Token) -> Result<Statement> {
+		use TokenType::*;
+
+		if self.peek_in_token_types(&[
+			CloseCurlyBracket,
+			OpenCurlyBracket,
+			Var,
+			Val,
+			Comment,
+			DocComment,
+			Function,
+			Return,
+			Loop,
+			While,
+			For,
+			Break,
+			Continue,
+			If,
+			Match,
+		]) {
+			return self.body(open_curly_bracket);
+		}
+
+		let expression = self.expression_root()?;
+
+		if let Some(specifier) = self.next_if_token_type(Specifier) {
+			specifier?;
+			let value = self.expression_root()?;
+
+			return Ok(Statement::Expression(self.map_from(
+				open_curly_bracket,
+				vec![(expression, value)],
+			)?));
+		}
+
+		self.body_from(open_curly_bracket, vec![Statement::Expression(expression)])
+	}
 
-		while let Some(Ok(token)) = self.tokenizer.peek() {
-			if token.r#type == TokenType::CloseCurlyBracket {
-				self.tokenizer.next();
-				return Ok(Statement::Body(statements));
+	fn body_from(
+		&mut self,
+		open_curly_bracket: Token,
+		mut statements: Vec<Statement>,
+	) -> Result<Statement> {
+		while let Some(token) = self.tokenizer.peek() {
+			if matches!(token, Ok(token) if token.r#type == TokenType::CloseCurlyBracket) {
+				let close_curly_bracket = self.tokenizer.next().unwrap()?;
+				return Ok(Statement::Body(
+					open_curly_bracket,
+					statements,
+					close_curly_bracket,
+				));
 			} else {
 				match self.next() {
 					Some(Ok(next)) => statements.push(next),
-					Some(Err(err)) => return Err(err),
+					Some(Err(err)) => self.recover(err)?,
 					None => {
 						return self.create_statement_syntax_error(
+							ErrorCode::ExpectedEndOfBody,
 							"Expected end of body".to_string(),
 							open_curly_bracket,
 						)
@@ -24,6 +75,10 @@ impl Parser<'_>{
 			}
 		}
 
-		self.create_statement_syntax_error("Expected end of body".to_string(), open_curly_bracket)
+		self.create_statement_syntax_error(
+			ErrorCode::ExpectedEndOfBody,
+			"Expected end of body".to_string(),
+			open_curly_bracket,
+		)
 	}
 }
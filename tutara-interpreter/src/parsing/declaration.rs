@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::ErrorCode;
 use crate::Result;
 use crate::parser::Parser;
 
@@ -17,7 +18,11 @@ impl Parser<'_>{
 				self.expression_root()?,
 			))
 		} else {
-			self.create_statement_syntax_error("Expected variable name".to_string(), token)
+			self.create_statement_syntax_error(
+				ErrorCode::ExpectedIdentifier,
+				"Expected variable name".to_string(),
+				token,
+			)
 		}
 	}
 }
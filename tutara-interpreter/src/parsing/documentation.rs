@@ -0,0 +1,37 @@
+use crate::ast::*;
+use crate::parser::Parser;
+use crate::ErrorCode;
+use crate::Result;
+
+impl Parser<'_> {
+	// Consecutive doc comments document the declaration that follows them
+	pub(crate) fn documentation(&mut self, token: Token) -> Result<Statement> {
+		use TokenType::*;
+
+		let mut comments = vec![token];
+
+		while let Some(Ok(comment)) = self.next_if_token_type(DocComment) {
+			comments.push(comment);
+		}
+
+		let declaration = match self.next_if_in_token_types(&[Val, Var, Function, Type, Enum]) {
+			Some(Ok(declaration)) => declaration,
+			_ => {
+				return self.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected declaration after doc comment".to_string(),
+					comments.pop().unwrap(),
+				)
+			}
+		};
+
+		let statement = match declaration.r#type {
+			Val | Var => self.declaration(declaration),
+			Function => self.function(declaration),
+			Type => self.record(declaration),
+			_ => self.r#enum(declaration),
+		}?;
+
+		Ok(Statement::Documentation(comments, Box::new(statement)))
+	}
+}
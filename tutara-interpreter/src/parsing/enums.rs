@@ -0,0 +1,95 @@
+use crate::ast::*;
+use crate::parser::Parser;
+use crate::ErrorCode;
+use crate::Result;
+
+impl Parser<'_> {
+	pub(crate) fn r#enum(&mut self, token: Token) -> Result<Statement> {
+		let identifier = match self.next_if_token_type(TokenType::Identifier) {
+			Some(Ok(identifier)) => identifier,
+			_ => {
+				return self.create_statement_syntax_error(
+					ErrorCode::ExpectedIdentifier,
+					"Expected identifier".to_string(),
+					token,
+				)
+			}
+		};
+
+		let open_curly_bracket = match self.next_if_token_type(TokenType::OpenCurlyBracket) {
+			Some(Ok(open_curly_bracket)) => open_curly_bracket,
+			_ => {
+				return self.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected enum body".to_string(),
+					identifier,
+				)
+			}
+		};
+
+		let mut variants: Vec<(Token, Vec<Token>)> = Vec::new();
+
+		while !self.peek_token_type(TokenType::CloseCurlyBracket) {
+			let variant = match self.next_if_token_type(TokenType::Identifier) {
+				Some(Ok(variant)) => variant,
+				_ => {
+					return self.create_statement_syntax_error(
+						ErrorCode::Syntax,
+						"Expected variant".to_string(),
+						open_curly_bracket,
+					)
+				}
+			};
+
+			let mut types: Vec<Token> = Vec::new();
+
+			if let Some(Ok(open_parenthesis)) = self.next_if_token_type(TokenType::OpenParenthesis)
+			{
+				while !self.peek_token_type(TokenType::CloseParenthesis) {
+					types.push(self.type_specification(open_parenthesis.clone())?);
+
+					if self.next_if_token_type(TokenType::Separator).is_none() {
+						break;
+					}
+				}
+
+				if self
+					.next_if_token_type(TokenType::CloseParenthesis)
+					.is_none()
+				{
+					return self.create_statement_syntax_error(
+						ErrorCode::Syntax,
+						"Expected closing parenthesis".to_string(),
+						open_parenthesis,
+					);
+				}
+			}
+
+			variants.push((variant, types));
+
+			if self.next_if_token_type(TokenType::Separator).is_none() {
+				break;
+			}
+		}
+
+		match self.next_if_token_type(TokenType::CloseCurlyBracket) {
+			Some(Ok(_close_curly_bracket)) if variants.is_empty() => self
+				.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected variant".to_string(),
+					open_curly_bracket,
+				),
+			Some(Ok(close_curly_bracket)) => Ok(Statement::Enum(
+				token,
+				identifier,
+				variants,
+				close_curly_bracket,
+			)),
+			_ => self.create_statement_syntax_error(
+				ErrorCode::Syntax,
+				"Incorrectly formatted enum".to_string(),
+				open_curly_bracket,
+			),
+		}
+	}
+}
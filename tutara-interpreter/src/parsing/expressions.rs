@@ -1,8 +1,10 @@
 use crate::ast::*;
 use crate::parser::Parser;
 use crate::Error;
+use crate::ErrorCode;
 use crate::ErrorType;
 use crate::Result;
+use crate::Tokenizer;
 
 impl Parser<'_> {
 	pub(crate) fn expression(&mut self) -> Result<Statement> {
@@ -36,7 +38,16 @@ impl Parser<'_> {
 					token,
 					Box::new(self.assignment()?),
 				)),
-				_ => self.create_expression_syntax_error("Failed on assignment".to_string(), token),
+				Expression::Index(..) | Expression::Get(..) => Ok(Expression::Set(
+					Box::new(expression),
+					token,
+					Box::new(self.assignment()?),
+				)),
+				_ => self.create_expression_syntax_error(
+					ErrorCode::Syntax,
+					"Failed on assignment".to_string(),
+					token,
+				),
 			};
 		}
 
@@ -67,7 +78,7 @@ impl Parser<'_> {
 	pub(super) fn comparison(&mut self) -> Result<Expression> {
 		use TokenType::*;
 
-		let mut expression: Expression = self.addition_and_subtraction()?;
+		let mut expression: Expression = self.range()?;
 
 		while let Some(Ok(token)) = self.next_if_in_token_types(&[
 			Equal,
@@ -77,11 +88,23 @@ impl Parser<'_> {
 			Lesser,
 			LesserOrEqual,
 		]) {
-			expression = Expression::Binary(
+			expression = Expression::Binary(Box::new(expression), token, Box::new(self.range()?));
+		}
+
+		Ok(expression)
+	}
+
+	pub(super) fn range(&mut self) -> Result<Expression> {
+		let expression = self.addition_and_subtraction()?;
+
+		if let Some(Ok(token)) =
+			self.next_if_in_token_types(&[TokenType::Range, TokenType::RangeInclusive])
+		{
+			return Ok(Expression::Range(
 				Box::new(expression),
 				token,
 				Box::new(self.addition_and_subtraction()?),
-			);
+			));
 		}
 
 		Ok(expression)
@@ -127,22 +150,59 @@ impl Parser<'_> {
 		if let Some(Ok(token)) =
 			self.next_if_in_token_types(&[TokenType::Minus, TokenType::Plus, TokenType::Not])
 		{
+			if token.r#type == TokenType::Minus {
+				if let Some(literal) = self.next_if_minimum_integer() {
+					return Ok(Expression::Literal(Parser::negative_literal(token, literal)));
+				}
+			}
+
 			return Ok(Expression::Unary(token, Box::new(self.unary()?)));
 		}
 
 		self.get()
 	}
 
+	// -9223372036854775808 is a single literal, as its digits do not fit in an Int without the minus
+	fn next_if_minimum_integer(&mut self) -> Option<Token> {
+		match self.tokenizer.peek() {
+			Some(Ok(Token {
+				literal: Some(Literal::Integer(i64::MIN)),
+				..
+			})) => self.tokenizer.next()?.ok(),
+			_ => None,
+		}
+	}
+
+	fn negative_literal(minus: Token, literal: Token) -> Token {
+		let length = match minus.line == literal.line {
+			true => literal.end_column - minus.column,
+			false => literal.length,
+		};
+
+		Token::new(
+			TokenType::Integer,
+			literal.literal,
+			minus.line,
+			minus.column,
+			length,
+			minus.offset,
+		)
+		.with_end(literal.end_line, literal.end_column)
+	}
+
 	pub(super) fn get(&mut self) -> Result<Expression> {
 		let mut expression = self.terms()?;
 
 		loop {
-			expression = match self
-				.next_if_in_token_types(&[TokenType::OpenParenthesis, TokenType::Dot])
-			{
+			expression = match self.next_if_in_token_types(&[
+				TokenType::OpenParenthesis,
+				TokenType::OpenSquareBracket,
+				TokenType::Dot,
+			]) {
 				Some(Err(error)) => return Err(error),
 				Some(Ok(token)) => match token.r#type {
 					TokenType::OpenParenthesis => self.call(expression, token)?,
+					TokenType::OpenSquareBracket => self.index(expression, token)?,
 					TokenType::Dot => {
 						if let Some(Ok(identifier)) = self.next_if_token_type(TokenType::Identifier)
 						{
@@ -152,10 +212,11 @@ impl Parser<'_> {
 							{
 								self.call(get, open_parenthesis)?
 							} else {
-								return Ok(get);
+								get
 							}
 						} else {
 							return self.create_expression_syntax_error(
+								ErrorCode::ExpectedIdentifier,
 								"expected identifier".to_string(),
 								token,
 							);
@@ -193,40 +254,267 @@ impl Parser<'_> {
 			))
 		} else {
 			self.create_expression_syntax_error(
+				ErrorCode::Syntax,
 				"Incorrectly formatted parameters".to_string(),
 				open_parenthesis,
 			)
 		}
 	}
 
+	pub(super) fn index(
+		&mut self,
+		indexed: Expression,
+		open_square_bracket: Token,
+	) -> Result<Expression> {
+		let index = self.expression_root()?;
+
+		if let Some(Ok(close_square_bracket)) =
+			self.next_if_token_type(TokenType::CloseSquareBracket)
+		{
+			Ok(Expression::Index(
+				Box::new(indexed),
+				open_square_bracket,
+				Box::new(index),
+				close_square_bracket,
+			))
+		} else {
+			self.create_expression_syntax_error(
+				ErrorCode::Syntax,
+				"Expected closing square bracket".to_string(),
+				open_square_bracket,
+			)
+		}
+	}
+
+	pub(super) fn array(&mut self, open_square_bracket: Token) -> Result<Expression> {
+		let mut elements: Vec<Expression> = Vec::new();
+
+		while !self.peek_token_type(TokenType::CloseSquareBracket) {
+			elements.push(self.expression_root()?);
+			match self.next_if_token_type(TokenType::Separator) {
+				Some(result) => {
+					result?;
+				}
+				None => break,
+			};
+		}
+
+		if let Some(Ok(close_square_bracket)) =
+			self.next_if_token_type(TokenType::CloseSquareBracket)
+		{
+			Ok(Expression::Array(
+				open_square_bracket,
+				elements,
+				close_square_bracket,
+			))
+		} else {
+			self.create_expression_syntax_error(
+				ErrorCode::Syntax,
+				"Incorrectly formatted array".to_string(),
+				open_square_bracket,
+			)
+		}
+	}
+
+	pub(super) fn map(&mut self, open_curly_bracket: Token) -> Result<Expression> {
+		self.map_from(open_curly_bracket, Vec::new())
+	}
+
+	pub(super) fn map_from(
+		&mut self,
+		open_curly_bracket: Token,
+		mut entries: Vec<(Expression, Expression)>,
+	) -> Result<Expression> {
+		while !self.peek_token_type(TokenType::CloseCurlyBracket) {
+			if !entries.is_empty() {
+				match self.next_if_token_type(TokenType::Separator) {
+					Some(result) => {
+						result?;
+					}
+					None => break,
+				};
+
+				if self.peek_token_type(TokenType::CloseCurlyBracket) {
+					break;
+				}
+			}
+
+			let key = self.expression_root()?;
+
+			if self.next_if_token_type(TokenType::Specifier).is_none() {
+				return self.create_expression_syntax_error(
+					ErrorCode::Syntax,
+					"Expected : after map key".to_string(),
+					open_curly_bracket,
+				);
+			}
+
+			entries.push((key, self.expression_root()?));
+		}
+
+		if let Some(Ok(close_curly_bracket)) = self.next_if_token_type(TokenType::CloseCurlyBracket)
+		{
+			Ok(Expression::Map(
+				open_curly_bracket,
+				entries,
+				close_curly_bracket,
+			))
+		} else {
+			self.create_expression_syntax_error(
+				ErrorCode::Syntax,
+				"Incorrectly formatted map".to_string(),
+				open_curly_bracket,
+			)
+		}
+	}
+
+	// The expressions of a template are parsed on their own, the tokens keep their position in the file
+	pub(super) fn template(&mut self, token: Token) -> Result<Expression> {
+		let parts = match &token.literal {
+			Some(Literal::Template(parts)) => parts.clone(),
+			_ => {
+				return self.create_expression_syntax_error(
+					ErrorCode::Syntax,
+					"Invalid template".to_string(),
+					token,
+				)
+			}
+		};
+		let mut expressions: Vec<Expression> = Vec::new();
+
+		for part in parts {
+			match part {
+				TemplatePart::Text(text) => expressions.push(Expression::Literal(Token::new(
+					TokenType::String,
+					Some(Literal::String(text)),
+					token.line,
+					token.column,
+					token.length,
+					token.offset,
+				))),
+				TemplatePart::Expression(source, line, column, offset) => {
+					let mut parser =
+						Parser::new(Tokenizer::new_at(&source, line, column, offset).peekable());
+
+					match parser.expression_root() {
+						Ok(expression) => expressions.push(expression),
+						Err(Error {
+							r#type: ErrorType::Eof,
+							..
+						}) => {
+							return Err(Error::new_lexical_error(
+								"Unexpected end of interpolation".to_string(),
+								line,
+								column + source.chars().count() as u32,
+								1,
+							)
+							.with_code(ErrorCode::InvalidInterpolation))
+						}
+						Err(err) => return Err(err),
+					}
+
+					match parser.tokenizer.next() {
+						Some(Ok(next)) => {
+							return self.create_expression_syntax_error(
+								ErrorCode::UnexpectedToken,
+								"Unexpected token in interpolation".to_string(),
+								next,
+							)
+						}
+						Some(Err(err)) => return Err(err),
+						None => {}
+					}
+				}
+			}
+		}
+
+		Ok(Expression::Template(token, expressions))
+	}
+
 	pub(crate) fn terms(&mut self) -> Result<Expression> {
 		use TokenType::*;
 
 		if let Some(Ok(token)) = self.next_if_token_type(Identifier) {
+			// Type names start with an uppercase letter which keeps constructions apart from blocks
+			let is_type_name = match &token.literal {
+				Some(Literal::String(name)) => name.starts_with(char::is_uppercase),
+				_ => false,
+			};
+
+			if is_type_name {
+				if let Some(Ok(open_curly_bracket)) = self.next_if_token_type(OpenCurlyBracket) {
+					return self.record_construction(token, open_curly_bracket);
+				}
+			}
+
 			return Ok(Expression::Identifier(token));
 		}
 
-		if let Some(Ok(token)) = self.next_if_in_token_types(&[String, Integer, Boolean]) {
+		if let Some(Ok(token)) = self.next_if_in_token_types(&[String, Integer, Float, Boolean]) {
+			// Without a unary minus in front of them the digits of i64::MIN are out of range
+			if token.literal == Some(Literal::Integer(i64::MIN)) {
+				return Err(Error::new_lexical_error(
+					"Int literal is out of range".to_string(),
+					token.line,
+					token.column,
+					token.length,
+				)
+				.with_code(ErrorCode::IntegerOutOfRange));
+			}
+
 			return Ok(Expression::Literal(token));
 		}
 
-		if let Some(Ok(_token)) = self.next_if_token_type(OpenParenthesis) {
+		if let Some(Ok(token)) = self.next_if_token_type(Template) {
+			return self.template(token);
+		}
+
+		if let Some(Ok(token)) = self.next_if_token_type(Match) {
+			return self.match_expression(token);
+		}
+
+		if let Some(Ok(token)) = self.next_if_token_type(OpenSquareBracket) {
+			return self.array(token);
+		}
+
+		if let Some(Ok(token)) = self.next_if_token_type(OpenCurlyBracket) {
+			return self.map(token);
+		}
+
+		if let Some(Ok(open_parenthesis)) = self.next_if_token_type(OpenParenthesis) {
+			// Lambdas start like a grouping, (a: Int) -> a or () -> 1
+			if self.peek_token_type(CloseParenthesis) {
+				return self.lambda(open_parenthesis, None);
+			}
+
 			let expression = self.assignment()?;
 
-			if let Some(Ok(_next)) = self.next_if_token_type(CloseParenthesis) {
-				return Ok(Expression::Grouping(Box::new(expression)));
+			if let Expression::Identifier(identifier) = &expression {
+				if self.peek_token_type(Specifier) {
+					return self.lambda(open_parenthesis, Some(identifier.clone()));
+				}
 			}
-		}
 
-		match self.tokenizer.next() {
-			Some(Ok(next)) => {
-				self.create_expression_syntax_error("Unexpected token".to_string(), next)
+			if let Some(Ok(close_parenthesis)) = self.next_if_token_type(CloseParenthesis) {
+				return Ok(Expression::Grouping(
+					open_parenthesis,
+					Box::new(expression),
+					close_parenthesis,
+				));
 			}
+		}
+
+		match self.next_unexpected() {
+			Some(Ok(next)) => self.create_expression_syntax_error(
+				ErrorCode::UnexpectedToken,
+				"Unexpected token".to_string(),
+				next,
+			),
 			Some(Err(err)) => Err(err),
-			None => Err(Error::new(
-				ErrorType::Eof,
-				"Unexpected end of file".to_string(),
-			)),
+			None => Err(
+				Error::new(ErrorType::Eof, "Unexpected end of file".to_string())
+					.with_code(ErrorCode::UnexpectedEndOfFile),
+			),
 		}
 	}
 }
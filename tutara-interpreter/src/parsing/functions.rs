@@ -1,6 +1,7 @@
 use crate::ast::*;
 use crate::parser::Parser;
 use crate::Error;
+use crate::ErrorCode;
 use crate::Result;
 
 impl Parser<'_> {
@@ -28,6 +29,7 @@ impl Parser<'_> {
 					Some(Ok(_close_parenthesis)) => {}
 					_ => {
 						return self.create_statement_syntax_error(
+							ErrorCode::Syntax,
 							"Expected closing parenthesis".to_string(),
 							open_parenthesis,
 						)
@@ -40,6 +42,7 @@ impl Parser<'_> {
 			{
 				match self.body(open_curly_bracket) {
 					Ok(body) => Ok(Statement::Function(
+						token,
 						type_specification,
 						identifier,
 						parameters,
@@ -48,42 +51,111 @@ impl Parser<'_> {
 					Err(error) => Err(error),
 				}
 			} else {
-				self.create_statement_syntax_error("Expected function body".to_string(), identifier)
+				self.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected function body".to_string(),
+					identifier,
+				)
 			}
 		} else {
-			self.create_statement_syntax_error("Expected identifier".to_string(), token)
+			self.create_statement_syntax_error(
+				ErrorCode::ExpectedIdentifier,
+				"Expected identifier".to_string(),
+				token,
+			)
+		}
+	}
+
+	// The first parameter of a lambda is already read to tell it apart from a grouping
+	pub(crate) fn lambda(
+		&mut self,
+		open_parenthesis: Token,
+		first: Option<Token>,
+	) -> Result<Expression> {
+		let mut parameters: Vec<(Token, Token)> = Vec::new();
+
+		if let Some(identifier) = first {
+			parameters.push(self.parameter_from(identifier)?);
 		}
+
+		while let Some(Ok(token)) = self.tokenizer.peek() {
+			if token.r#type == TokenType::CloseParenthesis {
+				break;
+			} else {
+				parameters.push(self.parameter()?);
+			}
+		}
+
+		if self
+			.next_if_token_type(TokenType::CloseParenthesis)
+			.is_none()
+		{
+			return self.create_expression_syntax_error(
+				ErrorCode::Syntax,
+				"Expected closing parenthesis".to_string(),
+				open_parenthesis,
+			);
+		}
+
+		let arrow = match self.next_if_token_type(TokenType::Arrow) {
+			Some(Ok(arrow)) => arrow,
+			_ => {
+				return self.create_expression_syntax_error(
+					ErrorCode::Syntax,
+					"Expected ->".to_string(),
+					open_parenthesis,
+				)
+			}
+		};
+
+		// Expression bodies return their value
+		let body = match self.next_if_token_type(TokenType::OpenCurlyBracket) {
+			Some(Ok(open_curly_bracket)) => self.body(open_curly_bracket)?,
+			_ => Statement::Return(arrow.clone(), Some(self.assignment()?)),
+		};
+
+		Ok(Expression::Lambda(
+			open_parenthesis,
+			parameters,
+			arrow,
+			Box::new(body),
+		))
 	}
 
 	fn parameter(&mut self) -> Result<(Token, Token)> {
 		if let Some(Ok(identifier)) = self.next_if_token_type(TokenType::Identifier) {
-			let type_specification: Token;
+			self.parameter_from(identifier)
+		} else {
+			let token = self.next_unexpected().unwrap().unwrap();
+			Err(
+				Error::new_parser_error("Expected identifier".to_string(), token)
+					.with_code(ErrorCode::ExpectedIdentifier),
+			)
+		}
+	}
 
-			match self.next_if_specifier() {
-				Some(next) => type_specification = next?,
-				None => {
-					return Err(Error::new_parser_error(
-						"Expected type specification".to_string(),
-						identifier,
-					));
-				}
-			}
+	fn parameter_from(&mut self, identifier: Token) -> Result<(Token, Token)> {
+		let type_specification: Token;
 
-			if let Some(Ok(_)) = self.next_if_token_type(TokenType::Separator) {
-				Ok((identifier, type_specification))
-			} else if self.peek_token_type(TokenType::CloseParenthesis) {
-				Ok((identifier, type_specification))
-			} else {
-				Err(Error::new_parser_error(
-					"Expected seperator".to_string(),
+		match self.next_if_specifier() {
+			Some(next) => type_specification = next?,
+			None => {
+				return Err(Error::new_parser_error(
+					"Expected type specification".to_string(),
 					identifier,
-				))
+				)
+				.with_code(ErrorCode::ExpectedType));
 			}
+		}
+
+		if let Some(Ok(_)) = self.next_if_token_type(TokenType::Separator) {
+			Ok((identifier, type_specification))
+		} else if self.peek_token_type(TokenType::CloseParenthesis) {
+			Ok((identifier, type_specification))
 		} else {
-			let token = self.tokenizer.next().unwrap().unwrap();
 			Err(Error::new_parser_error(
-				"Expected identifier".to_string(),
-				token,
+				"Expected seperator".to_string(),
+				identifier,
 			))
 		}
 	}
@@ -7,6 +7,11 @@ pub use common::*;
 pub mod declaration;
 pub use declaration::*;
 
+pub mod documentation;
+
+pub mod enums;
+pub use enums::*;
+
 pub mod expressions;
 pub use expressions::*;
 
@@ -15,3 +20,6 @@ pub use functions::*;
 
 pub mod loops;
 pub use loops::*;
+
+pub mod records;
+pub use records::*;
@@ -0,0 +1,121 @@
+use crate::ast::*;
+use crate::parser::Parser;
+use crate::ErrorCode;
+use crate::Result;
+
+impl Parser<'_> {
+	pub(crate) fn record(&mut self, token: Token) -> Result<Statement> {
+		let identifier = match self.next_if_token_type(TokenType::Identifier) {
+			Some(Ok(identifier)) => identifier,
+			_ => {
+				return self.create_statement_syntax_error(
+					ErrorCode::ExpectedIdentifier,
+					"Expected identifier".to_string(),
+					token,
+				)
+			}
+		};
+
+		let open_curly_bracket = match self.next_if_token_type(TokenType::OpenCurlyBracket) {
+			Some(Ok(open_curly_bracket)) => open_curly_bracket,
+			_ => {
+				return self.create_statement_syntax_error(
+					ErrorCode::Syntax,
+					"Expected record body".to_string(),
+					identifier,
+				)
+			}
+		};
+
+		let mut fields: Vec<(Token, Token)> = Vec::new();
+
+		while !self.peek_token_type(TokenType::CloseCurlyBracket) {
+			let field = match self.next_if_token_type(TokenType::Identifier) {
+				Some(Ok(field)) => field,
+				_ => {
+					return self.create_statement_syntax_error(
+						ErrorCode::Syntax,
+						"Expected field".to_string(),
+						open_curly_bracket,
+					)
+				}
+			};
+
+			match self.next_if_specifier() {
+				Some(type_specification) => fields.push((field, type_specification?)),
+				None => {
+					return self.create_statement_syntax_error(
+						ErrorCode::ExpectedType,
+						"Expected type specification".to_string(),
+						field,
+					)
+				}
+			}
+
+			if self.next_if_token_type(TokenType::Separator).is_none() {
+				break;
+			}
+		}
+
+		match self.next_if_token_type(TokenType::CloseCurlyBracket) {
+			Some(Ok(close_curly_bracket)) => Ok(Statement::Record(
+				token,
+				identifier,
+				fields,
+				close_curly_bracket,
+			)),
+			_ => self.create_statement_syntax_error(
+				ErrorCode::Syntax,
+				"Incorrectly formatted record".to_string(),
+				open_curly_bracket,
+			),
+		}
+	}
+
+	// Records are constructed by naming every field, like Point { x: 1, y: 2 }
+	pub(crate) fn record_construction(
+		&mut self,
+		identifier: Token,
+		open_curly_bracket: Token,
+	) -> Result<Expression> {
+		let mut fields: Vec<(Token, Expression)> = Vec::new();
+
+		while !self.peek_token_type(TokenType::CloseCurlyBracket) {
+			let field = match self.next_if_token_type(TokenType::Identifier) {
+				Some(Ok(field)) => field,
+				_ => {
+					return self.create_expression_syntax_error(
+						ErrorCode::Syntax,
+						"Expected field".to_string(),
+						open_curly_bracket,
+					)
+				}
+			};
+
+			if self.next_if_token_type(TokenType::Specifier).is_none() {
+				return self.create_expression_syntax_error(
+					ErrorCode::Syntax,
+					"Expected : after field".to_string(),
+					field,
+				);
+			}
+
+			fields.push((field, self.expression_root()?));
+
+			if self.next_if_token_type(TokenType::Separator).is_none() {
+				break;
+			}
+		}
+
+		match self.next_if_token_type(TokenType::CloseCurlyBracket) {
+			Some(Ok(close_curly_bracket)) => {
+				Ok(Expression::Record(identifier, fields, close_curly_bracket))
+			}
+			_ => self.create_expression_syntax_error(
+				ErrorCode::Syntax,
+				"Incorrectly formatted record".to_string(),
+				open_curly_bracket,
+			),
+		}
+	}
+}
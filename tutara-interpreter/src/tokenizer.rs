@@ -1,18 +1,52 @@
 use crate::ast::*;
 use crate::Error;
+use crate::ErrorCode;
 use crate::ErrorType;
 use crate::Result;
 
-use std::iter::Peekable;
-use std::str::Chars;
-
 pub struct Tokenizer<'a> {
-	chars: Peekable<Chars<'a>>,
+	chars: Cursor<'a>,
 	// Cursor position
 	line: u32,
 	column: u32,
 	// Current block length
 	length: u32,
+	// Byte offset of the source in its file and of the current block
+	origin: u32,
+	offset: u32,
+}
+
+// Peekable characters of the source that keep track of their byte offset
+#[derive(Clone)]
+struct Cursor<'a> {
+	source: &'a str,
+	offset: usize,
+	peeked: Option<char>,
+}
+
+impl Cursor<'_> {
+	fn peek(&mut self) -> Option<&char> {
+		self.peeked = self.source[self.offset..].chars().next();
+		self.peeked.as_ref()
+	}
+
+	fn next_if(&mut self, func: impl FnOnce(&char) -> bool) -> Option<char> {
+		match self.peek() {
+			Some(&next) if func(&next) => self.next(),
+			_ => None,
+		}
+	}
+}
+
+impl Iterator for Cursor<'_> {
+	type Item = char;
+
+	fn next(&mut self) -> Option<char> {
+		let next = self.source[self.offset..].chars().next()?;
+		self.offset += next.len_utf8();
+
+		Some(next)
+	}
 }
 
 impl Iterator for Tokenizer<'_> {
@@ -20,6 +54,8 @@ impl Iterator for Tokenizer<'_> {
 
 	fn next(&mut self) -> Option<Self::Item> {
 		loop {
+			self.offset = self.origin + self.chars.offset as u32;
+
 			if let Some(current) = self.chars.next() {
 				self.length = 1;
 				let mut token = None;
@@ -33,15 +69,25 @@ impl Iterator for Tokenizer<'_> {
 					}
 				} else if current.is_digit(10) {
 					token = Some(self.number(current));
+				} else if current == 'r' && self.peek_char('\'') {
+					self.chars.next();
+					self.length += 1;
+
+					token = Some(self.string(true));
 				} else if current.is_alphabetic() {
 					token = Some(self.identifier(current));
 				} else if current == '\'' {
-					token = Some(self.string());
+					token = Some(self.string(false));
 				} else if current == '/' && self.chars.peek() == Some(&'/') {
 					self.chars.next();
 					self.length += 1;
 
 					token = Some(self.comment());
+				} else if current == '/' && self.chars.peek() == Some(&'*') {
+					self.chars.next();
+					self.length += 1;
+
+					token = Some(self.block_comment());
 				} else if current == '&' {
 					token = Some(self.token_if_char('&', TokenType::And, "expected &"));
 				} else if current == '|' {
@@ -56,8 +102,10 @@ impl Iterator for Tokenizer<'_> {
 					token = Some(self.assignment_operation(token.unwrap().unwrap()));
 					token = Some(self.comparison(token.unwrap().unwrap()));
 					token = Some(self.arrow(token.unwrap().unwrap()));
+					token = Some(self.range(token.unwrap().unwrap()));
 				} else {
 					token = Some(self.create_error(
+						ErrorCode::UnexpectedCharacter,
 						ErrorType::Lexical(self.line, self.column, self.length),
 						format!(
 							"Unexpected token at {} ({}:{})",
@@ -81,11 +129,22 @@ impl Iterator for Tokenizer<'_> {
 // Constructor
 impl Tokenizer<'_> {
 	pub fn new(source: &str) -> Tokenizer {
+		Tokenizer::new_at(source, 1, 0, 0)
+	}
+
+	// Tokenizes a part of a file, like the expression of an interpolation, starting at its position
+	pub fn new_at(source: &str, line: u32, column: u32, offset: u32) -> Tokenizer {
 		Tokenizer {
-			chars: source.chars().peekable(),
-			line: 1,
-			column: 0,
+			chars: Cursor {
+				source,
+				offset: 0,
+				peeked: None,
+			},
+			line,
+			column,
 			length: 0,
+			origin: offset,
+			offset,
 		}
 	}
 }
@@ -107,36 +166,161 @@ impl Tokenizer<'_> {
 			self.line,
 			self.column,
 			self.length,
+			self.offset,
 		))
 	}
-	fn create_error(&mut self, r#type: ErrorType, message: String) -> Result<Token> {
-		Err(Error::new(r#type, message))
+	fn create_error(
+		&mut self,
+		code: ErrorCode,
+		r#type: ErrorType,
+		message: String,
+	) -> Result<Token> {
+		Err(Error::new(r#type, message).with_code(code))
 	}
 }
 
 // Token functions
 impl Tokenizer<'_> {
 	pub fn number(&mut self, current: char) -> Result<Token> {
+		let radix = match (current, self.chars.peek()) {
+			('0', Some('x')) | ('0', Some('X')) => 16,
+			('0', Some('b')) | ('0', Some('B')) => 2,
+			_ => 10,
+		};
+
+		if radix != 10 {
+			return self.radix_number(radix);
+		}
+
 		let mut value = current.to_string();
+		let mut float = false;
 
-		while let Some(next) = self.chars.peek() {
-			if next.is_digit(10) {
-				value.push(*next);
+		self.digits(&mut value, 10);
+
+		// A fraction needs a digit after the dot, so 1..2 remains a range
+		let mut lookahead = self.chars.clone();
+		if lookahead.next() == Some('.')
+			&& matches!(lookahead.peek(), Some(next) if next.is_digit(10))
+		{
+			value.push(self.chars.next().unwrap());
+			self.length += 1;
+			self.digits(&mut value, 10);
+			float = true;
+		}
+
+		let mut lookahead = self.chars.clone();
+		if let Some(exponent @ 'e') | Some(exponent @ 'E') = lookahead.next() {
+			let sign = lookahead.next_if(|next| *next == '+' || *next == '-');
+
+			if matches!(lookahead.peek(), Some(next) if next.is_digit(10)) {
 				self.chars.next();
+				value.push(exponent);
 				self.length += 1;
-			} else {
-				break;
+
+				if let Some(sign) = sign {
+					self.chars.next();
+					value.push(sign);
+					self.length += 1;
+				}
+
+				self.digits(&mut value, 10);
+				float = true;
 			}
 		}
-		let literal = value.parse::<u32>();
 
-		if let Err(_literal) = literal {
-			self.create_error(
-				ErrorType::Lexical(self.line, self.column, self.length),
-				"Invalid number".to_string(),
-			)
+		if float {
+			match value.parse::<f64>() {
+				Ok(literal) if literal.is_finite() => {
+					self.create_literal_token(TokenType::Float, Some(Literal::Float(literal)))
+				}
+				_ => self.create_error(
+					ErrorCode::FloatOutOfRange,
+					ErrorType::Lexical(self.line, self.column, self.length),
+					format!("Float literal {} is out of range", value),
+				),
+			}
 		} else {
-			self.create_literal_token(TokenType::Integer, Some(Literal::Number(literal.unwrap())))
+			self.integer(&value, 10)
+		}
+	}
+
+	// Hexadecimal and binary integers like 0xFF and 0b1010
+	fn radix_number(&mut self, radix: u32) -> Result<Token> {
+		let prefix = self.chars.next().unwrap();
+		self.length += 1;
+
+		let mut value = String::new();
+		self.digits(&mut value, radix);
+
+		// Letters and digits directly after the digits are part of the literal, like the 2 in 0b12
+		let mut invalid = None;
+		while let Some(next) = self
+			.chars
+			.next_if(|next| next.is_alphanumeric() || *next == '_')
+		{
+			invalid = invalid.or(Some(next));
+			self.length += 1;
+		}
+
+		if let Some(invalid) = invalid {
+			return self.create_error(
+				ErrorCode::InvalidDigits,
+				ErrorType::Lexical(self.line, self.column, self.length),
+				format!(
+					"Invalid digit {} in literal with prefix 0{}",
+					invalid, prefix
+				),
+			);
+		}
+
+		if value.is_empty() {
+			return self.create_error(
+				ErrorCode::InvalidDigits,
+				ErrorType::Lexical(self.line, self.column, self.length),
+				format!("Expected digits after 0{}", prefix),
+			);
+		}
+
+		self.integer(&value, radix)
+	}
+
+	fn integer(&mut self, value: &str, radix: u32) -> Result<Token> {
+		match i64::from_str_radix(value, radix) {
+			Ok(literal) => {
+				self.create_literal_token(TokenType::Integer, Some(Literal::Integer(literal)))
+			}
+			// The digits of i64::MIN are kept as i64::MIN, the parser only accepts them after a unary minus
+			Err(_) if u64::from_str_radix(value, radix) == Ok(i64::MIN.unsigned_abs()) => {
+				self.create_literal_token(TokenType::Integer, Some(Literal::Integer(i64::MIN)))
+			}
+			Err(_) => self.create_error(
+				ErrorCode::IntegerOutOfRange,
+				ErrorType::Lexical(self.line, self.column, self.length),
+				"Int literal is out of range".to_string(),
+			),
+		}
+	}
+
+	// Digits can be separated by underscores, like 1_000_000, the underscores are not part of the value
+	fn digits(&mut self, value: &mut String, radix: u32) {
+		loop {
+			let mut lookahead = self.chars.clone();
+
+			match lookahead.next() {
+				Some(next) if next.is_digit(radix) => {
+					value.push(next);
+					self.chars.next();
+					self.length += 1;
+				}
+				Some('_')
+					if !value.is_empty()
+						&& matches!(lookahead.peek(), Some(next) if next.is_digit(radix)) =>
+				{
+					self.chars.next();
+					self.length += 1;
+				}
+				_ => break,
+			}
 		}
 	}
 
@@ -164,26 +348,56 @@ impl Tokenizer<'_> {
 		}
 	}
 
-	pub fn string(&mut self) -> Result<Token> {
+	// Raw strings like r'C:\path' keep backslashes and dollar signs as text
+	pub fn string(&mut self, raw: bool) -> Result<Token> {
+		let mut lookahead = self.chars.clone();
+		if lookahead.next() == Some('\'') && lookahead.next() == Some('\'') {
+			self.chars.next();
+			self.chars.next();
+			self.length += 2;
+
+			return self.multiline_string(raw);
+		}
+
 		let mut value = String::new();
+		let mut parts: Vec<TemplatePart> = Vec::new();
+		let mut terminated = false;
 
 		while let Some(next) = self.chars.peek() {
-			if next == &'\\' {
+			if next == &'\\' && !raw {
 				// Escape sequences
 				self.chars.next();
 				self.length += 1;
-				if let Some(escaped) = self.escape() {
-					value += &*escaped;
+
+				match self.escape() {
+					Ok(character) => value.push(character),
+					Err(error) => {
+						self.skip_string();
+						return Err(error);
+					}
+				}
+			} else if *next == '$' && !raw {
+				// Interpolation
+				self.chars.next();
+				self.length += 1;
+
+				if let Some(part) = self.interpolation()? {
+					if !value.is_empty() {
+						parts.push(TemplatePart::Text(std::mem::take(&mut value)));
+					}
+					parts.push(part);
 				} else {
-					value.push('\\');
+					value.push('$');
 				}
 			} else if *next == '\'' {
 				// string end
 				self.chars.next();
 				self.length += 1;
+				terminated = true;
 				break;
 			} else if *next == '\n' {
 				return self.create_error(
+					ErrorCode::UnexpectedNewLine,
 					ErrorType::Lexical(self.line, self.column, self.length),
 					"Unexpected new line, expected end of string.".to_string(),
 				);
@@ -195,10 +409,318 @@ impl Tokenizer<'_> {
 			}
 		}
 
-		self.create_literal_token(TokenType::String, Some(Literal::String(value)))
+		if !terminated {
+			return self.create_error(
+				ErrorCode::UnterminatedString,
+				ErrorType::Lexical(self.line, self.column, self.length),
+				"Unterminated string, expected '".to_string(),
+			);
+		}
+
+		let (r#type, literal) = Tokenizer::string_literal(value, parts);
+		self.create_literal_token(r#type, Some(literal))
+	}
+
+	// Skips the rest of a string with an error, so its closing quote does not start another string
+	fn skip_string(&mut self) {
+		while let Some(next) = self.chars.next_if(|next| *next != '\n') {
+			self.length += 1;
+
+			match next {
+				'\'' => break,
+				'\\' if self.chars.next_if(|next| *next != '\n').is_some() => self.length += 1,
+				_ => {}
+			}
+		}
+	}
+
+	// Triple quoted strings can span multiple lines. A new line directly after the opening quotes and the
+	// line holding the closing quotes are left out, as is the indentation all other lines have in common.
+	fn multiline_string(&mut self, raw: bool) -> Result<Token> {
+		let line = self.line;
+		let column = self.column;
+		let opening = self.length;
+		let indentation = self.common_indentation(raw);
+
+		// Length of the lines before the current one, including their new line characters
+		let mut consumed = 0;
+		let mut value = String::new();
+		let mut parts: Vec<TemplatePart> = Vec::new();
+
+		let mut lookahead = self.chars.clone();
+		let mut skip_new_line =
+			lookahead.find(|next| !next.is_whitespace() || *next == '\n') == Some('\n');
+
+		if skip_new_line {
+			self.skip_whitespace();
+		}
+
+		loop {
+			let next = match self.chars.peek() {
+				Some(next) => *next,
+				None => {
+					return Err(Error::new_lexical_error(
+						"Unterminated string, expected '''".to_string(),
+						line,
+						column,
+						opening,
+					)
+					.with_code(ErrorCode::UnterminatedString))
+				}
+			};
+
+			if next == '\n' {
+				self.new_line(&mut consumed);
+
+				let closing = self.is_closing_line();
+				if closing {
+					self.skip_whitespace();
+				} else {
+					self.skip_indentation(&indentation);
+				}
+
+				if !std::mem::replace(&mut skip_new_line, false) && !closing {
+					value.push('\n');
+				}
+			} else if next == '\'' && self.is_closing_quotes() {
+				self.chars.nth(2);
+				self.length += 3;
+				break;
+			} else if next == '\\' && !raw {
+				self.chars.next();
+				self.length += 1;
+				value.push(self.escape()?);
+			} else if next == '$' && !raw {
+				self.chars.next();
+				self.length += 1;
+
+				if let Some(part) = self.interpolation()? {
+					if !value.is_empty() {
+						parts.push(TemplatePart::Text(std::mem::take(&mut value)));
+					}
+					parts.push(part);
+				} else {
+					value.push('$');
+				}
+			} else {
+				self.chars.next();
+				self.length += 1;
+				value.push(next);
+			}
+		}
+
+		// The token starts at the opening quotes, the cursor continues after the closing quotes
+		let (r#type, literal) = Tokenizer::string_literal(value, parts);
+		Ok(Token::new(
+			r#type,
+			Some(literal),
+			line,
+			column,
+			consumed + self.length,
+			self.offset,
+		)
+		.with_end(self.line, self.column + self.length))
+	}
+
+	// Leading whitespace all lines of a triple quoted string start with, ignoring the line of the opening
+	// quotes and lines with only whitespace. Tabs and spaces are not interchangeable.
+	fn common_indentation(&self, raw: bool) -> String {
+		let mut lookahead = self.chars.clone();
+		let mut content = String::new();
+		let mut quotes = 0;
+
+		while let Some(next) = lookahead.next() {
+			if next == '\\' && !raw {
+				content.push(next);
+				content.extend(lookahead.next());
+				quotes = 0;
+				continue;
+			}
+
+			content.push(next);
+
+			if next != '\'' {
+				quotes = 0;
+			} else if quotes == 2 {
+				content.truncate(content.len() - 3);
+				break;
+			} else {
+				quotes += 1;
+			}
+		}
+
+		let mut indentations = content
+			.lines()
+			.skip(1)
+			.filter(|line| !line.trim().is_empty())
+			.map(|line| &line[..line.len() - line.trim_start().len()]);
+
+		let first = indentations.next().unwrap_or_default();
+		indentations
+			.fold(first, |common, indentation| {
+				let length = common
+					.chars()
+					.zip(indentation.chars())
+					.take_while(|(a, b)| a == b)
+					.map(|(a, _)| a.len_utf8())
+					.sum();
+
+				&common[..length]
+			})
+			.to_string()
+	}
+
+	fn is_closing_line(&self) -> bool {
+		self.chars
+			.clone()
+			.skip_while(|next| next.is_whitespace() && *next != '\n')
+			.take(3)
+			.eq("'''".chars())
+	}
+
+	fn is_closing_quotes(&self) -> bool {
+		self.chars.clone().take(3).eq("'''".chars())
+	}
+
+	// Skips the whitespace on the current line
+	fn skip_whitespace(&mut self) {
+		while self
+			.chars
+			.next_if(|next| next.is_whitespace() && *next != '\n')
+			.is_some()
+		{
+			self.length += 1;
+		}
+	}
+
+	// Skips the common indentation at the start of a line, as far as the line matches it
+	fn skip_indentation(&mut self, indentation: &str) {
+		for expected in indentation.chars() {
+			if self.chars.next_if(|next| *next == expected).is_none() {
+				break;
+			}
+
+			self.length += 1;
+		}
+	}
+
+	fn string_literal(value: String, mut parts: Vec<TemplatePart>) -> (TokenType, Literal) {
+		if parts.is_empty() {
+			return (TokenType::String, Literal::String(value));
+		}
+
+		if !value.is_empty() {
+			parts.push(TemplatePart::Text(value));
+		}
+
+		(TokenType::Template, Literal::Template(parts))
+	}
+
+	// Reads $name or ${expression} after the dollar sign, a dollar sign followed by anything else is text
+	fn interpolation(&mut self) -> Result<Option<TemplatePart>> {
+		let column = self.column + self.length;
+		let offset = self.origin + self.chars.offset as u32;
+
+		match self.chars.peek() {
+			Some(next) if next.is_alphabetic() => {
+				let mut name = String::new();
+
+				while let Some(next) = self
+					.chars
+					.next_if(|next| next.is_alphanumeric() || *next == '_')
+				{
+					name.push(next);
+					self.length += 1;
+				}
+
+				Ok(Some(TemplatePart::Expression(
+					name, self.line, column, offset,
+				)))
+			}
+			Some('{') => {
+				self.chars.next();
+				self.length += 1;
+
+				let source = self.interpolation_source()?;
+
+				if source.trim().is_empty() {
+					return Err(Error::new_lexical_error(
+						"Expected expression in interpolation".to_string(),
+						self.line,
+						column,
+						2,
+					)
+					.with_code(ErrorCode::InvalidInterpolation));
+				}
+
+				Ok(Some(TemplatePart::Expression(
+					source,
+					self.line,
+					column + 1,
+					offset + 1,
+				)))
+			}
+			_ => Ok(None),
+		}
+	}
+
+	// Source of the expression up to the matching closing bracket, strings inside of the expression are
+	// copied as a whole so their brackets are not counted.
+	fn interpolation_source(&mut self) -> Result<String> {
+		let mut source = String::new();
+		let mut depth = 0;
+		let mut string = false;
+
+		loop {
+			let next = match self.chars.peek() {
+				Some('\n') | None => {
+					return Err(Error::new_lexical_error(
+						"Unterminated interpolation, expected }".to_string(),
+						self.line,
+						self.column,
+						self.length,
+					)
+					.with_code(ErrorCode::InvalidInterpolation))
+				}
+				Some(next) => *next,
+			};
+
+			self.chars.next();
+			self.length += 1;
+
+			match next {
+				'}' if !string && depth == 0 => return Ok(source),
+				'{' if !string => depth += 1,
+				'}' if !string => depth -= 1,
+				'\'' => string = !string,
+				'\\' if string => {
+					source.push(next);
+
+					if let Some(escaped) = self.chars.next_if(|next| *next != '\n') {
+						source.push(escaped);
+						self.length += 1;
+					}
+
+					continue;
+				}
+				_ => {}
+			}
+
+			source.push(next);
+		}
 	}
 
+	// Doc comments start with exactly three slashes, four or more slashes are a regular comment
 	pub fn comment(&mut self) -> Result<Token> {
+		let mut lookahead = self.chars.clone();
+		let r#type = if lookahead.next() == Some('/') && lookahead.next() != Some('/') {
+			self.chars.next();
+			self.length += 1;
+			TokenType::DocComment
+		} else {
+			TokenType::Comment
+		};
+
 		let mut value = String::new();
 
 		loop {
@@ -217,35 +739,171 @@ impl Tokenizer<'_> {
 			}
 		}
 
-		self.create_literal_token(TokenType::Comment, Some(Literal::String(value)))
+		self.create_literal_token(r#type, Some(Literal::String(value)))
 	}
 
-	pub fn escape(&mut self) -> Option<&str> {
-		match self.chars.peek() {
-			Some('n') => {
-				self.chars.next();
-				Some("\n")
-			}
-			Some('r') => {
-				self.chars.next();
-				Some("\r")
+	// Block comments can span multiple lines and contain other block comments
+	pub fn block_comment(&mut self) -> Result<Token> {
+		let line = self.line;
+		let column = self.column;
+		let mut consumed = 0;
+		let mut depth = 0;
+		let mut value = String::new();
+
+		loop {
+			let mut lookahead = self.chars.clone();
+
+			match (lookahead.next(), lookahead.next()) {
+				(None, _) => {
+					return Err(Error::new_lexical_error(
+						"Unterminated block comment, expected */".to_string(),
+						line,
+						column,
+						2,
+					)
+					.with_code(ErrorCode::UnterminatedComment))
+				}
+				(Some('*'), Some('/')) if depth == 0 => {
+					self.chars.nth(1);
+					self.length += 2;
+					break;
+				}
+				(Some('*'), Some('/')) => {
+					self.chars.nth(1);
+					self.length += 2;
+					value.push_str("*/");
+					depth -= 1;
+				}
+				(Some('/'), Some('*')) => {
+					self.chars.nth(1);
+					self.length += 2;
+					value.push_str("/*");
+					depth += 1;
+				}
+				(Some('\n'), _) => {
+					self.new_line(&mut consumed);
+					value.push('\n');
+				}
+				(Some(next), _) => {
+					self.chars.next();
+					self.length += 1;
+					value.push(next);
+				}
 			}
-			Some('t') => {
-				self.chars.next();
-				Some("\t")
+		}
+
+		Ok(Token::new(
+			TokenType::Comment,
+			Some(Literal::String(value)),
+			line,
+			column,
+			consumed + self.length,
+			self.offset,
+		)
+		.with_end(self.line, self.column + self.length))
+	}
+
+	// Called after the backslash, unknown escapes are an error instead of being kept as text
+	pub fn escape(&mut self) -> Result<char> {
+		let column = self.column + self.length - 1;
+		let next = self.chars.next();
+
+		if next.is_some() {
+			self.length += 1;
+		}
+
+		match next {
+			Some('n') => Ok('\n'),
+			Some('r') => Ok('\r'),
+			Some('t') => Ok('\t'),
+			Some('b') => Ok('\u{8}'),
+			Some('f') => Ok('\u{c}'),
+			Some('v') => Ok('\u{b}'),
+			Some('0') => Ok('\0'),
+			Some('\\') => Ok('\\'),
+			Some('\'') => Ok('\''),
+			Some('$') => Ok('$'),
+			Some('x') => self.hex_escape(column),
+			Some('u') => self.unicode_escape(column),
+			Some(next) => {
+				Err(self.escape_error(format!("Unknown escape sequence \\{}", next), column))
 			}
-			Some('\\') => {
-				self.chars.next();
-				Some("\\")
+			None => Err(self.escape_error("Unexpected end of escape sequence".to_string(), column)),
+		}
+	}
+
+	// \xHH escapes are limited to ASCII, like \x41
+	fn hex_escape(&mut self, column: u32) -> Result<char> {
+		let mut digits = String::new();
+
+		while digits.len() < 2 {
+			match self.chars.next_if(|next| next.is_ascii_hexdigit()) {
+				Some(digit) => {
+					digits.push(digit);
+					self.length += 1;
+				}
+				None => {
+					return Err(self.escape_error(
+						"Expected two hexadecimal digits in \\x escape".to_string(),
+						column,
+					))
+				}
 			}
-			Some('\'') => {
-				self.chars.next();
-				Some("'")
+		}
+
+		match u8::from_str_radix(&digits, 16) {
+			Ok(value) if value.is_ascii() => Ok(value as char),
+			_ => Err(self.escape_error(format!("Escape \\x{} is not ASCII", digits), column)),
+		}
+	}
+
+	// \u{1F600} escapes hold one to six hexadecimal digits of a unicode scalar value
+	fn unicode_escape(&mut self, column: u32) -> Result<char> {
+		if self.chars.next_if(|next| *next == '{').is_none() {
+			return Err(self.escape_error("Expected { in \\u escape".to_string(), column));
+		}
+		self.length += 1;
+
+		let mut digits = String::new();
+
+		while let Some(digit) = self.chars.next_if(|next| next.is_ascii_hexdigit()) {
+			digits.push(digit);
+			self.length += 1;
+		}
+
+		if self.chars.next_if(|next| *next == '}').is_none() {
+			return Err(self.escape_error("Expected } in \\u escape".to_string(), column));
+		}
+		self.length += 1;
+
+		if digits.is_empty() || digits.len() > 6 {
+			return Err(self.escape_error(
+				"Expected one to six hexadecimal digits in \\u escape".to_string(),
+				column,
+			));
+		}
+
+		match u32::from_str_radix(&digits, 16)
+			.ok()
+			.and_then(char::from_u32)
+		{
+			Some(character) => Ok(character),
+			None => {
+				Err(self.escape_error(format!("Invalid unicode scalar value {}", digits), column))
 			}
-			_ => None,
 		}
 	}
 
+	fn escape_error(&self, message: String, column: u32) -> Error {
+		Error::new_lexical_error(
+			message,
+			self.line,
+			column,
+			self.column + self.length - column,
+		)
+		.with_code(ErrorCode::InvalidEscape)
+	}
+
 	pub fn arrow(&mut self, token: Token) -> Result<Token> {
 		let r#type = &token.r#type;
 
@@ -256,6 +914,20 @@ impl Tokenizer<'_> {
 		Ok(token)
 	}
 
+	pub fn range(&mut self, token: Token) -> Result<Token> {
+		if token.r#type == TokenType::Dot && self.next_if_char('.').is_some() {
+			self.length += 1;
+			if self.next_if_char('=').is_some() {
+				self.length += 1;
+				return self.create_token(TokenType::RangeInclusive);
+			}
+
+			return self.create_token(TokenType::Range);
+		}
+
+		Ok(token)
+	}
+
 	pub fn assignment_operation(&mut self, token: Token) -> Result<Token> {
 		let r#type = &token.r#type;
 
@@ -270,6 +942,7 @@ impl Tokenizer<'_> {
 					TokenType::Exponentiation => self.create_token(TokenType::AssignExponentiation),
 					TokenType::Modulo => self.create_token(TokenType::AssignModulo),
 					_ => self.create_error(
+						ErrorCode::InvalidAssignmentOperation,
 						ErrorType::Lexical(self.line, self.column, self.length),
 						"Invalid assignment operation".to_string(),
 					),
@@ -297,6 +970,7 @@ impl Tokenizer<'_> {
 					TokenType::Greater => self.create_token(TokenType::GreaterOrEqual),
 					TokenType::Lesser => self.create_token(TokenType::LesserOrEqual),
 					_ => self.create_error(
+						ErrorCode::InvalidAssignmentOperation,
 						ErrorType::Lexical(self.line, self.column, self.length),
 						"Invalid assignment operation".to_string(),
 					),
@@ -309,6 +983,16 @@ impl Tokenizer<'_> {
 }
 
 impl Tokenizer<'_> {
+	// Moves the cursor past a new line inside of a token spanning multiple lines, consumed holds the
+	// length of the previous lines of the token
+	fn new_line(&mut self, consumed: &mut u32) {
+		self.chars.next();
+		*consumed += self.length + 1;
+		self.line += 1;
+		self.column = 0;
+		self.length = 0;
+	}
+
 	fn peek_char(&mut self, next: char) -> bool {
 		match self.chars.peek() {
 			Some(peek) => *peek == next,
@@ -336,6 +1020,7 @@ impl Tokenizer<'_> {
 		}
 
 		self.create_error(
+			ErrorCode::Lexical,
 			ErrorType::Lexical(self.line, self.column, self.length),
 			error_message.to_string(),
 		)
@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::PathBuf;
+use tutara_interpreter::{parser::Parser, Interpreter, Tokenizer, Value};
+
+fn test_result(name: &str, result: Value) {
+	let mut script_path: PathBuf = ["tests", "scripts", name, name].iter().collect();
+	script_path.set_extension("ttr");
+
+	let script = fs::read_to_string(script_path).expect("Could not read test script");
+
+	let parser = Parser::new(Tokenizer::new(&script).peekable());
+	let evaluation = Interpreter::new().evaluate(parser);
+
+	assert_eq!(result, evaluation.unwrap())
+}
+
+#[test]
+fn test_math_function_result() {
+	test_result("math-function", Value::Integer(3));
+}
+
+#[test]
+fn test_continue_result() {
+	test_result("continue", Value::Integer(5));
+}
+
+#[test]
+fn test_break_result() {
+	test_result("break", Value::Integer(9));
+}
+
+#[test]
+fn test_if_else_result() {
+	test_result("if-else", Value::Integer(10));
+}
+
+#[test]
+fn test_assignment_operations_result() {
+	test_result("assignment-operations", Value::Integer(3137));
+}
+
+#[test]
+fn test_logic_function_result() {
+	test_result("logic-function", Value::Integer(1));
+}
@@ -3,3 +3,5 @@ pub use parser_tests::*;
 
 mod tokenizer_tests;
 pub use tokenizer_tests::*;
+
+mod interpreter_tests;
@@ -0,0 +1,170 @@
+use tutara_interpreter::{
+	parser::Parser, Analyzer, Diagnostic, Error, ErrorCode, Interpreter, LintConfig, Linter, Style,
+	Tokenizer,
+};
+
+fn diagnose(src: &str) -> Vec<String> {
+	let (statements, errors) = Parser::new(Tokenizer::new(src).peekable()).parse_all();
+
+	if !errors.is_empty() {
+		return render(src, &errors);
+	}
+
+	let errors = Analyzer::new().check(&statements);
+
+	if !errors.is_empty() {
+		return render(src, &errors);
+	}
+
+	match Interpreter::new().evaluate(Parser::new(Tokenizer::new(src).peekable())) {
+		Ok(_) => Vec::new(),
+		Err(err) => render(src, &[err]),
+	}
+}
+
+fn render(src: &str, errors: &[Error]) -> Vec<String> {
+	errors
+		.iter()
+		.map(|error| Diagnostic::new(error, src).to_string())
+		.collect()
+}
+
+#[test]
+fn test_lexical_diagnostic() {
+	assert_eq!(
+		vec![concat!(
+			"error[E0108]: Unknown escape sequence \\q\n",
+			" --> line 1 on column 12\n",
+			"  |\n",
+			"1 | val a = 'abc\\q'\n",
+			"  |             ^^\n",
+			"  |\n",
+			"  = help: Supported escape sequences are \\n, \\r, \\t, \\b, \\f, \\v, \\0, \\\\, \\', \\$, \\xHH and \\u{HHHH}\n",
+		)],
+		diagnose("val a = 'abc\\q'")
+	);
+}
+
+#[test]
+fn test_end_of_file_diagnostic() {
+	assert_eq!(
+		vec![concat!(
+			"error[E0201]: Unexpected end of file\n",
+			" --> line 2 on column 12\n",
+			"  |\n",
+			"2 | val b = (1 +\n",
+			"  |             ^\n",
+			"  |\n",
+			"  = help: Check for an unclosed bracket or an incomplete expression\n",
+		)],
+		diagnose("val a = 1\nval b = (1 +\n\n")
+	);
+}
+
+#[test]
+fn test_label_diagnostic() {
+	assert_eq!(
+		vec![concat!(
+			"error[E0305]: Can not assign to val count declared at line 1 on column 4\n",
+			" --> line 3 on column 1\n",
+			"  |\n",
+			"1 | val count = 1\n",
+			"  |     ----- count is declared as val here\n",
+			"...\n",
+			"3 | \tcount = 2\n",
+			"  | \t^^^^^\n",
+			"  |\n",
+			"  = help: Declare the variable with var to be able to assign to it\n",
+		)],
+		diagnose("val count = 1\nif (true) {\n\tcount = 2\n}\nreturn count")
+	);
+}
+
+#[test]
+fn test_note_diagnostic() {
+	assert_eq!(
+		vec![concat!(
+			"error[E0310]: Function f does not return Int on every path\n",
+			" --> line 1 on column 9\n",
+			"  |\n",
+			"1 | fun: Int f(a: Int) {\n",
+			"  |          ^\n",
+			"  |\n",
+			"  = note: The end of the body can be reached without a return statement\n",
+			"  = help: Add a return statement at the end of the function\n",
+		)],
+		diagnose("fun: Int f(a: Int) {\n\tif (a > 1) { return 1 }\n}\nreturn f(1)")
+	);
+}
+
+#[test]
+fn test_runtime_diagnostic() {
+	assert_eq!(
+		vec![concat!(
+			"error[E0501]: Division by zero\n",
+			" --> line 1 on column 10\n",
+			"  |\n",
+			"1 | return 10 / 0\n",
+			"  |           ^\n",
+		)],
+		diagnose("return 10 / 0")
+	);
+
+	assert_eq!(
+		vec![concat!(
+			"error[E0506]: No return statement found in script\n",
+			" = help: Scripts end with a return statement of their result\n",
+		)],
+		diagnose("val a = 1")
+	);
+}
+
+#[test]
+fn test_warning_diagnostic() {
+	let src = "val a = 1\nreturn 1";
+	let statements = Parser::new(Tokenizer::new(src).peekable()).parse_all().0;
+	let warnings = Linter::new(LintConfig::default()).lint(&statements);
+	let diagnostic = Diagnostic::new(&warnings[0], src);
+
+	// Warnings have no code
+	assert_eq!(None, diagnostic.code());
+	assert_eq!(
+		concat!(
+			"warning: Variable a is never used (unused-variable)\n",
+			" --> line 1 on column 4\n",
+			"  |\n",
+			"1 | val a = 1\n",
+			"  |     ^\n",
+		),
+		diagnostic.to_string()
+	);
+	assert_eq!(
+		Some(&(Style::Warning, "warning".to_string())),
+		diagnostic.render().first()
+	);
+}
+
+#[test]
+fn test_error_codes() {
+	let inputs = [
+		("return 0b12", "E0107"),
+		("val a = (1 +", "E0201"),
+		("return b", "E0301"),
+		("val a = 1\na = 2\nreturn a", "E0305"),
+		("break\nreturn 1", "E0311"),
+		("return [1][2]", "E0503"),
+		("return { 'a': 1 }['b']", "E0504"),
+	];
+
+	for (src, code) in inputs.iter() {
+		let diagnostics = diagnose(src);
+		assert!(diagnostics[0].starts_with(&format!("error[{}]", code)), "{}", src);
+	}
+
+	// Codes are set where the error is created, the message is not used
+	let error = Error::new_runtime_error("Division by zero".to_string(), None);
+	assert_eq!(Some("E0500"), Diagnostic::new(&error, "").code());
+
+	let error = error.with_code(ErrorCode::DivisionByZero);
+	assert_eq!(Some("E0501"), Diagnostic::new(&error, "").code());
+}
@@ -0,0 +1,1165 @@
+use std::collections::HashMap;
+use tutara_interpreter::{
+	parser::Parser, Analyzer, Error, ErrorCode, ErrorType, Input, Interpreter, Statement,
+	Tokenizer, Value,
+};
+
+fn resolve(src: &str) -> Result<Value, Error> {
+	let tokenizer = Tokenizer::new(src);
+	let parser = Parser::new(tokenizer.peekable());
+
+	Interpreter::new().evaluate(parser)
+}
+
+fn resolve_fail(src: &str) {
+	assert!(resolve(src).is_err());
+}
+
+#[test]
+fn test_unexpected_token() {
+	resolve_fail("@ return 1");
+}
+
+#[test]
+fn test_no_return() {
+	resolve_fail("1 + 1");
+}
+
+#[test]
+fn test_invalid_function_type() {
+	resolve_fail("fun: TYPE add(a: Int, b: Int){return a + b} return 1");
+}
+
+#[test]
+fn test_invalid_parameter_type() {
+	resolve_fail("fun: Int add(a: TYPE, b: TYPE){return a + b} return 1");
+}
+
+#[test]
+fn test_invalid_argument_type() {
+	resolve_fail("fun: Int add(a: Int, b: Int){return a + b} return add(1, true)");
+}
+
+#[test]
+fn test_invalid_argument_count() {
+	resolve_fail("fun: Int add(a: Int, b: Int){return a + b} return add(1)");
+}
+
+#[test]
+fn test_invalid_return_type() {
+	resolve_fail("fun: Int add(a: Int, b: Int){return a == b} return add(1, 2)");
+}
+
+#[test]
+fn test_unknown_function() {
+	resolve_fail("return add(1, 2)");
+}
+
+#[test]
+fn test_return_1() {
+	assert_eq!(Value::Integer(1), resolve("return 1").unwrap())
+}
+
+#[test]
+fn test_return_1_plus_1() {
+	assert_eq!(Value::Integer(2), resolve("return 1 + 1").unwrap())
+}
+
+#[test]
+fn test_return_1_plus_true() {
+	resolve_fail("return 1 + true");
+}
+
+#[test]
+fn test_return_false_minus_true() {
+	resolve_fail("return false - true");
+}
+
+#[test]
+fn test_return_1_divide_2() {
+	assert_eq!(Value::Integer(0), resolve("return 1 / 2").unwrap())
+}
+
+#[test]
+fn test_return_float_divide() {
+	assert_eq!(Value::Float(0.5), resolve("return 1.0 / 2.0").unwrap());
+	assert_eq!(Value::Float(0.25), resolve("return 2.5e-1").unwrap());
+}
+
+#[test]
+fn test_return_int_overflow() {
+	resolve_fail("return 9223372036854775807 + 1");
+	resolve_fail("return 2 ** 63");
+	resolve_fail("return 1 / 0");
+	resolve_fail("return 1 + 1.0");
+}
+
+#[test]
+fn test_minimum_int_literal() {
+	assert_eq!(
+		Value::Integer(i64::MIN),
+		resolve("return -9223372036854775808").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(i64::MIN),
+		resolve("return -0x8000000000000000").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(i64::MIN + 1),
+		resolve("return -9223372036854775808 + 1").unwrap()
+	);
+	assert_eq!(
+		Some(ErrorCode::IntegerOutOfRange),
+		resolve("return 9223372036854775808").unwrap_err().code
+	);
+	resolve_fail("return 1 - 9223372036854775808");
+	resolve_fail("return -9223372036854775809");
+}
+
+#[test]
+fn test_numeric_conversions() {
+	assert_eq!(Value::Float(9.0), resolve("return 9.toFloat()").unwrap());
+	assert_eq!(Value::Integer(-2), resolve("return (-2.75).toInt()").unwrap());
+	resolve_fail("return (1e300).toInt()");
+}
+
+#[test]
+fn test_invalid_radix_literal() {
+	resolve_fail("val a = 0b12 return a");
+	resolve_fail("val a = 0x1G return a");
+	resolve_fail("return 0b19 + 1");
+}
+
+#[test]
+fn test_return_7_rest_2() {
+	assert_eq!(Value::Integer(1), resolve("return 7 % 2").unwrap())
+}
+
+#[test]
+fn test_return_2_pow_4() {
+	assert_eq!(Value::Integer(16), resolve("return 2 ** 4").unwrap())
+}
+
+#[test]
+fn test_return_minus_3_plus_8() {
+	assert_eq!(Value::Integer(5), resolve("return -3 + 8").unwrap())
+}
+
+#[test]
+fn test_return_bool() {
+	assert_eq!(Value::Boolean(true), resolve("return !false").unwrap())
+}
+
+#[test]
+fn test_return_string() {
+	assert_eq!(
+		Value::String("foobar".to_string()),
+		resolve("return 'foo' + 'bar'").unwrap()
+	)
+}
+
+#[test]
+fn test_bool_equals_operator() {
+	let result = resolve("var a = 0 if(true == false){ a = 1 } return a");
+	assert_eq!(Value::Integer(0), result.unwrap())
+}
+
+#[test]
+fn test_int_not_equals_operator() {
+	let result = resolve("var a = 0 if(12 != 24){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
+}
+
+#[test]
+fn test_int_lesser_or_equal_operator_equal() {
+	let result = resolve("var a = 0 if(5 <= 5){ a = 1 } return a");
+	assert_eq!(Value::Integer(1), result.unwrap())
+}
+
+#[test]
+fn test_nested_loop_assignment_operator() {
+	let result = resolve("var a = 0 loop { a += 2 if (a >= 10) { break } } return a");
+	assert_eq!(Value::Integer(10), result.unwrap())
+}
+
+#[test]
+fn test_return_from_loop() {
+	let result = resolve("var a = 0 while (true) { a += 1 if (a == 3) { return a } }");
+	assert_eq!(Value::Integer(3), result.unwrap())
+}
+
+#[test]
+fn test_recursive_function() {
+	let result = resolve(
+		"fun: Int fib(n: Int) { if (n <= 1) { return n } return fib(n - 1) + fib(n - 2) } return fib(10)",
+	);
+	assert_eq!(Value::Integer(55), result.unwrap())
+}
+
+#[test]
+fn test_call_depth() {
+	let error = resolve("fun: Int r(n: Int) { return r(n + 1) } return r(0)").unwrap_err();
+	assert_eq!("Maximum call depth of 64 exceeded", error.message);
+
+	let result = resolve(
+		"fun: Int count(n: Int) { if (n == 0) { return 0 } return count(n - 1) + 1 } return count(50)",
+	);
+	assert_eq!(Value::Integer(50), result.unwrap())
+}
+
+#[test]
+fn test_max_call_depth() {
+	let src = "fun: Int count(n: Int) { if (n == 0) { return 0 } return count(n - 1) + 1 } return count(1000)";
+
+	let error = resolve(src).unwrap_err();
+	assert_eq!("Maximum call depth of 64 exceeded", error.message);
+
+	// Deeper calls need a thread with a larger stack
+	let result = std::thread::Builder::new()
+		.stack_size(256 * 1024 * 1024)
+		.spawn(move || {
+			let parser = Parser::new(Tokenizer::new(src).peekable());
+			let result = Interpreter::new()
+				.with_max_call_depth(2000)
+				.evaluate(parser);
+
+			result
+				.map(|value| value.to_string())
+				.map_err(|error| error.message)
+		})
+		.unwrap()
+		.join()
+		.unwrap();
+	assert_eq!(Ok("1000".to_string()), result);
+}
+
+#[test]
+fn test_function_scope() {
+	resolve_fail("val a = 1 fun: Int get() { return a } return get()");
+}
+
+#[test]
+fn test_forward_call() {
+	let result = resolve("val a = double(2) fun: Int double(n: Int) { return n * 2 } return a");
+	assert_eq!(Value::Integer(4), result.unwrap())
+}
+
+#[test]
+fn test_mutual_recursion() {
+	let result = resolve(
+		"fun: Bool isEven(n: Int) { if (n == 0) { return true } return isOdd(n - 1) }
+		fun: Bool isOdd(n: Int) { if (n == 0) { return false } return isEven(n - 1) }
+		return isEven(10)",
+	);
+	assert_eq!(Value::Boolean(true), result.unwrap())
+}
+
+#[test]
+fn test_block_scope() {
+	resolve_fail("if (true) { val a = 1 } return a");
+}
+
+#[test]
+fn test_mismatched_assignment() {
+	resolve_fail("var a = 1 a = true return a");
+}
+
+#[test]
+fn test_unexpected_continue() {
+	resolve_fail("continue return 1");
+}
+
+#[test]
+fn test_unexpected_break() {
+	resolve_fail("break return 1");
+}
+
+#[test]
+fn test_unexpected_break_in_function() {
+	resolve_fail("fun foo() { break } while (true) { foo() } return 1");
+}
+
+#[test]
+fn test_invalid_while_condition() {
+	resolve_fail("while(1){} return 1");
+}
+
+#[test]
+fn test_invalid_if_condition() {
+	resolve_fail("if(1){} return 1");
+}
+
+#[test]
+fn test_invalid_declaration() {
+	resolve_fail("val a = if");
+}
+
+#[test]
+fn test_invalid_call() {
+	resolve_fail("1()");
+}
+
+#[test]
+fn test_print_output() {
+	let parser = Parser::new(Tokenizer::new("print('a', 1) print(true) return 1").peekable());
+	let mut interpreter = Interpreter::new();
+
+	assert_eq!(Value::Integer(1), interpreter.evaluate(parser).unwrap());
+	assert_eq!(&["a 1".to_string(), "true".to_string()], interpreter.output());
+}
+
+#[test]
+fn test_inputs() {
+	let parser = Parser::new(Tokenizer::new("return a + 1").peekable());
+	let mut inputs = HashMap::new();
+	inputs.insert("a".to_string(), Value::Integer(2));
+
+	let result = Interpreter::with_inputs(inputs).evaluate(parser);
+	assert_eq!(Value::Integer(3), result.unwrap());
+}
+
+#[test]
+fn test_input_values() {
+	let input: Input = serde_json::from_str("[0, 10, 0, false]").unwrap();
+	assert_eq!("[0, 10, 0, false]", Value::from(input.clone()).to_string());
+	assert!(matches!(Value::from(input), Value::Array(array) if array.borrow().len() == 4));
+
+	let input: Input = serde_json::from_str(r#"{"k": 1, "a": [1.5, "b"]}"#).unwrap();
+	assert_eq!(
+		Input::Map(vec![
+			("k".to_string(), Input::Integer(1)),
+			(
+				"a".to_string(),
+				Input::Array(vec![Input::Float(1.5), Input::String("b".to_string())])
+			),
+		]),
+		input
+	);
+
+	let input: Input = serde_json::from_str(r#"{"k": 1, "j": 2}"#).unwrap();
+	let parser = Parser::new(Tokenizer::new("return m['k'] + m['j']").peekable());
+	let mut inputs = HashMap::new();
+	inputs.insert("m".to_string(), Value::from(input));
+	assert_eq!(
+		Value::Integer(3),
+		Interpreter::with_inputs(inputs).evaluate(parser).unwrap()
+	);
+
+	assert!(serde_json::from_str::<Input>("null").is_err());
+}
+
+#[test]
+fn test_for_loop() {
+	assert_eq!(
+		Value::String("o-l-l-e-h-".to_string()),
+		resolve("var a = '' for (c in 'hello') { a = c + '-' + a } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_for_loop_break_continue() {
+	assert_eq!(
+		Value::String("heo".to_string()),
+		resolve("var a = '' for (c in 'hello!world') { if (c == 'l') { continue } if (c == '!') { break } a = a + c } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_for_loop_scope() {
+	resolve_fail("for (c in 'a') {} return c");
+}
+
+#[test]
+fn test_invalid_for_iterable() {
+	resolve_fail("for (c in true) {} return 1");
+}
+
+#[test]
+fn test_for_range() {
+	assert_eq!(
+		Value::Integer(45),
+		resolve("var a = 0 for (i in 0..10) { a += i } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_for_inclusive_range() {
+	assert_eq!(
+		Value::Integer(55),
+		resolve("var a = 0 for (i in 0..=10) { a += i } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_for_range_step() {
+	assert_eq!(
+		Value::Integer(20),
+		resolve("var a = 0 for (i in (0..10).step(2)) { a += i } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_range_variable() {
+	assert_eq!(
+		Value::Integer(3),
+		resolve("val r = 1..3 var a = 0 for (i in r) { a += 1 } for (i in r) { a += 1 } return a - 1").unwrap()
+	)
+}
+
+#[test]
+fn test_return_range() {
+	assert_eq!("(0..=10).step(5)", resolve("return (0..=10).step(5)").unwrap().to_string())
+}
+
+#[test]
+fn test_invalid_range_step() {
+	resolve_fail("return (0..10).step(0)");
+}
+
+#[test]
+fn test_invalid_range_bounds() {
+	resolve_fail("return 0..'a'");
+}
+
+#[test]
+fn test_match_expression() {
+	let src = "fun: String size(n: Int) { return match (n) { 0 -> 'none', 1, 2 -> 'few', 3..=9 -> 'some', else -> 'many' } }";
+
+	for (n, expected) in [(0, "none"), (2, "few"), (9, "some"), (10, "many")].iter() {
+		assert_eq!(
+			Value::String(expected.to_string()),
+			resolve(&format!("{} return size({})", src, n)).unwrap()
+		)
+	}
+}
+
+#[test]
+fn test_match_guard() {
+	assert_eq!(
+		Value::String("odd".to_string()),
+		resolve("val a = 3 return match (a) { 0..10 if a % 2 == 0 -> 'even', 0..10 -> 'odd', else -> 'big' }").unwrap()
+	)
+}
+
+#[test]
+fn test_match_statement() {
+	assert_eq!(
+		Value::Integer(12),
+		resolve("var a = 0 for (c in 'a-b') { match (c) { '-' -> { continue } else -> a += 6 } } return a").unwrap()
+	)
+}
+
+#[test]
+fn test_match_statement_return() {
+	assert_eq!(
+		Value::Boolean(true),
+		resolve("match (true) { true -> return true, else -> {} } return false").unwrap()
+	)
+}
+
+#[test]
+fn test_invalid_match_pattern() {
+	resolve_fail("return match (1) { 'a' -> 1, else -> 2 }");
+}
+
+#[test]
+fn test_array_index() {
+	assert_eq!(
+		Value::Integer(2),
+		resolve("val xs = [1, 2, 3] return xs[1]").unwrap()
+	)
+}
+
+#[test]
+fn test_array_assignment() {
+	assert_eq!(
+		"[10, 7, 3]",
+		resolve("var xs = [1, 2, 3] xs[0] = 10 xs[1] += 5 return xs")
+			.unwrap()
+			.to_string()
+	)
+}
+
+#[test]
+fn test_array_methods() {
+	assert_eq!(
+		Value::Integer(7),
+		resolve(
+			"val xs = [1] xs.push(2) xs.push(3) val last = xs.pop() return last + xs.length * 2"
+		)
+		.unwrap()
+	)
+}
+
+#[test]
+fn test_array_parameter() {
+	assert_eq!(
+		Value::Integer(6),
+		resolve("fun: Int sum(xs: Array<Int>) { var a = 0 for (x in xs) { a += x } return a } return sum([1, 2, 3])").unwrap()
+	)
+}
+
+#[test]
+fn test_array_out_of_bounds() {
+	resolve_fail("val xs = [1, 2] return xs[2]");
+	resolve_fail("var xs = [1, 2] xs[-1] = 0 return xs");
+}
+
+#[test]
+fn test_invalid_array_elements() {
+	resolve_fail("return [1, 'a']");
+	resolve_fail("val xs = [1] xs.push('a') return xs");
+}
+
+#[test]
+fn test_map_index() {
+	assert_eq!(
+		Value::Integer(2),
+		resolve("val m = { 'a': 1, 'b': 2 } return m['b']").unwrap()
+	)
+}
+
+#[test]
+fn test_map_assignment() {
+	assert_eq!(
+		"{a: 11, b: 2}",
+		resolve("var: Map<String, Int> m = {} m['a'] = 1 m['b'] = 2 m['a'] += 10 return m")
+			.unwrap()
+			.to_string()
+	)
+}
+
+#[test]
+fn test_map_methods() {
+	assert_eq!(
+		Value::Integer(21),
+		resolve("val m = { 1: 10, 2: 20 } val removed = m.remove(1) if (m.contains(1)) { return 0 } return removed + m.length * 11").unwrap()
+	)
+}
+
+#[test]
+fn test_map_iteration() {
+	assert_eq!(
+		Value::String("ab".to_string()),
+		resolve("val m = { 'a': 1, 'b': 2 } var s = '' var n = 0 for (k in m.keys()) { s += k } for (v in m.values()) { n += v } if (n == 3) { return s } return ''").unwrap()
+	)
+}
+
+#[test]
+fn test_map_parameter() {
+	assert_eq!(
+		Value::Integer(3),
+		resolve("fun: Int count(m: Map<String, Int>) { return m.length } return count({ 'a': 1, 'b': 2, 'c': 3 })").unwrap()
+	)
+}
+
+#[test]
+fn test_invalid_maps() {
+	resolve_fail("val m = { 'a': 1 } return m['b']");
+	resolve_fail("val m = { 'a': 1 } m.remove('b') return m");
+	resolve_fail("return { 'a': 1, 'b': 'c' }");
+	resolve_fail("return { [1]: 1 }");
+}
+
+#[test]
+fn test_record_field() {
+	assert_eq!(
+		Value::Integer(3),
+		resolve("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } return p.x + p.y").unwrap()
+	)
+}
+
+#[test]
+fn test_record_field_assignment() {
+	assert_eq!(
+		"Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 14 } }",
+		resolve("type Point { x: Int, y: Int } type Line { start: Point, end: Point } var line = Line { start: Point { x: 1, y: 2 }, end: Point { y: 4, x: 3 } } line.end.y = 10 line.end.y += 4 return line")
+			.unwrap()
+			.to_string()
+	)
+}
+
+#[test]
+fn test_record_value_semantics() {
+	assert_eq!(
+		Value::Integer(1),
+		resolve("type Point { x: Int, y: Int } val a = Point { x: 1, y: 2 } var b = a b.x = 5 return a.x").unwrap()
+	)
+}
+
+#[test]
+fn test_val_record_field_assignment() {
+	resolve_fail("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } p.x = 5 return p.x");
+	resolve_fail("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } p.x += 5 return p.x");
+	resolve_fail("type Point { x: Int, y: Int } type Line { start: Point, end: Point } val line = Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 4 } } line.end.y = 10 return 1");
+
+	match resolve("type Point { x: Int, y: Int }\nval p = Point { x: 1, y: 2 }\np.x = 5\nreturn p.x").unwrap_err() {
+		Error {
+			r#type: ErrorType::Semantic(span),
+			message,
+			..
+		} => {
+			assert_eq!((3, 0), (span.line, span.column));
+			assert_eq!("Can not assign to val p declared at line 2 on column 4", message);
+		}
+		error => panic!("Unexpected error {:?}", error),
+	}
+
+	assert_eq!(
+		Value::Integer(5),
+		resolve("type Point { x: Int, y: Int } fun: Int f(p: Point) { p.x = 5 return p.x } return f(Point { x: 1, y: 2 })").unwrap()
+	);
+}
+
+#[test]
+fn test_element_field_assignment() {
+	assert_eq!(
+		"[Point { x: 5, y: 2 }]",
+		resolve("type Point { x: Int, y: Int } val points = [Point { x: 1, y: 2 }] points[0].x = 5 return points")
+			.unwrap()
+			.to_string()
+	);
+	assert_eq!(
+		"[Point { x: 1, y: 7 }]",
+		resolve("type Point { x: Int, y: Int } var points = [Point { x: 1, y: 2 }] points[0].y += 5 return points")
+			.unwrap()
+			.to_string()
+	);
+	assert_eq!(
+		"Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 9 } }",
+		resolve("type Point { x: Int, y: Int } type Line { start: Point, end: Point } val lines = { 'a': Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 4 } } } lines['a'].end.y = 9 return lines['a']")
+			.unwrap()
+			.to_string()
+	);
+	assert_eq!(
+		Value::Integer(1),
+		resolve("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } val points = [p] points[0].x = 5 return p.x").unwrap()
+	);
+
+	resolve_fail("type Point { x: Int, y: Int } val points = [Point { x: 1, y: 2 }] points[1].x = 5 return 1");
+	resolve_fail("type Point { x: Int, y: Int } val points = { 'a': Point { x: 1, y: 2 } } points['b'].x = 5 return 1");
+	resolve_fail("type Point { x: Int, y: Int } type Line { start: Point, end: Point } val lines = [Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 4 } }] val line = lines[0] line.end.y = 9 return 1");
+}
+
+#[test]
+fn test_record_equality() {
+	assert_eq!(
+		Value::Boolean(true),
+		resolve("type Point { x: Int, y: Int } val a = Point { x: 1, y: 2 } return a == Point { y: 2, x: 1 } && a != Point { x: 2, y: 2 }").unwrap()
+	)
+}
+
+#[test]
+fn test_record_parameter() {
+	assert_eq!(
+		Value::Integer(6),
+		resolve("type Point { x: Int, y: Int } fun: Point shift(p: Point) { var q = p q.x += 5 return q } return shift(Point { x: 1, y: 2 }).x").unwrap()
+	)
+}
+
+#[test]
+fn test_invalid_records() {
+	resolve_fail("type Point { x: Int, y: Int } return Point { x: 1 }");
+	resolve_fail("type Point { x: Int, y: Int } return Point { x: 1, y: 2, z: 3 }");
+	resolve_fail("type Point { x: Int, y: Int } return Point { x: 'a', y: 2 }");
+	resolve_fail("type Point { x: Int, y: Int } var p = Point { x: 1, y: 2 } p.x = 'a' return p");
+	resolve_fail("type Point { x: Int, y: Int } val p = Point { x: 1, y: 2 } return p.z");
+	resolve_fail("type Line { start: Point } return 1");
+	resolve_fail("type Int { x: Int } return 1");
+	resolve_fail("return Point { x: 1 }");
+}
+
+#[test]
+fn test_enum_match() {
+	let src = "enum Status { Ok(Int), NotFound, Failed(String) } fun: String describe(status: Status) { return match (status) { Status.Ok(n) if n > 9 -> 'big', Status.Ok(n) -> 'ok', Status.NotFound -> 'missing', Status.Failed(message) -> message } }";
+
+	for (status, expected) in [
+		("Status.Ok(10)", "big"),
+		("Status.Ok(1)", "ok"),
+		("Status.NotFound", "missing"),
+		("Status.Failed('timeout')", "timeout"),
+	]
+	.iter()
+	{
+		assert_eq!(
+			Value::String(expected.to_string()),
+			resolve(&format!("{} return describe({})", src, status)).unwrap()
+		)
+	}
+}
+
+#[test]
+fn test_enum_match_statement() {
+	assert_eq!(
+		Value::Integer(5),
+		resolve("enum Shape { Circle(Int), Square(Int, Int) } val s = Shape.Square(2, 3) match (s) { Shape.Circle(r) -> return r, Shape.Square(w, h) -> return w + h }").unwrap()
+	)
+}
+
+#[test]
+fn test_enum_value() {
+	assert_eq!(
+		"Status.Failed(timeout)",
+		resolve("enum Status { Ok(Int), NotFound, Failed(String) } val s = Status.Failed('timeout') return s")
+			.unwrap()
+			.to_string()
+	);
+	assert_eq!(
+		Value::Boolean(true),
+		resolve("enum Status { Ok(Int), NotFound } return Status.Ok(1) == Status.Ok(1) && Status.Ok(1) != Status.NotFound").unwrap()
+	)
+}
+
+#[test]
+fn test_invalid_enums() {
+	resolve_fail("enum Status { Ok(Int), NotFound } return Status.Ok('a')");
+	resolve_fail("enum Status { Ok(Int), NotFound } return Status.Ok");
+	resolve_fail("enum Status { Ok(Int), NotFound } return Status.Missing");
+	resolve_fail("enum Status { Ok, Ok } return 1");
+	resolve_fail("type Status { x: Int } enum Status { Ok } return 1");
+	resolve_fail("enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(n) -> n }");
+	resolve_fail("enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(n) if n > 1 -> n, Status.NotFound -> 0 }");
+	resolve_fail("enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok -> 1, else -> 0 }");
+	resolve_fail("enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(n), Status.NotFound -> 1 }");
+	resolve_fail("return match (1) { 1 -> 1 }");
+}
+
+#[test]
+fn test_duplicate_declarations() {
+	let sources = [
+		"type P { x: Int } type P { y: Int } return 1",
+		"enum P { A } type P { y: Int } return 1",
+		"fun: Int f() { return 1 } fun: Int f() { return 2 } return f()",
+		"type P { x: Int, x: Int } return 1",
+		"enum S { A, A } return 1",
+	];
+
+	let errors: Vec<(u32, u32, Option<ErrorCode>)> = sources
+		.iter()
+		.map(|src| {
+			let error = resolve(src).unwrap_err();
+
+			match error.r#type {
+				ErrorType::Semantic(span) => (span.line, span.column, error.code),
+				r#type => panic!("Unexpected error {:?}", r#type),
+			}
+		})
+		.collect();
+
+	assert_eq!(
+		vec![
+			(1, 23, Some(ErrorCode::DuplicateDeclaration)),
+			(1, 18, Some(ErrorCode::DuplicateDeclaration)),
+			(1, 35, Some(ErrorCode::DuplicateDeclaration)),
+			(1, 17, Some(ErrorCode::DuplicateDeclaration)),
+			(1, 12, Some(ErrorCode::DuplicateDeclaration)),
+		],
+		errors
+	);
+}
+
+#[test]
+fn test_non_exhaustive_match_error() {
+	let sources = [
+		"enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(n) -> n }",
+		"val a = 1 return match (a) { 1 -> 1 }",
+	];
+
+	let positions: Vec<(u32, u32)> = sources
+		.iter()
+		.map(|src| match resolve(src).unwrap_err().r#type {
+			ErrorType::Semantic(span) => (span.line, span.column),
+			r#type => panic!("Unexpected error {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(vec![(1, 41), (1, 17)], positions);
+}
+
+#[test]
+fn test_variant_pattern_errors() {
+	let sources = [
+		"enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Missing -> 1, else -> 0 }",
+		"enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok -> 1, else -> 0 }",
+		"enum Status { Ok(Int), NotFound } return match (Status.NotFound) { Status.Ok(1) -> 1, else -> 0 }",
+	];
+
+	let errors: Vec<(u32, u32, Option<ErrorCode>)> = sources
+		.iter()
+		.map(|src| {
+			let error = resolve(src).unwrap_err();
+
+			match error.r#type {
+				ErrorType::Semantic(span) => (span.line, span.column, error.code),
+				r#type => panic!("Unexpected error {:?}", r#type),
+			}
+		})
+		.collect();
+
+	assert_eq!(
+		vec![
+			(1, 74, Some(ErrorCode::UnknownMember)),
+			(1, 74, Some(ErrorCode::ArgumentCount)),
+			(1, 74, Some(ErrorCode::ExpectedIdentifier)),
+		],
+		errors
+	);
+}
+
+#[test]
+fn test_lambda_call() {
+	assert_eq!(
+		Value::Integer(5),
+		resolve("val add = (a: Int, b: Int) -> a + b return add(2, 3)").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(42),
+		resolve("return ((a: Int) -> a * 2)(21)").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(6),
+		resolve("val f = (n: Int) -> { var total = 0 for (i in 1..=n) { total += i } return total } return f(3)").unwrap()
+	)
+}
+
+#[test]
+fn test_lambda_parameter() {
+	let src = "fun: Array<Int> map(xs: Array<Int>, f: Fun<Int, Int>) { var: Array<Int> ys = [] for (x in xs) { ys.push(f(x)) } return ys }";
+
+	assert_eq!(
+		"[1, 4, 9]",
+		resolve(&format!("{} return map([1, 2, 3], (x: Int) -> x * x)", src))
+			.unwrap()
+			.to_string()
+	)
+}
+
+#[test]
+fn test_lambda_captures() {
+	assert_eq!(
+		Value::Integer(11),
+		resolve("val offset = 10 val add = (a: Int) -> a + offset return add(1)").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(3),
+		resolve("var count = 0 val increment = () -> { count += 1 } increment() increment() increment() return count").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(2),
+		resolve("fun: Fun<Int> counter() { var count = 0 return () -> { count += 1 return count } } val next = counter() next() return next()").unwrap()
+	)
+}
+
+#[test]
+fn test_invalid_lambdas() {
+	resolve_fail("val add = (a: Int, b: Int) -> a + b return add(1)");
+	resolve_fail("val add = (a: Int) -> a return add('a')");
+	resolve_fail("val a = 1 return a(1)");
+	resolve_fail("fun: Int apply(f: Fun<Int, Int>) { return f(1) } return apply((a: String) -> a)");
+	resolve_fail("val f = (a: Unknown) -> a return 1");
+}
+
+#[test]
+fn test_lambda_returns() {
+	let sources = [
+		"val f = (a: Int) -> { if (a > 1) { return 1 } return 'a' } return 1",
+		"val f = (a: Int) -> { if (a > 1) { return 1 } } return 1",
+	];
+
+	let errors: Vec<(u32, u32, Option<ErrorCode>)> = sources
+		.iter()
+		.map(|src| {
+			let error = resolve(src).unwrap_err();
+
+			match error.r#type {
+				ErrorType::Semantic(span) => (span.line, span.column, error.code),
+				r#type => panic!("Unexpected error {:?}", r#type),
+			}
+		})
+		.collect();
+
+	assert_eq!(
+		vec![
+			(1, 53, Some(ErrorCode::Semantic)),
+			(1, 8, Some(ErrorCode::MissingReturn)),
+		],
+		errors
+	);
+	assert_eq!(
+		Value::Integer(1),
+		resolve("val f = (a: Int) -> { if (a > 1) { return 1 } return 0 } return f(2)").unwrap()
+	);
+}
+
+#[test]
+fn test_template() {
+	assert_eq!(
+		Value::String("foo 6 1.5 true $a".to_string()),
+		resolve("val a = 'foo' val b = 2 return '$a ${b * 3} ${1.5} ${b > 1} \\$a'").unwrap()
+	);
+	assert_eq!(
+		Value::String("x-y".to_string()),
+		resolve("val a = 'y' return '${'x-${a}'}'").unwrap()
+	);
+}
+
+#[test]
+fn test_multiline_string() {
+	assert_eq!(
+		Value::String("<p>\n\tfoo\n</p>".to_string()),
+		resolve("val a = 'foo'\nreturn '''\n\t<p>\n\t\t$a\n\t</p>\n\t'''").unwrap()
+	);
+	assert_eq!(
+		Value::String("\\d+$a".to_string()),
+		resolve("return r'\\d+$a'").unwrap()
+	);
+}
+
+#[test]
+fn test_invalid_templates() {
+	resolve_fail("val a = [1] return '$a'");
+	resolve_fail("return '$unknown'");
+
+	match resolve("val b = 'xy ${1 @}'").unwrap_err().r#type {
+		ErrorType::Lexical(line, column, _) => assert_eq!((1, 16), (line, column)),
+		r#type => panic!("Unexpected error {:?}", r#type),
+	}
+}
+
+#[test]
+fn test_comments() {
+	assert_eq!(
+		Value::Integer(3),
+		resolve(
+			"/* a /* nested */\ncomment */\n/// Doc\nfun: Int f() { return 3 }\nreturn f() // done"
+		)
+		.unwrap()
+	);
+}
+
+#[test]
+fn test_comments_in_expressions() {
+	assert_eq!(
+		Value::Integer(3),
+		resolve("val a = 1 /* one */ + 2 return a").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(4),
+		resolve("return 8 / /* c */ 2").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(5),
+		resolve("fun: Int add(a: Int, b: Int) { return a + b }\nreturn add(/* a */ 2,\n/* b\n*/ 3)").unwrap()
+	);
+}
+
+#[test]
+fn test_val_assignment() {
+	resolve_fail("val a = 1 a = 2 return a");
+	resolve_fail("val a = 1 a += 2 return a");
+	resolve_fail("val a = 1 if (true) { a = 2 } return a");
+	resolve_fail("val a = 1 val f = () -> { a = 2 } return a");
+	resolve_fail("for (i in 0..2) { i = 3 } return 1");
+
+	match resolve("val a = 1\nif (true) {\n\ta = 2\n}\nreturn a").unwrap_err() {
+		Error {
+			r#type: ErrorType::Semantic(span),
+			message,
+			..
+		} => {
+			assert_eq!((3, 1), (span.line, span.column));
+			assert_eq!("Can not assign to val a declared at line 1 on column 4", message);
+		}
+		error => panic!("Unexpected error {:?}", error),
+	}
+
+	assert_eq!(
+		Value::Integer(3),
+		resolve("var a = 1 a += 2 fun: Int f(a: Int) { a = 3 return a } return f(a)").unwrap()
+	);
+	assert_eq!(
+		Value::Integer(2),
+		resolve("val a = 1 if (true) { var a = 1 a = 2 return a } return a").unwrap()
+	);
+}
+
+#[test]
+fn test_declared_types() {
+	resolve_fail("val: Bool x = 3 return x");
+	resolve_fail("val a = 1 val: String x = a + 1 return x");
+	resolve_fail("var: Float a = 1.5 a = 2 return a");
+	resolve_fail("fun: Int f() { return 1 } val: Bool x = f() return x");
+
+	assert_eq!(
+		Value::Float(2.5),
+		resolve("var: Float a = 1.5 a = a + 1.0 return a").unwrap()
+	);
+}
+
+fn check(src: &str) -> Vec<Error> {
+	let tokenizer = Tokenizer::new(src);
+	let statements: Vec<Statement> = Parser::new(tokenizer.peekable())
+		.collect::<Result<Vec<Statement>, Error>>()
+		.unwrap();
+
+	Analyzer::new().check(&statements)
+}
+
+#[test]
+fn test_type_errors() {
+	resolve_fail("fun: Int f(a: Int) { return a } return f(1, 2)");
+	resolve_fail("fun: Int f(a: Int) { return a } return f('a')");
+	resolve_fail("fun: Int f() { return 'a' } return 1");
+	resolve_fail("fun f() { return 1 } return 1");
+	resolve_fail("val f = (a: Int) -> a return f(true)");
+	resolve_fail("if (1) { return 1 } return 2");
+	resolve_fail("return 1 val a = 1 + 'a'");
+	resolve_fail("enum Color { Red, Custom(Int) } return Color.Custom('a')");
+	resolve_fail("type Point { x: Int } val p = Point { x: 1 } return p.y");
+	resolve_fail("val a = [1, 2] return a['a']");
+	resolve_fail("val a = 1 return a.pop()");
+
+	match resolve("val a = 1
+while (a) {
+}
+return a").unwrap_err() {
+		Error {
+			r#type: ErrorType::Semantic(span),
+			message,
+			..
+		} => {
+			assert_eq!((2, 7), (span.line, span.column));
+			assert_eq!("Unsupported type Int in condition", message);
+		}
+		error => panic!("Unexpected error {:?}", error),
+	}
+}
+
+#[test]
+fn test_every_type_error() {
+	let errors = check("fun: Int f(a: Int) { return true }
+val b = f('a')
+return -true");
+
+	let positions: Vec<(u32, u32)> = errors
+		.iter()
+		.map(|error| match &error.r#type {
+			ErrorType::Semantic(span) => (span.line, span.column),
+			r#type => panic!("Unexpected error {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(vec![(1, 28), (2, 9), (3, 7)], positions);
+	assert_eq!(
+		"Function f should return Int but returned Bool",
+		errors[0].message
+	);
+	assert_eq!("Expected Int for parameter a but got String", errors[1].message);
+	assert_eq!("Unsupported operation Minus on Bool", errors[2].message);
+}
+
+#[test]
+fn test_inferred_types() {
+	assert!(check(
+		"enum Shape { Circle(Float), Square(Float) }
+		type Box { shape: Shape, tags: Array<String> }
+		fun: Float area(shape: Shape) {
+			return match (shape) {
+				Shape.Circle(radius) -> radius * radius * 3.14,
+				Shape.Square(side) -> side * side,
+			}
+		}
+		val boxes = [Box { shape: Shape.Circle(1.0), tags: [] }]
+		val counts = { 'a': 1 }
+		val double = (a: Int) -> a * 2
+		var total = 0.0
+		for (box in boxes) {
+			total += area(box.shape) + double(counts['a']).toFloat()
+		}
+		return total"
+	)
+	.is_empty());
+}
+
+#[test]
+fn test_unknown_identifiers() {
+	let errors = check("val a = 1
+if (true) { val b = 2 }
+return a + b + c(a)");
+
+	let positions: Vec<(u32, u32)> = errors
+		.iter()
+		.map(|error| match &error.r#type {
+			ErrorType::Semantic(span) => (span.line, span.column),
+			r#type => panic!("Unexpected error {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(vec![(3, 11), (3, 15)], positions);
+	assert_eq!("Unknown variable b", errors[0].message);
+	assert_eq!("Unknown function c", errors[1].message);
+}
+
+#[test]
+fn test_control_flow_errors() {
+	let errors = check("fun: Int sign(n: Int) {
+	if (n > 0) { return 1 } else { if (n < 0) { return 0 - 1 } }
+}
+break
+while (true) { val f = () -> { continue } }");
+
+	let positions: Vec<(u32, u32)> = errors
+		.iter()
+		.map(|error| match &error.r#type {
+			ErrorType::Semantic(span) => (span.line, span.column),
+			r#type => panic!("Unexpected error {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(vec![(1, 9), (4, 0), (5, 31)], positions);
+	assert_eq!("Function sign does not return Int on every path", errors[0].message);
+	assert_eq!("Unable to break outside of a loop", errors[1].message);
+	assert_eq!("Unable to continue outside of a loop", errors[2].message);
+}
+
+#[test]
+fn test_returns_on_every_path() {
+	assert!(check(
+		"fun: Int sign(n: Int) { if (n > 0) { return 1 } else { return 0 } }
+		fun: Int first(xs: Array<Int>) { loop { for (x in xs) { return x } } }
+		fun: Int count() { var a = 0 while (true) { a += 1 if (a > 9) { return a } } }
+		return sign(1)"
+	)
+	.is_empty());
+}
+
+#[test]
+fn test_unreachable_statements() {
+	let tokenizer = Tokenizer::new(
+		"var a = 0
+while (a < 10) {
+	a += 1
+	continue
+	print(a)
+}
+return a
+a = 1",
+	);
+	let statements: Vec<Statement> = Parser::new(tokenizer.peekable())
+		.collect::<Result<Vec<Statement>, Error>>()
+		.unwrap();
+
+	let mut analyzer = Analyzer::new();
+	assert!(analyzer.check(&statements).is_empty());
+
+	let positions: Vec<(u32, u32)> = analyzer
+		.take_warnings()
+		.iter()
+		.map(|warning| match &warning.r#type {
+			ErrorType::Semantic(span) => (span.line, span.column),
+			r#type => panic!("Unexpected warning {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(vec![(5, 1), (8, 0)], positions);
+}
@@ -0,0 +1,102 @@
+use tutara_interpreter::{
+	parser::Parser, Error, ErrorType, Level, LintConfig, Linter, Rule, Severity, Statement,
+	Tokenizer,
+};
+
+fn lint_with(src: &str, config: LintConfig) -> Vec<Error> {
+	let tokenizer = Tokenizer::new(src);
+	let statements: Vec<Statement> = Parser::new(tokenizer.peekable())
+		.collect::<Result<Vec<Statement>, Error>>()
+		.unwrap();
+
+	Linter::new(config).lint(&statements)
+}
+
+fn lint(src: &str) -> Vec<String> {
+	lint_with(src, LintConfig::default())
+		.into_iter()
+		.map(|diagnostic| diagnostic.message)
+		.collect()
+}
+
+#[test]
+fn test_unused_bindings() {
+	assert_eq!(
+		vec![
+			"Parameter b is never used (unused-parameter)",
+			"Variable a is never used (unused-variable)",
+		],
+		lint("val a = 1 fun: Int f(a: Int, b: Int) { return a } return f(1, 2)")
+	);
+}
+
+#[test]
+fn test_shadowed_binding() {
+	assert_eq!(
+		vec!["a shadows the declaration at line 1 on column 4 (shadowed-binding)"],
+		lint("val a = 1 val f = (a: Int) -> a return f(a)")
+	);
+}
+
+#[test]
+fn test_unnecessary_var() {
+	assert_eq!(
+		vec!["Variable a is never reassigned, use val (unnecessary-var)"],
+		lint("var a = 1 var b = 1 b += a var c = [] c.push(b) return c")
+	);
+}
+
+#[test]
+fn test_constant_condition() {
+	assert_eq!(
+		vec![
+			"Condition is always false (constant-condition)",
+			"Condition is constant (constant-condition)",
+			"Condition is always true, use loop instead (constant-condition)",
+		],
+		lint("if (!true) { } if (1 < 2) { } while (true) { break } return 1")
+	);
+}
+
+#[test]
+fn test_self_assignment() {
+	assert_eq!(
+		vec![
+			"Value is assigned to itself (self-assignment)",
+			"Value is assigned to itself (self-assignment)",
+		],
+		lint("type Point { x: Int } var a = 1 a = a var p = Point { x: a } p.x = p.x return p")
+	);
+}
+
+#[test]
+fn test_boolean_comparison() {
+	assert_eq!(
+		vec![
+			"Comparison to true can be simplified (boolean-comparison)",
+			"Comparison to false can be simplified (boolean-comparison)",
+		],
+		lint("val a = true val b = a == true return false != b")
+	);
+}
+
+#[test]
+fn test_match_bindings() {
+	assert!(lint(
+		"enum Lookup { Found(Int), NotFound } val l = Lookup.Found(1) return match (l) { Lookup.Found(n) -> n, Lookup.NotFound -> 0 }"
+	)
+	.is_empty());
+}
+
+#[test]
+fn test_rule_levels() {
+	let mut config = LintConfig::default();
+	config.rules.insert(Rule::UnusedVariable, Level::Error);
+	config.rules.insert(Rule::ConstantCondition, Level::Off);
+
+	let diagnostics = lint_with("val a = 1 if (true) { } return 1", config);
+
+	assert_eq!(1, diagnostics.len());
+	assert_eq!(Severity::Error, diagnostics[0].severity);
+	assert!(matches!(&diagnostics[0].r#type, ErrorType::Semantic(span) if (span.line, span.column) == (1, 4)));
+}
@@ -3,3 +3,9 @@ pub use tokenizer_tests::*;
 
 mod parser_tests;
 pub use parser_tests::*;
+
+mod interpreter_tests;
+
+mod linter_tests;
+
+mod diagnostic_tests;
@@ -1,4 +1,4 @@
-use tutara_interpreter::{Expression, Literal, parser::Parser, Statement, Token, TokenType, Tokenizer};
+use tutara_interpreter::{ErrorType, Expression, Literal, parser::Parser, Span, Statement, Token, TokenType, Tokenizer};
 
 use Expression::*;
 use Statement::*;
@@ -32,10 +32,11 @@ fn test_create_expression_literal_number() {
 		"1000",
 		Expression(Literal(Token::new(
 			TokenType::Integer,
-			Some(Literal::Number(1000)),
+			Some(Literal::Integer(1000)),
 			1,
 			0,
 			4,
+			0,
 		))),
 	)
 }
@@ -62,6 +63,7 @@ fn test_create_expression_literal_string() {
 			1,
 			0,
 			8,
+			0,
 		))),
 	)
 }
@@ -76,6 +78,7 @@ fn test_create_expression_identifier() {
 			1,
 			0,
 			3,
+			0,
 		))),
 	)
 }
@@ -85,7 +88,7 @@ fn test_create_declaration_integer() {
 	create_parser_test(
 		"val foo = 12",
 		Declaration(
-			Token::new(TokenType::Val, None, 1, 0, 3),
+			Token::new(TokenType::Val, None, 1, 0, 3, 0),
 			None,
 			Assignment(
 				Token::new(
@@ -94,14 +97,16 @@ fn test_create_declaration_integer() {
 					1,
 					4,
 					3,
+					4,
 				),
-				Token::new(TokenType::Assign, None, 1, 8, 1),
+				Token::new(TokenType::Assign, None, 1, 8, 1, 8),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(12)),
+					Some(Literal::Integer(12)),
 					1,
 					10,
 					2,
+					10,
 				))),
 			),
 		),
@@ -113,7 +118,7 @@ fn test_create_declaration_boolean() {
 	create_parser_test(
 		"var foo = true",
 		Declaration(
-			Token::new(TokenType::Var, None, 1, 0, 3),
+			Token::new(TokenType::Var, None, 1, 0, 3, 0),
 			None,
 			Assignment(
 				Token::new(
@@ -122,14 +127,16 @@ fn test_create_declaration_boolean() {
 					1,
 					4,
 					3,
+					4,
 				),
-				Token::new(TokenType::Assign, None, 1, 8, 1),
+				Token::new(TokenType::Assign, None, 1, 8, 1, 8),
 				Box::new(Literal(Token::new(
 					TokenType::Boolean,
 					Some(Literal::Boolean(true)),
 					1,
 					10,
 					4,
+					10,
 				))),
 			),
 		),
@@ -141,7 +148,7 @@ fn test_create_declaration_string() {
 	create_parser_test(
 		"val foo = 'string'",
 		Declaration(
-			Token::new(TokenType::Val, None, 1, 0, 3),
+			Token::new(TokenType::Val, None, 1, 0, 3, 0),
 			None,
 			Assignment(
 				Token::new(
@@ -150,14 +157,16 @@ fn test_create_declaration_string() {
 					1,
 					4,
 					3,
+					4,
 				),
-				Token::new(TokenType::Assign, None, 1, 8, 1),
+				Token::new(TokenType::Assign, None, 1, 8, 1, 8),
 				Box::new(Literal(Token::new(
 					TokenType::String,
 					Some(Literal::String("string".to_string())),
 					1,
 					10,
 					8,
+					10,
 				))),
 			),
 		),
@@ -169,13 +178,14 @@ fn test_create_typed_declaration_number() {
 	create_parser_test(
 		"var: Integer foo = 200",
 		Declaration(
-			Token::new(TokenType::Var, None, 1, 0, 3),
+			Token::new(TokenType::Var, None, 1, 0, 3, 0),
 			Some(Token::new(
 				TokenType::Identifier,
 				Some(Literal::String("Integer".to_string())),
 				1,
 				5,
 				7,
+				5,
 			)),
 			Assignment(
 				Token::new(
@@ -184,14 +194,16 @@ fn test_create_typed_declaration_number() {
 					1,
 					13,
 					3,
+					13,
 				),
-				Token::new(TokenType::Assign, None, 1, 17, 1),
+				Token::new(TokenType::Assign, None, 1, 17, 1, 17),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(200)),
+					Some(Literal::Integer(200)),
 					1,
 					19,
 					3,
+					19,
 				))),
 			),
 		),
@@ -205,18 +217,20 @@ fn test_create_expression_binary_number_addition() {
 		Expression(Binary(
 			Box::new(Literal(Token::new(
 				TokenType::Integer,
-				Some(Literal::Number(2)),
+				Some(Literal::Integer(2)),
 				1,
 				0,
 				1,
+				0,
 			))),
-			Token::new(TokenType::Plus, None, 1, 2, 1),
+			Token::new(TokenType::Plus, None, 1, 2, 1, 2),
 			Box::new(Literal(Token::new(
 				TokenType::Integer,
-				Some(Literal::Number(7)),
+				Some(Literal::Integer(7)),
 				1,
 				4,
 				1,
+				4,
 			))),
 		)),
 	)
@@ -230,27 +244,30 @@ fn test_create_nested_expression_binary_number_addition() {
 			Box::new(Binary(
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(2)),
+					Some(Literal::Integer(2)),
 					1,
 					0,
 					1,
+					0,
 				))),
-				Token::new(TokenType::Plus, None, 1, 2, 1),
+				Token::new(TokenType::Plus, None, 1, 2, 1, 2),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(7)),
+					Some(Literal::Integer(7)),
 					1,
 					4,
 					1,
+					4,
 				))),
 			)),
-			Token::new(TokenType::Plus, None, 1, 6, 1),
+			Token::new(TokenType::Plus, None, 1, 6, 1, 6),
 			Box::new(Literal(Token::new(
 				TokenType::Integer,
-				Some(Literal::Number(8)),
+				Some(Literal::Integer(8)),
 				1,
 				8,
 				1,
+				8,
 			))),
 		)),
 	)
@@ -263,27 +280,30 @@ fn test_create_nested_expression_binary_number_addition_and_multiplication() {
 		Expression(Binary(
 			Box::new(Literal(Token::new(
 				TokenType::Integer,
-				Some(Literal::Number(2)),
+				Some(Literal::Integer(2)),
 				1,
 				0,
 				1,
+				0,
 			))),
-			Token::new(TokenType::Plus, None, 1, 2, 1),
+			Token::new(TokenType::Plus, None, 1, 2, 1, 2),
 			Box::new(Binary(
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(7)),
+					Some(Literal::Integer(7)),
 					1,
 					4,
 					1,
+					4,
 				))),
-				Token::new(TokenType::Multiply, None, 1, 6, 1),
+				Token::new(TokenType::Multiply, None, 1, 6, 1, 6),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(8)),
+					Some(Literal::Integer(8)),
 					1,
 					8,
 					1,
+					8,
 				))),
 			)),
 		)),
@@ -302,23 +322,26 @@ fn test_create_nested_expression_binary_identifier_addition() {
 					1,
 					0,
 					3,
+					0,
 				))),
-				Token::new(TokenType::Plus, None, 1, 4, 1),
+				Token::new(TokenType::Plus, None, 1, 4, 1, 4),
 				Box::new(Identifier(Token::new(
 					TokenType::Identifier,
 					Some(Literal::String("bar".to_string())),
 					1,
 					6,
 					3,
+					6,
 				))),
 			)),
-			Token::new(TokenType::Plus, None, 1, 10, 1),
+			Token::new(TokenType::Plus, None, 1, 10, 1, 10),
 			Box::new(Identifier(Token::new(
 				TokenType::Identifier,
 				Some(Literal::String("baz".to_string())),
 				1,
 				12,
 				3,
+				12,
 			))),
 		)),
 	)
@@ -329,10 +352,11 @@ fn test_create_expression_unary_number() {
 	create_parser_test(
 		"-1",
 		Expression(Unary(
-			Token::new(TokenType::Minus, None, 1, 0, 1),
+			Token::new(TokenType::Minus, None, 1, 0, 1, 0),
 			Box::new(Literal(Token::new(
 				TokenType::Integer,
-				Some(Literal::Number(1)),
+				Some(Literal::Integer(1)),
+				1,
 				1,
 				1,
 				1,
@@ -346,13 +370,14 @@ fn test_create_expression_unary_boolean() {
 	create_parser_test(
 		"!true",
 		Expression(Unary(
-			Token::new(TokenType::Not, None, 1, 0, 1),
+			Token::new(TokenType::Not, None, 1, 0, 1, 0),
 			Box::new(Literal(Token::new(
 				TokenType::Boolean,
 				Some(Literal::Boolean(true)),
 				1,
 				1,
 				4,
+				1,
 			))),
 		)),
 	)
@@ -366,29 +391,32 @@ fn test_create_expression_unary_in_binary_numbers() {
 			Box::new(Binary(
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(2)),
+					Some(Literal::Integer(2)),
 					1,
 					0,
 					1,
+					0,
 				))),
-				Token::new(TokenType::Plus, None, 1, 2, 1),
+				Token::new(TokenType::Plus, None, 1, 2, 1, 2),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(7)),
+					Some(Literal::Integer(7)),
 					1,
 					4,
 					1,
+					4,
 				))),
 			)),
-			Token::new(TokenType::Plus, None, 1, 6, 1),
+			Token::new(TokenType::Plus, None, 1, 6, 1, 6),
 			Box::new(Unary(
-				Token::new(TokenType::Minus, None, 1, 8, 1),
+				Token::new(TokenType::Minus, None, 1, 8, 1, 8),
 				Box::new(Literal(Token::new(
 					TokenType::Integer,
-					Some(Literal::Number(8)),
+					Some(Literal::Integer(8)),
 					1,
 					9,
 					1,
+					9,
 				))),
 			)),
 		)),
@@ -403,3 +431,540 @@ fn test_create_failed_expressions() {
 	create_fail_statement_test("2 + (7 + -8");
 	create_fail_statement_test("2 *** 8");
 }
+
+#[test]
+fn test_create_for_statement() {
+	create_parser_test(
+		"for (c in s) {}",
+		For(
+			Token::new(TokenType::For, None, 1, 0, 3, 0),
+			Identifier(Token::new(
+				TokenType::Identifier,
+				Some(Literal::String("c".to_string())),
+				1,
+				5,
+				1,
+				5,
+			)),
+			Identifier(Token::new(
+				TokenType::Identifier,
+				Some(Literal::String("s".to_string())),
+				1,
+				10,
+				1,
+				10,
+			)),
+			Box::new(Body(
+				Token::new(TokenType::OpenCurlyBracket, None, 1, 13, 1, 13),
+				vec![],
+				Token::new(TokenType::CloseCurlyBracket, None, 1, 14, 1, 14),
+			)),
+		),
+	)
+}
+
+#[test]
+fn test_create_failed_for_statements() {
+	create_fail_statement_test("for (in s) {}");
+	create_fail_statement_test("for (c s) {}");
+	create_fail_statement_test("for (c in s {}");
+	create_fail_statement_test("for (c in s)");
+}
+
+#[test]
+fn test_create_expression_range() {
+	create_parser_test(
+		"0..=10",
+		Expression(Range(
+			Box::new(Literal(Token::new(
+				TokenType::Integer,
+				Some(Literal::Integer(0)),
+				1,
+				0,
+				1,
+				0,
+			))),
+			Token::new(TokenType::RangeInclusive, None, 1, 1, 3, 1),
+			Box::new(Literal(Token::new(
+				TokenType::Integer,
+				Some(Literal::Integer(10)),
+				1,
+				4,
+				2,
+				4,
+			))),
+		)),
+	)
+}
+
+#[test]
+fn test_create_match_statement() {
+	let tokenizer = Tokenizer::new("match (a) { 1, 2 if b -> {}, else -> c }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Match(_, Identifier(_), arms, default, _) => {
+			assert_eq!(1, arms.len());
+			assert_eq!(2, arms[0].patterns.len());
+			assert!(arms[0].guard.is_some());
+			assert!(matches!(&arms[0].body, Body(_, statements, _) if statements.is_empty()));
+			assert!(matches!(default.as_deref(), Some(Expression(Identifier(_)))));
+		}
+		_ => panic!("Expected match statement"),
+	}
+}
+
+#[test]
+fn test_create_match_expression() {
+	let tokenizer = Tokenizer::new("return match (a) { 0..10 -> 1, else -> 2 }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Return(_, Some(Expression::Match(_, _, arms, default, _))) => {
+			assert!(matches!(arms[0].patterns[0], Range(..)));
+			assert!(matches!(default.as_deref(), Some(Literal(_))));
+		}
+		_ => panic!("Expected match expression"),
+	}
+}
+
+#[test]
+fn test_create_failed_match_statements() {
+	create_fail_statement_test("match (a) { }");
+	create_fail_statement_test("match (a) { 1 b, else -> c }");
+	create_fail_statement_test("match (a) { else -> c, 1 -> b }");
+	create_fail_statement_test("match a { else -> c }");
+}
+
+#[test]
+fn test_create_expression_array() {
+	create_parser_test(
+		"[1, 2]",
+		Expression(Array(
+			Token::new(TokenType::OpenSquareBracket, None, 1, 0, 1, 0),
+			vec![
+				Literal(Token::new(
+					TokenType::Integer,
+					Some(Literal::Integer(1)),
+					1,
+					1,
+					1,
+					1,
+				)),
+				Literal(Token::new(
+					TokenType::Integer,
+					Some(Literal::Integer(2)),
+					1,
+					4,
+					1,
+					4,
+				)),
+			],
+			Token::new(TokenType::CloseSquareBracket, None, 1, 5, 1, 5),
+		)),
+	)
+}
+
+#[test]
+fn test_create_expression_index_assignment() {
+	let tokenizer = Tokenizer::new("xs[0] = 1");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Expression(Set(target, operator, _)) => {
+			assert!(matches!(*target, Index(..)));
+			assert_eq!(TokenType::Assign, operator.r#type);
+		}
+		_ => panic!("Expected set expression"),
+	}
+}
+
+#[test]
+fn test_create_generic_type_specification() {
+	let tokenizer = Tokenizer::new("fun: Array<Array<Int>> foo(xs: Array<Int>) {}");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Function(_, Some(r#type), _, parameters, _) => {
+			assert_eq!(
+				Some(Literal::String("Array<Array<Int>>".to_string())),
+				r#type.literal
+			);
+			assert_eq!(17, r#type.length);
+			assert_eq!(
+				Some(Literal::String("Array<Int>".to_string())),
+				parameters[0].1.literal
+			);
+		}
+		_ => panic!("Expected function"),
+	}
+}
+
+#[test]
+fn test_create_failed_arrays() {
+	create_fail_statement_test("[1, 2");
+	create_fail_statement_test("xs[0");
+	create_fail_statement_test("fun foo(xs: Array<Int) {}");
+}
+
+#[test]
+fn test_create_expression_map() {
+	let tokenizer = Tokenizer::new("{ 'a': 1, 'b': 2 }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Expression(Map(open_curly_bracket, entries, _)) => {
+			assert_eq!(TokenType::OpenCurlyBracket, open_curly_bracket.r#type);
+			assert_eq!(2, entries.len());
+		}
+		_ => panic!("Expected map expression"),
+	}
+}
+
+#[test]
+fn test_create_block_statement() {
+	let tokenizer = Tokenizer::new("{ a }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	assert!(matches!(statement, Body(_, statements, _) if statements.len() == 1));
+
+	let tokenizer = Tokenizer::new("{}");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	assert!(matches!(statement, Body(_, statements, _) if statements.is_empty()));
+}
+
+#[test]
+fn test_create_failed_maps() {
+	create_fail_statement_test("{ 'a': }");
+	create_fail_statement_test("{ 'a': 1, 'b' }");
+	create_fail_statement_test("{ 'a': 1");
+}
+
+#[test]
+fn test_create_record_statement() {
+	let tokenizer = Tokenizer::new("type Point { x: Int, y: Array<Int> }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Record(_, identifier, fields, _) => {
+			assert_eq!(Some(Literal::String("Point".to_string())), identifier.literal);
+			assert_eq!(2, fields.len());
+			assert_eq!(
+				Some(Literal::String("Array<Int>".to_string())),
+				fields[1].1.literal
+			);
+		}
+		_ => panic!("Expected record"),
+	}
+}
+
+#[test]
+fn test_create_expression_record() {
+	let tokenizer = Tokenizer::new("Point { x: 1, y: 2 }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Expression(Expression::Record(identifier, fields, _)) => {
+			assert_eq!(Some(Literal::String("Point".to_string())), identifier.literal);
+			assert_eq!(2, fields.len());
+		}
+		_ => panic!("Expected record expression"),
+	}
+}
+
+#[test]
+fn test_create_expression_field_assignment() {
+	let tokenizer = Tokenizer::new("line.start.x += 1");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Expression(Set(target, operator, _)) => {
+			assert!(matches!(*target, Get(..)));
+			assert_eq!(TokenType::AssignPlus, operator.r#type);
+		}
+		_ => panic!("Expected set expression"),
+	}
+}
+
+#[test]
+fn test_create_failed_records() {
+	create_fail_statement_test("type { x: Int }");
+	create_fail_statement_test("type Point { x }");
+	create_fail_statement_test("type Point { x: Int");
+	create_fail_statement_test("Point { x 1 }");
+}
+
+#[test]
+fn test_create_enum_statement() {
+	let tokenizer = Tokenizer::new("enum Status { Ok(Int), NotFound, Failed(String) }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Enum(_, identifier, variants, _) => {
+			assert_eq!(Some(Literal::String("Status".to_string())), identifier.literal);
+			assert_eq!(3, variants.len());
+			assert_eq!(1, variants[0].1.len());
+			assert!(variants[1].1.is_empty());
+		}
+		_ => panic!("Expected enum"),
+	}
+}
+
+#[test]
+fn test_create_match_without_else() {
+	let tokenizer = Tokenizer::new("match (s) { Status.Ok(n) -> a, Status.NotFound -> b }");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Match(_, _, arms, default, _) => {
+			assert_eq!(2, arms.len());
+			assert!(matches!(arms[0].patterns[0], Call(..)));
+			assert!(default.is_none());
+		}
+		_ => panic!("Expected match statement"),
+	}
+}
+
+#[test]
+fn test_create_failed_enums() {
+	create_fail_statement_test("enum { Ok }");
+	create_fail_statement_test("enum Status { }");
+	create_fail_statement_test("enum Status { Ok(Int }");
+	create_fail_statement_test("enum Status { Ok NotFound }");
+}
+
+#[test]
+fn test_create_expression_lambda() {
+	let tokenizer = Tokenizer::new("val add = (a: Int, b: Int) -> a + b");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Declaration(_, _, Assignment(_, _, expression)) => match *expression {
+			Lambda(_, parameters, _, body) => {
+				assert_eq!(2, parameters.len());
+				assert!(matches!(*body, Statement::Return(_, Some(Binary(..)))));
+			}
+			_ => panic!("Expected lambda"),
+		},
+		_ => panic!("Expected declaration"),
+	}
+}
+
+#[test]
+fn test_create_expression_lambda_body() {
+	let tokenizer = Tokenizer::new("apply(() -> { return 1 }, (x: Int) -> x)");
+	let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+	match statement {
+		Statement::Expression(Call(_, _, arguments, _)) => {
+			assert!(matches!(&arguments[0], Lambda(_, parameters, _, body) if parameters.is_empty() && matches!(**body, Statement::Body(..))));
+			assert!(matches!(&arguments[1], Lambda(_, parameters, ..) if parameters.len() == 1));
+		}
+		_ => panic!("Expected call"),
+	}
+}
+
+#[test]
+fn test_create_failed_lambdas() {
+	create_fail_statement_test("val f = (a: Int) a");
+	create_fail_statement_test("val f = (a: Int b: Int) -> a");
+	create_fail_statement_test("val f = (a: ) -> a");
+	create_fail_statement_test("val f = (a: Int) ->");
+}
+
+#[test]
+fn test_create_failed_templates() {
+	create_fail_statement_test("val a = '${1 +}'");
+	create_fail_statement_test("val a = '${1 2}'");
+}
+
+#[test]
+fn test_create_documentation() {
+	create_parser_test(
+		"/// Answer\nval a = 42",
+		Documentation(
+			vec![Token::new(
+				TokenType::DocComment,
+				Some(Literal::String(" Answer".to_string())),
+				1,
+				0,
+				10,
+				0,
+			)],
+			Box::new(Declaration(
+				Token::new(TokenType::Val, None, 2, 0, 3, 11),
+				None,
+				Assignment(
+					Token::new(
+						TokenType::Identifier,
+						Some(Literal::String("a".to_string())),
+						2,
+						4,
+						1,
+						15,
+					),
+					Token::new(TokenType::Assign, None, 2, 6, 1, 17),
+					Box::new(Literal(Token::new(
+						TokenType::Integer,
+						Some(Literal::Integer(42)),
+						2,
+						8,
+						2,
+						19,
+					))),
+				),
+			)),
+		),
+	)
+}
+
+#[test]
+fn test_create_documented_function() {
+	let tokenizer = Tokenizer::new("/// Adds one\n/// to a\nfun add(a: Int) { return a + 1 }");
+	let mut parser = Parser::new(tokenizer.peekable());
+
+	match parser.next().unwrap().unwrap() {
+		Documentation(comments, statement) => {
+			assert_eq!(2, comments.len());
+			assert!(matches!(*statement, Function(..)));
+		}
+		statement => panic!("Unexpected statement {:?}", statement),
+	}
+}
+
+#[test]
+fn test_create_comments_in_expression() {
+	let tokenizer = Tokenizer::new("/* a */\nval a = 1 /* b */ + /* c */ 2\n// d");
+	let statements: Vec<Statement> = Parser::new(tokenizer.peekable())
+		.collect::<Result<Vec<Statement>, tutara_interpreter::Error>>()
+		.unwrap();
+
+	assert_eq!(2, statements.len());
+	assert!(matches!(&statements[0], Comment(_)));
+	assert!(matches!(&statements[1], Declaration(_, _, Assignment(_, _, value)) if matches!(**value, Binary(..))));
+}
+
+#[test]
+fn test_create_failed_documentation() {
+	create_fail_statement_test("/// Answer\nreturn 42");
+	create_fail_statement_test("/// Answer");
+}
+
+fn create_parse_all_test(input: &str, expected_statements: usize, expected_errors: &[(u32, u32)]) {
+	let tokenizer = Tokenizer::new(input);
+	let (statements, errors) = Parser::new(tokenizer.peekable()).parse_all();
+
+	let positions: Vec<(u32, u32)> = errors
+		.iter()
+		.map(|error| match &error.r#type {
+			ErrorType::Parser(token) => (token.line, token.column),
+			ErrorType::Lexical(line, column, _) => (*line, *column),
+			r#type => panic!("Unexpected error type {:?}", r#type),
+		})
+		.collect();
+
+	assert_eq!(expected_errors, positions.as_slice());
+	assert_eq!(expected_statements, statements.len());
+}
+
+#[test]
+fn test_parse_all() {
+	create_parse_all_test("val a = 1\nval b = a", 2, &[]);
+	create_parse_all_test("val = 1\nval a = 2\n2 *** 8\nval b = a", 2, &[(1, 0), (3, 4)]);
+	create_parse_all_test("val a = 1 }\nval b = \"x", 1, &[(1, 10), (2, 8)]);
+}
+
+#[test]
+fn test_parse_all_return() {
+	create_parse_all_test("return 1 + * 2\nval a = 1", 1, &[(1, 11)]);
+	create_parse_all_test("return '\\q'\nval a = 1", 1, &[(1, 8)]);
+	create_parse_all_test("return 0b19 + 1", 0, &[(1, 7)]);
+	create_parse_all_test("fun f() {\n\treturn\n}\nloop { return // done\n}\nreturn", 3, &[]);
+}
+
+#[test]
+fn test_parse_all_in_bodies() {
+	create_parse_all_test(
+		"fun: Int f(a: Int) {\n\tval = a\n\tif (a +) { return 1 }\n\treturn a\n}\nval c = f(1",
+		1,
+		&[(2, 1), (3, 8), (6, 9)],
+	);
+	create_parse_all_test("loop { val x = 1", 0, &[(1, 5)]);
+}
+
+#[test]
+fn test_parse_all_synchronizing_token() {
+	create_parse_all_test("val a = (1 +\nreturn 1", 1, &[(2, 0)]);
+	create_parse_all_test(
+		"fun f() { val b = }\nval c = 1\nfun g() { return 2 }",
+		3,
+		&[(1, 18)],
+	);
+	create_parse_all_test("}\nval a = 1 } }\nval b = 2", 2, &[(1, 0), (2, 10), (2, 12)]);
+}
+
+#[test]
+fn test_parse_all_partial_body() {
+	let tokenizer = Tokenizer::new("loop {\n\tval = 1\n\tbreak\n}");
+	let (statements, errors) = Parser::new(tokenizer.peekable()).parse_all();
+
+	assert_eq!(1, errors.len());
+	assert_eq!(
+		vec![Loop(
+			Token::new(TokenType::Loop, None, 1, 0, 4, 0),
+			Box::new(Body(
+				Token::new(TokenType::OpenCurlyBracket, None, 1, 5, 1, 5),
+				vec![Break(Token::new(TokenType::Break, None, 3, 1, 5, 17))],
+				Token::new(TokenType::CloseCurlyBracket, None, 4, 0, 1, 23),
+			))
+		)],
+		statements
+	);
+}
+
+#[test]
+fn test_spans() {
+	let tokenizer = Tokenizer::new("val a = f(1, 2)\nfun: Int g() {\n\treturn 'é'\n}\nloop { break }");
+	let statements: Vec<Statement> = Parser::new(tokenizer.peekable())
+		.collect::<Result<Vec<Statement>, tutara_interpreter::Error>>()
+		.unwrap();
+
+	assert_eq!(Span::new(0, 1, 0, 1, 15), statements[0].span());
+	assert_eq!(Span::new(16, 2, 0, 4, 1), statements[1].span());
+	assert_eq!(Span::new(46, 5, 0, 5, 14), statements[2].span());
+
+	match &statements[0] {
+		Declaration(_, _, Assignment(_, _, value)) => assert_eq!(Span::new(8, 1, 8, 1, 15), value.span()),
+		_ => panic!("Expected declaration"),
+	}
+}
+
+#[test]
+fn test_node_spans() {
+	let inputs = [
+		("(1 + 2)", 7),
+		("a[0]", 4),
+		("[1, 2]", 6),
+		("{ 'a': 1 }", 10),
+		("Point { x: 1 }", 14),
+		("(a: Int) -> a", 13),
+		("if (a) { b } else { c }", 23),
+		("while (a) { b }", 15),
+		("for (c in s) { c }", 18),
+		("loop { b }", 10),
+		("fun: Int f() { return 1 }", 25),
+		("type P { x: Int }", 17),
+		("enum E { A, B }", 15),
+		("match (a) { 1 -> b }", 20),
+		("match (a) { 1 -> b, else -> c }", 31),
+		("val c = match (a) { else -> b }", 31),
+	];
+
+	for (input, length) in inputs.iter() {
+		let tokenizer = Tokenizer::new(input);
+		let statement = Parser::new(tokenizer.peekable()).next().unwrap().unwrap();
+
+		assert_eq!(Span::new(0, 1, 0, 1, *length), statement.span(), "{}", input);
+	}
+}
@@ -1,4 +1,4 @@
-use tutara_interpreter::{Literal, Token, TokenType, Tokenizer};
+use tutara_interpreter::{ErrorCode, ErrorType, Literal, TemplatePart, Token, TokenType, Tokenizer};
 
 #[test]
 fn test_create_tokenizer() {
@@ -24,13 +24,85 @@ fn create_fail_token_test(input: &str) {
 fn test_create_number() {
 	create_token_test(
 		"1000",
-		Token::new(TokenType::Integer, Some(Literal::Number(1000)), 1, 0, 4),
+		Token::new(TokenType::Integer, Some(Literal::Integer(1000)), 1, 0, 4, 0),
 	)
 }
 
+#[test]
+fn test_create_float() {
+	create_token_test(
+		"1.5",
+		Token::new(TokenType::Float, Some(Literal::Float(1.5)), 1, 0, 3, 0),
+	);
+	create_token_test(
+		"1e-3",
+		Token::new(TokenType::Float, Some(Literal::Float(0.001)), 1, 0, 4, 0),
+	);
+}
+
+#[test]
+fn test_float_literal_equality() {
+	assert_eq!(Literal::Float(f64::NAN), Literal::Float(f64::NAN));
+	assert_ne!(Literal::Float(1.5), Literal::Float(2.5));
+	assert_ne!(Literal::Float(1.0), Literal::Integer(1));
+}
+
+#[test]
+fn test_create_radix_number() {
+	create_token_test(
+		"0xFF",
+		Token::new(TokenType::Integer, Some(Literal::Integer(255)), 1, 0, 4, 0),
+	);
+	create_token_test(
+		"0b1010",
+		Token::new(TokenType::Integer, Some(Literal::Integer(10)), 1, 0, 6, 0),
+	);
+}
+
+#[test]
+fn test_create_separated_number() {
+	create_token_test(
+		"1_000_000",
+		Token::new(TokenType::Integer, Some(Literal::Integer(1_000_000)), 1, 0, 9, 0),
+	);
+	create_token_test(
+		"2.718_5",
+		Token::new(TokenType::Float, Some(Literal::Float(2.7185)), 1, 0, 7, 0),
+	);
+}
+
 #[test]
 fn test_create_invalid_number() {
-	create_fail_token_test("1000000000000000000000")
+	create_fail_token_test("1000000000000000000000");
+	create_fail_token_test("0x");
+	create_fail_token_test("0x1_0000_0000_0000_0000");
+	create_fail_token_test("1e400");
+}
+
+#[test]
+fn test_invalid_radix_digit() {
+	let inputs = [
+		("0b12", 4),
+		("0b19 + 1", 4),
+		("0x1G", 4),
+		("0xFFz_1", 7),
+		("0b1_", 4),
+	];
+
+	for (input, length) in inputs.iter() {
+		let mut tokenizer = Tokenizer::new(input);
+		let error = tokenizer.next().unwrap().unwrap_err();
+
+		assert!(matches!(error.r#type, ErrorType::Lexical(1, 0, l) if l == *length));
+	}
+}
+
+#[test]
+fn test_invalid_number_span() {
+	let mut tokenizer = Tokenizer::new("val a = 99999999999999999999");
+	let error = tokenizer.find_map(|token| token.err()).unwrap();
+
+	assert!(matches!(error.r#type, ErrorType::Lexical(1, 8, 20)));
 }
 
 #[test]
@@ -51,10 +123,203 @@ fn test_create_string() {
 			1,
 			0,
 			8,
+			0,
 		),
 	)
 }
 
+#[test]
+fn test_create_escaped_string() {
+	create_token_test(
+		"'\\0\\x41\\u{1F600}\\v'",
+		Token::new(
+			TokenType::String,
+			Some(Literal::String("\0A\u{1F600}\u{b}".to_string())),
+			1,
+			0,
+			19,
+			0,
+		),
+	)
+}
+
+#[test]
+fn test_create_invalid_escape() {
+	create_fail_token_test("'\\q'");
+	create_fail_token_test("'\\x4'");
+	create_fail_token_test("'\\xFF'");
+	create_fail_token_test("'\\u{}'");
+	create_fail_token_test("'\\u{D800}'");
+	create_fail_token_test("'\\u{1234567}'");
+
+	let mut tokenizer = Tokenizer::new("'ab\\q'");
+	let error = tokenizer.next().unwrap().unwrap_err();
+
+	assert!(matches!(error.r#type, ErrorType::Lexical(1, 3, 2)));
+}
+
+#[test]
+fn test_create_template() {
+	create_token_test(
+		"'$a is ${b + 1}'",
+		Token::new(
+			TokenType::Template,
+			Some(Literal::Template(vec![
+				TemplatePart::Expression("a".to_string(), 1, 2, 2),
+				TemplatePart::Text(" is ".to_string()),
+				TemplatePart::Expression("b + 1".to_string(), 1, 9, 9),
+			])),
+			1,
+			0,
+			16,
+			0,
+		),
+	)
+}
+
+#[test]
+fn test_create_invalid_template() {
+	create_fail_token_test("'${}'");
+	create_fail_token_test("'${a'");
+}
+
+#[test]
+fn test_create_raw_string() {
+	create_token_test(
+		"r'C:\\new\\$path'",
+		Token::new(
+			TokenType::String,
+			Some(Literal::String("C:\\new\\$path".to_string())),
+			1,
+			0,
+			15,
+			0,
+		),
+	);
+	create_fail_token_test("r'abc");
+	create_fail_token_test("'abc");
+
+	let error = Tokenizer::new("return r'abc").nth(1).unwrap().unwrap_err();
+	assert_eq!(Some(ErrorCode::UnterminatedString), error.code);
+}
+
+#[test]
+fn test_create_multiline_string() {
+	create_token_test(
+		"'''\n\t\tfoo\n\t\t\tbar\n\n\t\tbaz\n\t\t'''",
+		Token::new(
+			TokenType::String,
+			Some(Literal::String("foo\n\tbar\n\nbaz".to_string())),
+			1,
+			0,
+			29,
+			0,
+		)
+		.with_end(6, 5),
+	);
+	create_token_test(
+		"r'''a\\n\n  b'''",
+		Token::new(
+			TokenType::String,
+			Some(Literal::String("a\\n\nb".to_string())),
+			1,
+			0,
+			14,
+			0,
+		)
+		.with_end(2, 6),
+	);
+	create_token_test(
+		"'''\n  foo\n\tbar\n\t'''",
+		Token::new(
+			TokenType::String,
+			Some(Literal::String("  foo\n\tbar".to_string())),
+			1,
+			0,
+			19,
+			0,
+		)
+		.with_end(4, 4),
+	);
+	create_fail_token_test("'''foo\nbar");
+}
+
+#[test]
+fn test_multiline_string_position() {
+	let mut tokenizer = Tokenizer::new("val a = '''\n  x ${b}\n  ''' c");
+	let tokens: Vec<Token> = tokenizer.by_ref().map(|token| token.unwrap()).collect();
+
+	assert_eq!(
+		Some(&Literal::Template(vec![
+			TemplatePart::Text("x ".to_string()),
+			TemplatePart::Expression("b".to_string(), 2, 6, 18),
+		])),
+		tokens[3].literal.as_ref()
+	);
+	assert_eq!((1, 8, 18), (tokens[3].line, tokens[3].column, tokens[3].length));
+	assert_eq!((3, 6), (tokens[4].line, tokens[4].column));
+}
+
+#[test]
+fn test_create_comments() {
+	create_token_test(
+		"// foo",
+		Token::new(
+			TokenType::Comment,
+			Some(Literal::String(" foo".to_string())),
+			1,
+			0,
+			6,
+			0,
+		),
+	);
+	create_token_test(
+		"/// foo",
+		Token::new(
+			TokenType::DocComment,
+			Some(Literal::String(" foo".to_string())),
+			1,
+			0,
+			7,
+			0,
+		),
+	);
+	create_token_test(
+		"//// foo",
+		Token::new(
+			TokenType::Comment,
+			Some(Literal::String("// foo".to_string())),
+			1,
+			0,
+			8,
+			0,
+		),
+	);
+}
+
+#[test]
+fn test_create_block_comment() {
+	create_token_test(
+		"/* a /* b */\nc */",
+		Token::new(
+			TokenType::Comment,
+			Some(Literal::String(" a /* b */\nc ".to_string())),
+			1,
+			0,
+			17,
+			0,
+		)
+		.with_end(2, 4),
+	);
+	create_fail_token_test("/* a /* b */");
+
+	let mut tokenizer = Tokenizer::new("/*\n*/ val");
+	tokenizer.next();
+	let token = tokenizer.next().unwrap().unwrap();
+
+	assert_eq!((2, 3), (token.line, token.column));
+}
+
 #[test]
 fn test_create_identifier() {
 	create_token_test(
@@ -65,6 +330,47 @@ fn test_create_identifier() {
 			1,
 			0,
 			3,
+			0,
 		),
 	)
 }
+
+#[test]
+fn test_create_range() {
+	create_token_test("..", Token::new(TokenType::Range, None, 1, 0, 2, 0));
+	create_token_test("..=", Token::new(TokenType::RangeInclusive, None, 1, 0, 3, 0));
+}
+
+#[test]
+fn test_create_range_between_numbers() {
+	let tokens: Vec<TokenType> = Tokenizer::new("0..10")
+		.map(|token| token.unwrap().r#type)
+		.collect();
+
+	assert_eq!(
+		vec![TokenType::Integer, TokenType::Range, TokenType::Integer],
+		tokens
+	);
+}
+
+#[test]
+fn test_create_square_brackets() {
+	create_token_test("[", Token::new(TokenType::OpenSquareBracket, None, 1, 0, 1, 0));
+	create_token_test(
+		"]",
+		Token::new(TokenType::CloseSquareBracket, None, 1, 0, 1, 0),
+	);
+}
+
+#[test]
+fn test_token_offsets() {
+	let offsets: Vec<(u32, u32, u32)> = Tokenizer::new("val s = 'é'\n\tval b = '${s}'")
+		.map(|token| token.unwrap())
+		.map(|token| (token.line, token.column, token.offset))
+		.collect();
+
+	assert_eq!(
+		vec![(1, 0, 0), (1, 4, 4), (1, 6, 6), (1, 8, 8), (2, 1, 14), (2, 5, 18), (2, 7, 20), (2, 9, 22)],
+		offsets
+	);
+}
@@ -1,13 +1,15 @@
+use serde::Serialize;
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
-use tutara_interpreter::{Tokenizer, Error, parser::Parser};
+use tutara_interpreter::{Tokenizer, Error, Input, Interpreter, Value, parser::Parser};
 use tutara_interpreter::ast::*;
 
 #[wasm_bindgen]
 pub struct Source {
     text: String,
     tokens: Option<Result<Vec<Token>, Error>>,
-    statements: Option<Result<Vec<Statement>, Error>>,
+    statements: Option<(Vec<Statement>, Vec<Error>)>,
 }
 
 #[wasm_bindgen]
@@ -30,15 +32,77 @@ impl Source {
     pub fn get_statements(&mut self) -> Result<JsValue, JsValue> {
         if self.statements.is_none() {
 			let parser = Parser::new(Tokenizer::new(&self.text).peekable());
-            self.statements = Some(parser.collect());
+            self.statements = Some(parser.parse_all());
         }
 
+        // Scripts with syntax errors still get the partial tree of the statements without errors
         match &self.statements {
-            Some(Ok(statements)) => Ok(JsValue::from_serde(&statements).unwrap()),
-            Some(Err(err)) => Err(JsValue::from_serde(&err).unwrap()),
+            Some((statements, errors)) => {
+                let parse = JsValue::from_serde(&Parse { statements, errors }).unwrap();
+
+                if errors.is_empty() {
+                    Ok(parse)
+                } else {
+                    Err(parse)
+                }
+            }
             None => unreachable!(),
         }
     }
+
+    #[wasm_bindgen(catch)]
+    pub fn evaluate(&self) -> Result<JsValue, JsValue> {
+        self.evaluate_inputs(HashMap::new())
+    }
+
+    #[wasm_bindgen(catch)]
+    pub fn evaluate_with_inputs(&self, inputs: &JsValue) -> Result<JsValue, JsValue> {
+        // Inputs only accept plain data, so they can't create ranges, records, enums or functions
+        match inputs.into_serde::<HashMap<String, Input>>() {
+            Ok(inputs) => self.evaluate_inputs(
+                inputs
+                    .into_iter()
+                    .map(|(name, input)| (name, Value::from(input)))
+                    .collect(),
+            ),
+            Err(err) => Err(JsValue::from_str(&format!("Invalid inputs: {}", err))),
+        }
+    }
+}
+
+impl Source {
+    fn evaluate_inputs(&self, inputs: HashMap<String, Value>) -> Result<JsValue, JsValue> {
+        let parser = Parser::new(Tokenizer::new(&self.text).peekable());
+        let mut interpreter = Interpreter::with_inputs(inputs);
+
+        match interpreter.evaluate(parser) {
+            Ok(result) => Ok(JsValue::from_serde(&Evaluation {
+                result: Some(result),
+                error: None,
+                output: interpreter.output(),
+            })
+            .unwrap()),
+            Err(err) => Err(JsValue::from_serde(&Evaluation {
+                result: None,
+                error: Some(err),
+                output: interpreter.output(),
+            })
+            .unwrap()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Parse<'a> {
+    statements: &'a [Statement],
+    errors: &'a [Error],
+}
+
+#[derive(Serialize)]
+struct Evaluation<'a> {
+    result: Option<Value>,
+    error: Option<Error>,
+    output: &'a [String],
 }
 
 #[wasm_bindgen]